@@ -33,43 +33,22 @@ struct CreatePhoto {
     name: String,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Entity)]
 struct Photo {
+    #[entity(id)]
     id: i64,
     name: String,
 }
 
-impl Entity for Photo {
-    type Id = i64;
-
-    fn id(&self) -> &Self::Id {
-        &self.id
-    }
-
-    fn name() -> &'static str {
-        "photo"
-    }
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Entity)]
+#[entity(name = "User")]
 pub struct User {
+    #[entity(id)]
     pub id: String,
     pub email: String,
     pub password_hash: String,
 }
 
-impl Entity for User {
-    type Id = String;
-
-    fn id(&self) -> &Self::Id {
-        &self.id
-    }
-
-    fn name() -> &'static str {
-        "User"
-    }
-}
-
 use nimble_web::endpoint::route::EndpointRoute;
 
 struct ApiController;
@@ -0,0 +1,140 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, LitStr, Member, Type};
+
+pub(crate) struct EntityField {
+    pub(crate) member: Member,
+    pub(crate) ty: Type,
+}
+
+pub(crate) fn derive_entity(input: DeriveInput) -> syn::Result<TokenStream> {
+    let crate_path = crate::resolve_crate_path();
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut name: Option<LitStr> = None;
+    let mut plural: Option<LitStr> = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("entity"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                name = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("plural") {
+                plural = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("expected `name` or `plural`"));
+            }
+            Ok(())
+        })?;
+    }
+
+    let EntityField { member, ty } = id_field(&input)?;
+    let name = name.unwrap_or_else(|| LitStr::new(&snake_case(&ident.to_string()), ident.span()));
+    let plural_tokens = plural
+        .map(|plural| {
+            quote! {
+                fn plural_name() -> ::std::string::String {
+                    ::std::string::String::from(#plural)
+                }
+            }
+        })
+        .unwrap_or_default();
+
+    Ok(quote! {
+        impl #impl_generics #crate_path::entity::entity::Entity for #ident #ty_generics #where_clause {
+            type Id = #ty;
+
+            fn id(&self) -> &Self::Id {
+                &self.#member
+            }
+
+            fn name() -> &'static str {
+                #name
+            }
+
+            #plural_tokens
+        }
+    })
+}
+
+pub(crate) fn id_field(input: &DeriveInput) -> syn::Result<EntityField> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "entities must be structs",
+            ));
+        }
+    };
+
+    let mut found: Option<EntityField> = None;
+    for (index, field) in fields.iter().enumerate() {
+        let mut is_id = false;
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("entity"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("id") {
+                    is_id = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `id`"))
+                }
+            })?;
+        }
+        if !is_id {
+            continue;
+        }
+        if found.is_some() {
+            return Err(syn::Error::new(
+                field.span(),
+                "only one field can be marked with #[entity(id)]",
+            ));
+        }
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(index.into()),
+        };
+        found = Some(EntityField {
+            member,
+            ty: field.ty.clone(),
+        });
+    }
+
+    found.ok_or_else(|| {
+        syn::Error::new(
+            input.ident.span(),
+            format!(
+                "`{}` has no id field; mark one field with #[entity(id)]",
+                input.ident
+            ),
+        )
+    })
+}
+
+// A run of capitals is one word, so `HTTPLog` becomes `http_log` and `UserID` `user_id`.
+fn snake_case(value: &str) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    let mut result = String::new();
+    for (index, ch) in chars.iter().enumerate() {
+        if ch.is_uppercase() && index > 0 {
+            let prev = chars[index - 1];
+            let next_is_lower = chars.get(index + 1).is_some_and(|c| c.is_lowercase());
+            if prev.is_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_uppercase() && next_is_lower)
+            {
+                result.push('_');
+            }
+        }
+        result.extend(ch.to_lowercase());
+    }
+    result
+}
@@ -0,0 +1,157 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+use syn::{Fields, ItemStruct, LitStr, Member, Token};
+
+pub(crate) struct InjectableArgs {
+    lifetime: Option<LitStr>,
+}
+
+impl Parse for InjectableArgs {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let mut lifetime = None;
+        while !input.is_empty() {
+            let ident: syn::Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            match ident.to_string().as_str() {
+                "lifetime" => {
+                    if lifetime.is_some() {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            "lifetime provided more than once",
+                        ));
+                    }
+                    lifetime = Some(input.parse::<LitStr>()?);
+                }
+                _ => return Err(syn::Error::new(ident.span(), "expected `lifetime`")),
+            }
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(Self { lifetime })
+    }
+}
+
+pub(crate) fn generate_injectable(
+    args: InjectableArgs,
+    mut item: ItemStruct,
+) -> syn::Result<TokenStream> {
+    if !item.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            item.generics.span(),
+            "injectable services cannot be generic",
+        ));
+    }
+
+    let crate_path = crate::resolve_crate_path();
+    let di = quote! { #crate_path::di };
+    let lifetime = match args.lifetime.as_ref().map(|lifetime| lifetime.value()) {
+        None => quote! { Transient },
+        Some(value) => match value.to_ascii_lowercase().as_str() {
+            "singleton" => quote! { Singleton },
+            "scoped" => quote! { Scoped },
+            "transient" => quote! { Transient },
+            _ => {
+                return Err(syn::Error::new(
+                    args.lifetime.span(),
+                    "lifetime must be \"singleton\", \"scoped\" or \"transient\"",
+                ));
+            }
+        },
+    };
+
+    let mut dependencies = Vec::new();
+    let mut initializers = Vec::new();
+    let fields = match &mut item.fields {
+        Fields::Named(fields) => fields.named.iter_mut().collect::<Vec<_>>(),
+        Fields::Unnamed(fields) => fields.unnamed.iter_mut().collect(),
+        Fields::Unit => Vec::new(),
+    };
+    for (index, field) in fields.into_iter().enumerate() {
+        let (member, field_name) = match &field.ident {
+            Some(ident) => (
+                Member::Named(ident.clone()),
+                ident.to_string().trim_start_matches("r#").to_string(),
+            ),
+            None => (Member::Unnamed(index.into()), index.to_string()),
+        };
+
+        let mut use_default = false;
+        let mut key: Option<LitStr> = None;
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("inject"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("default") {
+                    use_default = true;
+                    Ok(())
+                } else if meta.path.is_ident("key") {
+                    key = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `default` or `key = \"...\"`"))
+                }
+            })?;
+        }
+        field.attrs.retain(|attr| !attr.path().is_ident("inject"));
+
+        let resolve = |service_ty: &syn::Type| match &key {
+            Some(key) => quote! { provider.resolve_keyed::<#service_ty>(#key) },
+            None => quote! { provider.resolve::<#service_ty>() },
+        };
+        let dependency = |service_ty: &syn::Type, kind: TokenStream| match &key {
+            Some(key) => quote! {
+                #di::injectable::Dependency::#kind::<#service_ty>(#field_name).with_key(#key)
+            },
+            None => quote! { #di::injectable::Dependency::#kind::<#service_ty>(#field_name) },
+        };
+
+        if use_default {
+            initializers.push(quote! { #member: ::std::default::Default::default() });
+        } else if let Some(service_ty) = crate::option_inner(&field.ty).and_then(crate::arc_inner) {
+            dependencies.push(dependency(service_ty, quote! { optional }));
+            let resolve = resolve(service_ty);
+            initializers.push(quote! { #member: #resolve });
+        } else if let Some(service_ty) = crate::arc_inner(&field.ty) {
+            dependencies.push(dependency(service_ty, quote! { required }));
+            let resolve = resolve(service_ty);
+            initializers.push(quote! {
+                #member: #resolve.ok_or_else(|| {
+                    #di::injectable::MissingDependency::new::<Self, #service_ty>(#field_name)
+                })?
+            });
+        } else {
+            return Err(syn::Error::new(
+                field.ty.span(),
+                "injectable fields must be `Arc<T>` or `Option<Arc<T>>`; mark other fields with #[inject(default)]",
+            ));
+        }
+    }
+
+    let ident = &item.ident;
+    Ok(quote! {
+        #item
+
+        impl #di::injectable::Injectable for #ident {
+            const LIFETIME: #di::ServiceLifetime = #di::ServiceLifetime::#lifetime;
+
+            fn dependencies() -> ::std::vec::Vec<#di::injectable::Dependency> {
+                ::std::vec![#(#dependencies),*]
+            }
+
+            fn create(
+                provider: &#di::ServiceProvider,
+            ) -> ::std::result::Result<Self, #di::injectable::MissingDependency> {
+                ::std::result::Result::Ok(Self { #(#initializers),* })
+            }
+        }
+
+        #crate_path::inventory::submit! {
+            #di::injectable::RegisteredInjectable::of::<#ident>()
+        }
+    })
+}
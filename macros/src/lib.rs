@@ -1,3 +1,10 @@
+mod entity;
+mod injectable;
+mod mongo;
+mod postgres;
+mod serde_attrs;
+mod validate;
+
 use proc_macro::TokenStream;
 use proc_macro2::Span;
 use proc_macro_crate::{crate_name, FoundCrate};
@@ -6,7 +13,9 @@ use syn::parse::Parse;
 use syn::parse::ParseStream;
 use syn::parse_macro_input;
 use syn::spanned::Spanned;
-use syn::{Expr, ItemImpl, LitStr, Token};
+use syn::{
+    Expr, FnArg, GenericArgument, Item, ItemFn, ItemImpl, LitStr, Pat, PathArguments, Token, Type,
+};
 
 #[proc_macro_attribute]
 pub fn get(attr: TokenStream, item: TokenStream) -> TokenStream {
@@ -28,42 +37,180 @@ pub fn delete(attr: TokenStream, item: TokenStream) -> TokenStream {
     expand_route(Method::Delete, attr, item)
 }
 
+#[proc_macro_attribute]
+pub fn patch(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_route(Method::Patch, attr, item)
+}
+
+#[proc_macro_attribute]
+pub fn route(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut args = parse_macro_input!(attr as RouteArgs);
+    let item = parse_macro_input!(item as Item);
+    let method = match args.method.take() {
+        Some(method) => Method::from_literal(&method),
+        None => Err(syn::Error::new(
+            Span::call_site(),
+            "#[route] requires method = \"...\" (e.g. method = \"PATCH\")",
+        )),
+    };
+    match method.and_then(|method| generate_route(method, args, item)) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[proc_macro_attribute]
+pub fn controller(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as ControllerArgs);
+    let item_impl = parse_macro_input!(item as ItemImpl);
+    match generate_controller(args, item_impl) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[proc_macro_attribute]
+pub fn injectable(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as injectable::InjectableArgs);
+    let item = parse_macro_input!(item as syn::ItemStruct);
+    match injectable::generate_injectable(args, item) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[proc_macro_derive(Entity, attributes(entity))]
+pub fn derive_entity(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as syn::DeriveInput);
+    match entity::derive_entity(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[proc_macro_derive(PostgresEntity, attributes(entity, column))]
+pub fn derive_postgres_entity(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as syn::DeriveInput);
+    match postgres::derive_postgres_entity(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[proc_macro_derive(MongoEntity, attributes(entity, index))]
+pub fn derive_mongo_entity(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as syn::DeriveInput);
+    match mongo::derive_mongo_entity(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[proc_macro_derive(Validate, attributes(validate))]
+pub fn derive_validate(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as syn::DeriveInput);
+    match validate::derive_validate(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
 enum Method {
     Get,
     Post,
     Put,
     Delete,
+    Patch,
+    Custom(LitStr),
 }
 
 impl Method {
-    fn builder_ident(&self) -> proc_macro2::Ident {
+    fn from_literal(method: &LitStr) -> syn::Result<Self> {
+        let value = method.value().to_ascii_uppercase();
+        if value.is_empty()
+            || !value
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c))
+        {
+            return Err(syn::Error::new(
+                method.span(),
+                "method must be a valid HTTP method token such as \"PATCH\"",
+            ));
+        }
+
+        Ok(match value.as_str() {
+            "GET" => Method::Get,
+            "POST" => Method::Post,
+            "PUT" => Method::Put,
+            "DELETE" => Method::Delete,
+            "PATCH" => Method::Patch,
+            _ => Method::Custom(LitStr::new(&value, method.span())),
+        })
+    }
+
+    fn builder_tokens(
+        &self,
+        crate_path: &syn::Path,
+        path: &LitStr,
+        handler: &proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
         let name = match self {
             Method::Get => "get",
             Method::Post => "post",
             Method::Put => "put",
             Method::Delete => "delete",
+            Method::Patch => "patch",
+            Method::Custom(method) => {
+                return quote! {
+                    #crate_path::endpoint::route::RouteBuilder::new(#method, #path, #handler)
+                };
+            }
         };
-        format_ident!("{}", name)
+        let builder_ident = format_ident!("{}", name);
+        quote! { #crate_path::endpoint::route::EndpointRoute::#builder_ident(#path, #handler) }
     }
 }
 
 fn expand_route(method: Method, attr: TokenStream, item: TokenStream) -> TokenStream {
     let args = parse_macro_input!(attr as RouteArgs);
-    let path_lit = args.path;
-    let policy_expr = args.policy;
-    let item_impl = parse_macro_input!(item as ItemImpl);
-    match generate_impl(method, path_lit, policy_expr, item_impl) {
+    let item = parse_macro_input!(item as Item);
+    if let Some(method) = args.method.as_ref() {
+        return syn::Error::new(
+            method.span(),
+            "`method` is only supported by the #[route] attribute",
+        )
+        .to_compile_error()
+        .into();
+    }
+    match generate_route(method, args, item) {
         Ok(tokens) => tokens.into(),
         Err(err) => err.to_compile_error().into(),
     }
 }
 
+fn generate_route(
+    method: Method,
+    args: RouteArgs,
+    item: Item,
+) -> syn::Result<proc_macro2::TokenStream> {
+    match item {
+        Item::Impl(item_impl) => generate_impl(method, args, item_impl),
+        Item::Fn(item_fn) => generate_fn(method, args, item_fn),
+        other => Err(syn::Error::new(
+            other.span(),
+            "route attributes can only be applied to an `impl HttpHandler` block or a function",
+        )),
+    }
+}
+
 fn generate_impl(
     method: Method,
-    path: LitStr,
-    policy: Option<Expr>,
+    args: RouteArgs,
     item_impl: ItemImpl,
 ) -> syn::Result<proc_macro2::TokenStream> {
+    let RouteArgs {
+        path, policy, name, ..
+    } = args;
     let handler_ty = item_impl.self_ty.clone();
     let handler_expr = match handler_ty.as_ref() {
         syn::Type::Path(type_path) => {
@@ -79,18 +226,55 @@ fn generate_impl(
     };
 
     let crate_path = resolve_crate_path();
-    let builder_ident = method.builder_ident();
+    let template_params = template_params(&path)?;
+    let generics = item_impl.generics.clone();
+    let registration = route_registration(
+        &crate_path,
+        &method,
+        RouteArgs {
+            path,
+            policy,
+            name,
+            method: None,
+        },
+        &template_params,
+        &handler_ty,
+        &handler_expr,
+        &generics,
+    );
+    Ok(quote! {
+        #item_impl
+
+        #registration
+    })
+}
+
+fn route_registration(
+    crate_path: &syn::Path,
+    method: &Method,
+    args: RouteArgs,
+    template_params: &[String],
+    handler_ty: &Type,
+    handler_expr: &proc_macro2::TokenStream,
+    generics: &syn::Generics,
+) -> proc_macro2::TokenStream {
+    let RouteArgs {
+        path, policy, name, ..
+    } = args;
+    let builder_tokens = method.builder_tokens(crate_path, &path, handler_expr);
     let policy_tokens = policy
         .map(|policy_expr| quote! { .with_policy(#policy_expr) })
         .unwrap_or_default();
-    let generics = item_impl.generics.clone();
+    let name_tokens = name
+        .map(|name| quote! { .with_name(#name) })
+        .unwrap_or_default();
     let (impl_generics, _, where_clause) = generics.split_for_impl();
-    let tokens = quote! {
-        #item_impl
-
+    quote! {
         impl #impl_generics #crate_path::controller::route::HttpRoute for #handler_ty #where_clause {
+            const PARAMS: &'static [&'static str] = &[#(#template_params),*];
+
             fn route() -> #crate_path::endpoint::route::RouteBuilder {
-                #crate_path::endpoint::route::EndpointRoute::#builder_ident(#path, #handler_expr)#policy_tokens
+                #builder_tokens #policy_tokens #name_tokens
             }
         }
 
@@ -99,13 +283,628 @@ fn generate_impl(
                 build: || <#handler_ty as #crate_path::controller::route::HttpRoute>::endpoint(),
             }
         }
+    }
+}
+
+enum ParamSource {
+    Context { mutable: bool },
+    Route(String),
+    Query(String),
+    Body,
+    Service(Option<String>),
+}
+
+const PARAM_ATTRIBUTES: [&str; 4] = ["param", "query", "body", "service"];
+
+fn generate_fn(
+    method: Method,
+    args: RouteArgs,
+    mut item_fn: ItemFn,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let sig = &item_fn.sig;
+    if !sig.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            sig.generics.span(),
+            "route functions cannot be generic",
+        ));
+    }
+
+    let crate_path = resolve_crate_path();
+    let template_params = template_params(&args.path)?;
+    let fn_ident = sig.ident.clone();
+    let is_async = sig.asyncness.is_some();
+    let handler_ident = format_ident!(
+        "{}Handler",
+        pascal_case(&fn_ident.to_string()),
+        span = fn_ident.span()
+    );
+
+    if let Some(receiver) = item_fn.sig.receiver() {
+        return Err(syn::Error::new(
+            receiver.span(),
+            "route functions cannot take `self`; use #[controller] for methods",
+        ));
+    }
+    let (bindings, call_args) = bind_inputs(
+        &crate_path,
+        item_fn
+            .sig
+            .inputs
+            .iter_mut()
+            .filter_map(|input| match input {
+                FnArg::Typed(pat_type) => Some(pat_type),
+                FnArg::Receiver(_) => None,
+            }),
+        &template_params,
+    )?;
+
+    let call = if is_async {
+        quote! { #fn_ident(#(#call_args),*).await }
+    } else {
+        quote! { #fn_ident(#(#call_args),*) }
+    };
+    let vis = &item_fn.vis;
+    let handler_ty: Type = syn::parse_quote!(#handler_ident);
+    let handler_expr = quote! { #handler_ident };
+    let registration = route_registration(
+        &crate_path,
+        &method,
+        args,
+        &template_params,
+        &handler_ty,
+        &handler_expr,
+        &syn::Generics::default(),
+    );
+
+    Ok(quote! {
+        #item_fn
+
+        #vis struct #handler_ident;
+
+        #[#crate_path::async_trait::async_trait]
+        impl #crate_path::endpoint::http_handler::HttpHandler for #handler_ident {
+            async fn invoke(
+                &self,
+                context: &mut #crate_path::http::context::HttpContext,
+            ) -> ::std::result::Result<
+                #crate_path::result::into_response::ResponseValue,
+                #crate_path::pipeline::pipeline::PipelineError,
+            > {
+                #(#bindings)*
+                let result = #call;
+                #crate_path::endpoint::binding::IntoHandlerResult::into_handler_result(result)
+            }
+        }
+
+        #registration
+    })
+}
+
+const ROUTE_ATTRIBUTES: [&str; 6] = ["get", "post", "put", "delete", "patch", "route"];
+
+fn generate_controller(
+    args: ControllerArgs,
+    mut item_impl: ItemImpl,
+) -> syn::Result<proc_macro2::TokenStream> {
+    if let Some((_, trait_path, _)) = &item_impl.trait_ {
+        return Err(syn::Error::new(
+            trait_path.span(),
+            "#[controller] must be applied to an inherent impl block",
+        ));
+    }
+    if !item_impl.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            item_impl.generics.span(),
+            "controllers cannot be generic",
+        ));
+    }
+    let controller_ty = item_impl.self_ty.clone();
+    let controller_name = match controller_ty.as_ref() {
+        Type::Path(type_path) if type_path.qself.is_none() => type_path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string())
+            .unwrap_or_default(),
+        other => {
+            return Err(syn::Error::new(
+                other.span(),
+                "#[controller] supports only concrete type paths",
+            ));
+        }
+    };
+
+    let crate_path = resolve_crate_path();
+    let ControllerArgs { prefix, policy } = args;
+    template_params(&prefix)?;
+    let controller_policy = policy
+        .map(|policy_expr| quote! { .with_policy(#policy_expr) })
+        .unwrap_or_default();
+
+    let mut handlers = Vec::new();
+    let mut routes = Vec::new();
+    for impl_item in item_impl.items.iter_mut() {
+        let syn::ImplItem::Fn(action) = impl_item else {
+            continue;
+        };
+        let Some(position) = action.attrs.iter().position(|attr| {
+            ROUTE_ATTRIBUTES
+                .iter()
+                .any(|name| last_segment_matches(attr.path(), name))
+        }) else {
+            continue;
+        };
+        let attr = action.attrs.remove(position);
+        if let Some(other) = action.attrs.iter().find(|attr| {
+            ROUTE_ATTRIBUTES
+                .iter()
+                .any(|name| last_segment_matches(attr.path(), name))
+        }) {
+            return Err(syn::Error::new(
+                other.span(),
+                "controller actions can have only one route attribute",
+            ));
+        }
+
+        let (method, args) = action_route(&attr)?;
+        let path = LitStr::new(
+            &join_paths(&prefix.value(), &args.path.value()),
+            args.path.span(),
+        );
+        let template_params = template_params(&path)?;
+
+        let sig = &action.sig;
+        if !sig.generics.params.is_empty() {
+            return Err(syn::Error::new(
+                sig.generics.span(),
+                "controller actions cannot be generic",
+            ));
+        }
+        let action_ident = sig.ident.clone();
+        let is_async = sig.asyncness.is_some();
+        let resolve_controller = match sig.receiver() {
+            None => None,
+            Some(receiver) if receiver.reference.is_some() && receiver.mutability.is_none() => {
+                Some(quote! { let controller = context.service::<#controller_ty>()?; })
+            }
+            Some(receiver) => {
+                return Err(syn::Error::new(
+                    receiver.span(),
+                    "controller actions must take `&self` or no receiver",
+                ));
+            }
+        };
+
+        let (bindings, call_args) = bind_inputs(
+            &crate_path,
+            action
+                .sig
+                .inputs
+                .iter_mut()
+                .filter_map(|input| match input {
+                    FnArg::Typed(pat_type) => Some(pat_type),
+                    FnArg::Receiver(_) => None,
+                }),
+            &template_params,
+        )?;
+
+        let call_target = if resolve_controller.is_some() {
+            quote! { controller.#action_ident }
+        } else {
+            quote! { <#controller_ty>::#action_ident }
+        };
+        let call = if is_async {
+            quote! { #call_target(#(#call_args),*).await }
+        } else {
+            quote! { #call_target(#(#call_args),*) }
+        };
+        let handler_ident = format_ident!(
+            "{}{}Handler",
+            controller_name,
+            pascal_case(&action_ident.to_string()),
+            span = action_ident.span()
+        );
+        handlers.push(quote! {
+            #[doc(hidden)]
+            struct #handler_ident;
+
+            #[#crate_path::async_trait::async_trait]
+            impl #crate_path::endpoint::http_handler::HttpHandler for #handler_ident {
+                async fn invoke(
+                    &self,
+                    context: &mut #crate_path::http::context::HttpContext,
+                ) -> ::std::result::Result<
+                    #crate_path::result::into_response::ResponseValue,
+                    #crate_path::pipeline::pipeline::PipelineError,
+                > {
+                    #resolve_controller
+                    #(#bindings)*
+                    let result = #call;
+                    #crate_path::endpoint::binding::IntoHandlerResult::into_handler_result(result)
+                }
+            }
+        });
+
+        let builder_tokens = method.builder_tokens(&crate_path, &path, &quote! { #handler_ident });
+        let policy_tokens = args
+            .policy
+            .map(|policy_expr| quote! { .with_policy(#policy_expr) })
+            .unwrap_or_default();
+        let name_tokens = args
+            .name
+            .map(|name| quote! { .with_name(#name) })
+            .unwrap_or_default();
+        routes.push(quote! {
+            #builder_tokens #controller_policy #policy_tokens #name_tokens .build()
+        });
+    }
+
+    Ok(quote! {
+        #item_impl
+
+        #(#handlers)*
+
+        impl #crate_path::controller::controller::Controller for #controller_ty {
+            fn routes() -> ::std::vec::Vec<#crate_path::endpoint::route::EndpointRoute> {
+                ::std::vec![#(#routes),*]
+            }
+        }
+    })
+}
+
+fn action_route(attr: &syn::Attribute) -> syn::Result<(Method, RouteArgs)> {
+    let kind = attr
+        .path()
+        .segments
+        .last()
+        .map(|segment| segment.ident.to_string())
+        .unwrap_or_default();
+    let mut args = match &attr.meta {
+        syn::Meta::Path(_) => RouteArgs {
+            path: LitStr::new("", attr.span()),
+            policy: None,
+            name: None,
+            method: None,
+        },
+        _ => attr.parse_args::<RouteArgs>()?,
+    };
+    let method = match kind.as_str() {
+        "get" => Method::Get,
+        "post" => Method::Post,
+        "put" => Method::Put,
+        "delete" => Method::Delete,
+        "patch" => Method::Patch,
+        _ => {
+            let method = args.method.take().ok_or_else(|| {
+                syn::Error::new(
+                    attr.span(),
+                    "#[route] requires method = \"...\" (e.g. method = \"PATCH\")",
+                )
+            })?;
+            return Ok((Method::from_literal(&method)?, args));
+        }
+    };
+    if let Some(method) = args.method.as_ref() {
+        return Err(syn::Error::new(
+            method.span(),
+            "`method` is only supported by the #[route] attribute",
+        ));
+    }
+    Ok((method, args))
+}
+
+fn last_segment_matches(path: &syn::Path, name: &str) -> bool {
+    path.segments
+        .last()
+        .is_some_and(|segment| segment.ident == name)
+}
+
+fn join_paths(prefix: &str, path: &str) -> String {
+    let prefix = prefix.trim_end_matches('/');
+    let path = path.trim_start_matches('/');
+    match (prefix.is_empty(), path.is_empty()) {
+        (true, true) => "/".to_string(),
+        (true, false) => format!("/{}", path),
+        (false, true) => prefix.to_string(),
+        (false, false) => format!("{}/{}", prefix, path),
+    }
+}
+
+fn bind_inputs<'a>(
+    crate_path: &syn::Path,
+    inputs: impl Iterator<Item = &'a mut syn::PatType>,
+    template_params: &[String],
+) -> syn::Result<(Vec<proc_macro2::TokenStream>, Vec<proc_macro2::TokenStream>)> {
+    let mut bindings = Vec::new();
+    let mut call_args = Vec::new();
+    let mut has_context = false;
+    for (index, pat_type) in inputs.enumerate() {
+        let param_name = match pat_type.pat.as_ref() {
+            Pat::Ident(pat_ident) => pat_ident
+                .ident
+                .to_string()
+                .trim_start_matches("r#")
+                .to_string(),
+            other => {
+                return Err(syn::Error::new(
+                    other.span(),
+                    "route function parameters must be simple identifiers",
+                ));
+            }
+        };
+
+        let source = param_source(pat_type, &param_name, template_params)?;
+        pat_type.attrs.retain(|attr| {
+            !PARAM_ATTRIBUTES
+                .iter()
+                .any(|name| attr.path().is_ident(name))
+        });
+
+        let ty = pat_type.ty.as_ref();
+        let arg_ident = format_ident!("__arg{}", index);
+        let binding = quote! { #crate_path::endpoint::binding };
+        let response_value = quote! { #crate_path::result::into_response::ResponseValue };
+        match source {
+            ParamSource::Context { mutable } => {
+                if has_context {
+                    return Err(syn::Error::new(
+                        pat_type.span(),
+                        "only one HttpContext parameter is allowed",
+                    ));
+                }
+                has_context = true;
+                call_args.push(if mutable {
+                    quote! { context }
+                } else {
+                    quote! { &*context }
+                });
+                continue;
+            }
+            ParamSource::Route(name) => {
+                let function = if option_inner(ty).is_some() {
+                    quote! { optional_route_param }
+                } else {
+                    quote! { route_param }
+                };
+                bindings.push(quote! {
+                    let #arg_ident: #ty = match #binding::#function(&*context, #name) {
+                        Ok(value) => value,
+                        Err(error) => return Ok(#response_value::new(error)),
+                    };
+                });
+            }
+            ParamSource::Query(name) => {
+                let function = if option_inner(ty).is_some() {
+                    quote! { optional_query_param }
+                } else {
+                    quote! { query_param }
+                };
+                bindings.push(quote! {
+                    let #arg_ident: #ty = match #binding::#function(&*context, #name) {
+                        Ok(value) => value,
+                        Err(error) => return Ok(#response_value::new(error)),
+                    };
+                });
+            }
+            ParamSource::Body => {
+                bindings.push(quote! {
+                    let #arg_ident: #ty = match #binding::body(&*context) {
+                        Ok(value) => value,
+                        Err(error) => return Ok(#response_value::new(error)),
+                    };
+                });
+            }
+            ParamSource::Service(key) => {
+                if let Some(service_ty) = option_inner(ty).and_then(arc_inner) {
+                    let resolve = match &key {
+                        Some(key) => quote! { resolve_keyed::<#service_ty>(#key) },
+                        None => quote! { resolve::<#service_ty>() },
+                    };
+                    bindings.push(quote! {
+                        let #arg_ident: #ty = context.services().#resolve;
+                    });
+                } else if let Some(service_ty) = arc_inner(ty) {
+                    let resolve = match &key {
+                        Some(key) => quote! { keyed_service::<#service_ty>(#key) },
+                        None => quote! { service::<#service_ty>() },
+                    };
+                    bindings.push(quote! {
+                        let #arg_ident: #ty = context.#resolve?;
+                    });
+                } else {
+                    return Err(syn::Error::new(
+                        ty.span(),
+                        "service parameters must be `Arc<T>` or `Option<Arc<T>>`",
+                    ));
+                }
+            }
+        }
+        call_args.push(quote! { #arg_ident });
+    }
+
+    Ok((bindings, call_args))
+}
+
+fn param_source(
+    pat_type: &syn::PatType,
+    param_name: &str,
+    template_params: &[String],
+) -> syn::Result<ParamSource> {
+    for attr in &pat_type.attrs {
+        let Some(kind) = PARAM_ATTRIBUTES
+            .iter()
+            .find(|name| attr.path().is_ident(name))
+        else {
+            continue;
+        };
+        let rename = match &attr.meta {
+            syn::Meta::Path(_) => None,
+            _ => Some(attr.parse_args::<LitStr>()?.value()),
+        };
+        if *kind == "service" {
+            return Ok(ParamSource::Service(rename));
+        }
+        let name = rename.unwrap_or_else(|| param_name.to_string());
+        return Ok(match *kind {
+            "param" => {
+                if !template_params.contains(&name) {
+                    return Err(syn::Error::new(
+                        attr.span(),
+                        format!("route template has no parameter named `{}`", name),
+                    ));
+                }
+                ParamSource::Route(name)
+            }
+            "query" => ParamSource::Query(name),
+            _ => ParamSource::Body,
+        });
+    }
+
+    if let Type::Reference(reference) = pat_type.ty.as_ref() {
+        if last_segment_is(&reference.elem, "HttpContext") {
+            return Ok(ParamSource::Context {
+                mutable: reference.mutability.is_some(),
+            });
+        }
+    }
+    if template_params.iter().any(|name| name == param_name) {
+        return Ok(ParamSource::Route(param_name.to_string()));
+    }
+    let ty = pat_type.ty.as_ref();
+    if arc_inner(ty).is_some() || option_inner(ty).and_then(arc_inner).is_some() {
+        return Ok(ParamSource::Service(None));
+    }
+    Ok(ParamSource::Query(param_name.to_string()))
+}
+
+fn template_params(path: &LitStr) -> syn::Result<Vec<String>> {
+    let value = path.value();
+    let error = |message: String| Err(syn::Error::new(path.span(), message));
+    if value.contains("//") {
+        return error(format!(
+            "route template `{}` contains an empty segment",
+            value
+        ));
+    }
+
+    let mut params: Vec<String> = Vec::new();
+    for segment in value.split('/') {
+        if let Some(name) = segment.strip_prefix('{') {
+            let Some(name) = name.strip_suffix('}') else {
+                if name.contains('}') {
+                    return error(format!(
+                        "route segment `{}` mixes a parameter with literal text; parameters must span a whole segment such as `{{id}}`",
+                        segment
+                    ));
+                }
+                return error(format!(
+                    "route parameter in `{}` is missing a closing `}}`; parameters must span a whole segment such as `{{id}}`",
+                    value
+                ));
+            };
+            if name.is_empty() {
+                return error(format!("route template `{}` has an empty parameter", value));
+            }
+            if name.contains(['{', '}']) {
+                return error(format!("route parameter `{{{}}}` has nested braces", name));
+            }
+            let mut chars = name.chars();
+            let valid = chars
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !valid {
+                return error(format!(
+                    "route parameter `{}` must start with a letter or `_` and contain only letters, digits and `_`",
+                    name
+                ));
+            }
+            if params.iter().any(|param| param == name) {
+                return error(format!(
+                    "duplicate route parameter `{}` in `{}`",
+                    name, value
+                ));
+            }
+            params.push(name.to_string());
+            continue;
+        }
+
+        if let Some(brace) = segment.chars().find(|c| matches!(c, '{' | '}')) {
+            return error(format!(
+                "unexpected `{}` in route segment `{}`; parameters must span a whole segment such as `{{id}}`",
+                brace, segment
+            ));
+        }
+        if let Some(invalid) = segment
+            .chars()
+            .find(|c| !c.is_ascii_alphanumeric() && !"-._~!$&'()*+,;=:@%".contains(*c))
+        {
+            return error(format!(
+                "invalid character `{}` in route template `{}`",
+                invalid.escape_default(),
+                value
+            ));
+        }
+    }
+    Ok(params)
+}
+
+fn last_segment_is(ty: &Type, name: &str) -> bool {
+    match ty {
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == name),
+        _ => false,
+    }
+}
+
+fn generic_inner<'a>(ty: &'a Type, name: &str) -> Option<&'a Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != name {
+        return None;
+    }
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
     };
-    Ok(tokens)
+    match arguments.args.first()? {
+        GenericArgument::Type(inner) => Some(inner),
+        _ => None,
+    }
+}
+
+fn option_inner(ty: &Type) -> Option<&Type> {
+    generic_inner(ty, "Option")
+}
+
+fn arc_inner(ty: &Type) -> Option<&Type> {
+    generic_inner(ty, "Arc")
+}
+
+fn pascal_case(value: &str) -> String {
+    value
+        .trim_start_matches("r#")
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect()
 }
 
 struct RouteArgs {
     path: LitStr,
     policy: Option<Expr>,
+    name: Option<LitStr>,
+    method: Option<LitStr>,
 }
 
 impl Parse for RouteArgs {
@@ -113,12 +912,14 @@ impl Parse for RouteArgs {
         if input.is_empty() {
             return Err(syn::Error::new(
                 Span::call_site(),
-                "route attribute requires a path literal (and optional policy = ...)",
+                "route attribute requires a path literal (and optional policy = ..., name = ...)",
             ));
         }
 
         let mut path: Option<LitStr> = None;
         let mut policy: Option<Expr> = None;
+        let mut name: Option<LitStr> = None;
+        let mut method: Option<LitStr> = None;
 
         if input.peek(syn::LitStr) {
             path = Some(input.parse()?);
@@ -150,8 +951,29 @@ impl Parse for RouteArgs {
                     }
                     policy = Some(input.parse::<Expr>()?);
                 }
+                "name" => {
+                    if name.is_some() {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            "name provided more than once",
+                        ));
+                    }
+                    name = Some(input.parse::<LitStr>()?);
+                }
+                "method" => {
+                    if method.is_some() {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            "method provided more than once",
+                        ));
+                    }
+                    method = Some(input.parse::<LitStr>()?);
+                }
                 _ => {
-                    return Err(syn::Error::new(ident.span(), "expected `path` or `policy`"));
+                    return Err(syn::Error::new(
+                        ident.span(),
+                        "expected `path`, `policy`, `name` or `method`",
+                    ));
                 }
             }
 
@@ -167,14 +989,84 @@ impl Parse for RouteArgs {
             )
         })?;
 
-        Ok(Self { path, policy })
+        Ok(Self {
+            path,
+            policy,
+            name,
+            method,
+        })
     }
 }
 
+struct ControllerArgs {
+    prefix: LitStr,
+    policy: Option<Expr>,
+}
+
+impl Parse for ControllerArgs {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let mut prefix: Option<LitStr> = None;
+        let mut policy: Option<Expr> = None;
+
+        if input.peek(syn::LitStr) {
+            prefix = Some(input.parse()?);
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        while !input.is_empty() {
+            let ident: syn::Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+
+            match ident.to_string().as_str() {
+                "prefix" => {
+                    if prefix.is_some() {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            "prefix provided more than once",
+                        ));
+                    }
+                    prefix = Some(input.parse::<LitStr>()?);
+                }
+                "policy" => {
+                    if policy.is_some() {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            "policy provided more than once",
+                        ));
+                    }
+                    policy = Some(input.parse::<Expr>()?);
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        ident.span(),
+                        "expected `prefix` or `policy`",
+                    ));
+                }
+            }
+
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(Self {
+            prefix: prefix.unwrap_or_else(|| LitStr::new("", Span::call_site())),
+            policy,
+        })
+    }
+}
+
+fn is_library_target() -> bool {
+    std::env::var("CARGO_CRATE_NAME").map_or(true, |name| name == "nimble_web")
+}
+
 fn resolve_crate_path() -> syn::Path {
     fn convert(found: FoundCrate) -> syn::Path {
         match found {
-            FoundCrate::Itself => syn::parse_quote!(crate),
+            FoundCrate::Itself if is_library_target() => syn::parse_quote!(crate),
+            FoundCrate::Itself => syn::parse_quote!(::nimble_web),
             FoundCrate::Name(name) => {
                 let ident = syn::Ident::new(&name, Span::call_site());
                 syn::parse_quote!(::#ident)
@@ -0,0 +1,206 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{Attribute, Data, DeriveInput, LitStr, Member, Token, Type};
+
+use crate::entity::{id_field, EntityField};
+use crate::serde_attrs::serialized_names;
+
+struct IndexDef {
+    keys: Vec<(String, bool)>,
+    unique: bool,
+    name: Option<LitStr>,
+}
+
+pub(crate) fn derive_mongo_entity(input: DeriveInput) -> syn::Result<TokenStream> {
+    let crate_path = crate::resolve_crate_path();
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let EntityField {
+        member: id_member,
+        ty: id_ty,
+    } = id_field(&input)?;
+
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(ident.span(), "entities must be structs"));
+    };
+    let names = serialized_names(&input.attrs, &data.fields)?;
+
+    let mut id_name = String::new();
+    let mut indexes = Vec::new();
+    for (index, field) in data.fields.iter().enumerate() {
+        let serialized = &names[index];
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(index.into()),
+        };
+        if member == id_member {
+            id_name = serialized.clone();
+        }
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("index"))
+        {
+            indexes.push(field_index(attr, serialized)?);
+        }
+    }
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("index"))
+    {
+        indexes.push(struct_index(attr)?);
+    }
+
+    let mongo = quote! { #crate_path::data::mongo };
+    let id_bson = id_bson_tokens(&crate_path, &id_ty)?;
+    let index_tokens = indexes.iter().map(|index| {
+        let keys = index.keys.iter().map(|(field, descending)| {
+            if *descending {
+                quote! { .descending(#field) }
+            } else {
+                quote! { .ascending(#field) }
+            }
+        });
+        let unique = index.unique.then(|| quote! { .unique() });
+        let name = index.name.as_ref().map(|name| quote! { .with_name(#name) });
+        quote! {
+            #mongo::index::MongoIndex::new() #(#keys)* #unique #name
+        }
+    });
+    let indexes_fn = if indexes.is_empty() {
+        TokenStream::new()
+    } else {
+        quote! {
+            fn indexes() -> ::std::vec::Vec<#mongo::index::MongoIndex> {
+                ::std::vec![#(#index_tokens),*]
+            }
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics #mongo::MongoEntity for #ident #ty_generics #where_clause {
+            fn id_field() -> &'static str {
+                #id_name
+            }
+
+            #id_bson
+
+            #indexes_fn
+        }
+    })
+}
+
+fn id_bson_tokens(crate_path: &syn::Path, ty: &Type) -> syn::Result<TokenStream> {
+    let bson = quote! { #crate_path::data::mongo::bson };
+    let data_error = quote! { #crate_path::data::provider::DataError };
+    let name = match ty {
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string())
+            .unwrap_or_default(),
+        _ => String::new(),
+    };
+    let conversion = match name.as_str() {
+        "ObjectId" => quote! { #bson::Bson::ObjectId(*id) },
+        "String" => quote! { #bson::Bson::String(id.clone()) },
+        "i32" => quote! { #bson::Bson::Int32(*id) },
+        "i8" | "i16" | "u8" | "u16" => quote! { #bson::Bson::Int32(*id as i32) },
+        "i64" => quote! { #bson::Bson::Int64(*id) },
+        "u32" => quote! { #bson::Bson::Int64(*id as i64) },
+        "u64" | "usize" | "u128" | "i128" => {
+            return Err(syn::Error::new(
+                ty.span(),
+                format!(
+                    "`{}` ids do not fit in a BSON integer; use `i64`, `String` or `ObjectId`",
+                    name
+                ),
+            ));
+        }
+        // Other types go through serde, which can fail at runtime (e.g. a nested u64).
+        _ => {
+            return Ok(quote! {
+                fn id_bson(id: &Self::Id) -> ::std::result::Result<#bson::Bson, #data_error> {
+                    #bson::to_bson(id).map_err(|err| {
+                        #data_error::InvalidQuery(::std::format!(
+                            "id cannot be converted to BSON: {}",
+                            err
+                        ))
+                    })
+                }
+            });
+        }
+    };
+    Ok(quote! {
+        fn id_bson(id: &Self::Id) -> ::std::result::Result<#bson::Bson, #data_error> {
+            ::std::result::Result::Ok(#conversion)
+        }
+    })
+}
+
+fn field_index(attr: &Attribute, field: &str) -> syn::Result<IndexDef> {
+    let mut index = IndexDef {
+        keys: vec![(field.to_string(), false)],
+        unique: false,
+        name: None,
+    };
+    if matches!(attr.meta, syn::Meta::Path(_)) {
+        return Ok(index);
+    }
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("unique") {
+            index.unique = true;
+        } else if meta.path.is_ident("descending") {
+            index.keys[0].1 = true;
+        } else if meta.path.is_ident("name") {
+            index.name = Some(meta.value()?.parse()?);
+        } else {
+            return Err(meta.error("expected `unique`, `descending` or `name`"));
+        }
+        Ok(())
+    })?;
+    Ok(index)
+}
+
+fn struct_index(attr: &Attribute) -> syn::Result<IndexDef> {
+    let mut index = IndexDef {
+        keys: Vec::new(),
+        unique: false,
+        name: None,
+    };
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("keys") {
+            let content;
+            syn::parenthesized!(content in meta.input);
+            for key in Punctuated::<LitStr, Token![,]>::parse_terminated(&content)? {
+                let value = key.value();
+                let (field, descending) = match value.strip_prefix('-') {
+                    Some(field) => (field.to_string(), true),
+                    None => (value.trim_start_matches('+').to_string(), false),
+                };
+                if field.is_empty() {
+                    return Err(syn::Error::new(key.span(), "index keys cannot be empty"));
+                }
+                index.keys.push((field, descending));
+            }
+        } else if meta.path.is_ident("unique") {
+            index.unique = true;
+        } else if meta.path.is_ident("name") {
+            index.name = Some(meta.value()?.parse()?);
+        } else {
+            return Err(meta.error("expected `keys(...)`, `unique` or `name`"));
+        }
+        Ok(())
+    })?;
+    if index.keys.is_empty() {
+        return Err(syn::Error::new(
+            attr.span(),
+            "struct-level indexes need keys(\"field\", \"-other\")",
+        ));
+    }
+    Ok(index)
+}
@@ -0,0 +1,419 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+use syn::{Attribute, Data, DeriveInput, Field, LitStr, Member, Token, Type};
+
+use crate::entity::{id_field, EntityField};
+use crate::serde_attrs::apply_rename_rule;
+
+struct ColumnAttrs {
+    name: Option<LitStr>,
+    column_type: Option<LitStr>,
+    unique: bool,
+    default: Option<LitStr>,
+    skip_update: bool,
+    skip: bool,
+}
+
+// The parts of `#[sqlx(...)]` that decide which columns `sqlx::FromRow` reads.
+#[derive(Default)]
+struct SqlxAttrs {
+    rename: Option<LitStr>,
+    rename_all: Option<LitStr>,
+    skip: bool,
+}
+
+struct Column {
+    name: String,
+    member: Member,
+    ty: Type,
+    attrs: ColumnAttrs,
+}
+
+pub(crate) fn derive_postgres_entity(input: DeriveInput) -> syn::Result<TokenStream> {
+    let crate_path = crate::resolve_crate_path();
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let EntityField {
+        member: id_member,
+        ty: id_ty,
+    } = id_field(&input)?;
+
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(ident.span(), "entities must be structs"));
+    };
+    let rename_all = sqlx_attrs(&input.attrs)?.rename_all;
+    let mut columns = Vec::new();
+    for (index, field) in data.fields.iter().enumerate() {
+        let attrs = column_attrs(field)?;
+        let sqlx = sqlx_attrs(&field.attrs)?;
+        if attrs.skip || sqlx.skip {
+            if field_member(field, index) == id_member {
+                return Err(syn::Error::new(
+                    field.span(),
+                    "the id field cannot be skipped",
+                ));
+            }
+            continue;
+        }
+        let member = field_member(field, index);
+        let name = match (&attrs.name, &sqlx.rename, &field.ident) {
+            (Some(name), Some(rename), _) if name.value() != rename.value() => {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!(
+                        "#[column(name = \"{}\")] conflicts with #[sqlx(rename = \"{}\")]",
+                        name.value(),
+                        rename.value()
+                    ),
+                ));
+            }
+            (Some(name), _, _) | (None, Some(name), _) => name.value(),
+            (None, None, Some(ident)) => {
+                let name = ident.to_string().trim_start_matches("r#").to_string();
+                match &rename_all {
+                    Some(rule) => apply_rename_rule("sqlx", &rule.value(), &name, rule.span())?,
+                    None => name,
+                }
+            }
+            (None, None, None) => {
+                return Err(syn::Error::new(
+                    field.span(),
+                    "tuple struct fields need #[column(name = \"...\")]",
+                ));
+            }
+        };
+        columns.push(Column {
+            name,
+            member,
+            ty: field.ty.clone(),
+            attrs,
+        });
+    }
+
+    let id_column = columns
+        .iter()
+        .find(|column| column.member == id_member)
+        .map(|column| column.name.clone())
+        .unwrap_or_default();
+    let id_value = value_tokens(&crate_path, &id_ty, quote! { (*id) })?;
+
+    let mut insert_columns = Vec::new();
+    let mut insert_values = Vec::new();
+    let mut update_columns = Vec::new();
+    let mut update_values = Vec::new();
+    let mut table_columns = Vec::new();
+    for column in &columns {
+        let member = &column.member;
+        let name = &column.name;
+        let value = value_tokens(&crate_path, &column.ty, quote! { self.#member })?;
+        insert_columns.push(name.clone());
+        insert_values.push(value.clone());
+        if column.member != id_member && !column.attrs.skip_update {
+            update_columns.push(name.clone());
+            update_values.push(value);
+        }
+        table_columns.push(column_def(&crate_path, column, column.member == id_member)?);
+    }
+
+    Ok(quote! {
+        impl #impl_generics #crate_path::data::postgres::PostgresEntity for #ident #ty_generics #where_clause {
+            fn id_column() -> &'static str {
+                #id_column
+            }
+
+            fn id_value(id: &Self::Id) -> #crate_path::data::query::Value {
+                #id_value
+            }
+
+            fn insert_columns() -> &'static [&'static str] {
+                &[#(#insert_columns),*]
+            }
+
+            fn insert_values(&self) -> ::std::vec::Vec<#crate_path::data::query::Value> {
+                ::std::vec![#(#insert_values),*]
+            }
+
+            fn update_columns() -> &'static [&'static str] {
+                &[#(#update_columns),*]
+            }
+
+            fn update_values(&self) -> ::std::vec::Vec<#crate_path::data::query::Value> {
+                ::std::vec![#(#update_values),*]
+            }
+
+            fn table_columns() -> ::std::vec::Vec<#crate_path::data::schema::ColumnDef> {
+                ::std::vec![#(#table_columns),*]
+            }
+        }
+    })
+}
+
+fn field_member(field: &Field, index: usize) -> Member {
+    match &field.ident {
+        Some(ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(index.into()),
+    }
+}
+
+fn column_attrs(field: &Field) -> syn::Result<ColumnAttrs> {
+    let mut attrs = ColumnAttrs {
+        name: None,
+        column_type: None,
+        unique: false,
+        default: None,
+        skip_update: false,
+        skip: false,
+    };
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("column"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                attrs.name = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("type") {
+                attrs.column_type = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("default") {
+                attrs.default = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("unique") {
+                attrs.unique = true;
+            } else if meta.path.is_ident("skip_update") {
+                attrs.skip_update = true;
+            } else if meta.path.is_ident("skip") {
+                attrs.skip = true;
+            } else {
+                return Err(meta.error(
+                    "expected `name`, `type`, `unique`, `default`, `skip_update` or `skip`",
+                ));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(attrs)
+}
+
+fn sqlx_attrs(attrs: &[Attribute]) -> syn::Result<SqlxAttrs> {
+    let mut sqlx = SqlxAttrs::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("sqlx")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                sqlx.rename = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("rename_all") {
+                sqlx.rename_all = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("skip") {
+                sqlx.skip = true;
+            } else if meta.path.is_ident("flatten") {
+                return Err(meta.error("#[sqlx(flatten)] is not supported by PostgresEntity"));
+            } else if meta.input.peek(Token![=]) {
+                let _ = meta.value()?.parse::<syn::Expr>()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                let _ = content.parse::<TokenStream>()?;
+            }
+            Ok(())
+        })?;
+    }
+    Ok(sqlx)
+}
+
+fn column_def(crate_path: &syn::Path, column: &Column, is_id: bool) -> syn::Result<TokenStream> {
+    let name = &column.name;
+    let (inner, nullable) = match crate::option_inner(&column.ty) {
+        Some(inner) => (inner, true),
+        None => (&column.ty, false),
+    };
+    let column_type = match &column.attrs.column_type {
+        Some(column_type) => explicit_column_type(crate_path, column_type),
+        None => {
+            let kind = type_kind(inner).ok_or_else(|| unsupported(inner))?;
+            inferred_column_type(crate_path, kind)
+        }
+    };
+
+    let mut tokens = quote! {
+        #crate_path::data::schema::ColumnDef::new(#name, #column_type)
+    };
+    if is_id {
+        tokens.extend(quote! { .primary_key() });
+    } else if !nullable {
+        tokens.extend(quote! { .not_null() });
+    }
+    if column.attrs.unique {
+        tokens.extend(quote! { .unique() });
+    }
+    if let Some(default) = &column.attrs.default {
+        tokens.extend(quote! { .default(#default) });
+    }
+    Ok(tokens)
+}
+
+fn explicit_column_type(crate_path: &syn::Path, column_type: &LitStr) -> TokenStream {
+    let schema = quote! { #crate_path::data::schema::ColumnType };
+    let value = column_type.value();
+    let normalized = value.trim().to_ascii_lowercase();
+    if let Some(length) = normalized
+        .strip_prefix("varchar(")
+        .and_then(|rest| rest.strip_suffix(')'))
+        .and_then(|length| length.trim().parse::<u32>().ok())
+    {
+        return quote! { #schema::Varchar(#length) };
+    }
+    match normalized.as_str() {
+        "boolean" | "bool" => quote! { #schema::Boolean },
+        "integer" | "int" | "int4" => quote! { #schema::Integer },
+        "bigint" | "int8" => quote! { #schema::BigInt },
+        "real" | "float" | "float4" => quote! { #schema::Float },
+        "double precision" | "double" | "float8" => quote! { #schema::Double },
+        "text" => quote! { #schema::Text },
+        "bytea" | "bytes" => quote! { #schema::Bytes },
+        "timestamp" | "timestamptz" | "timestamp with time zone" => {
+            quote! { #schema::Timestamp }
+        }
+        "uuid" => quote! { #schema::Uuid },
+        "json" | "jsonb" => quote! { #schema::Json },
+        _ => quote! { #schema::Custom(#column_type) },
+    }
+}
+
+#[derive(Clone, Copy)]
+enum TypeKind {
+    Bool,
+    I8,
+    I16,
+    I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+    F32,
+    F64,
+    String,
+    Uuid,
+    DateTime,
+    Date,
+    Bytes,
+    StringArray,
+}
+
+fn type_kind(ty: &Type) -> Option<TypeKind> {
+    if let Some(inner) = crate::generic_inner(ty, "Vec") {
+        return if crate::last_segment_is(inner, "u8") {
+            Some(TypeKind::Bytes)
+        } else if crate::last_segment_is(inner, "String") {
+            Some(TypeKind::StringArray)
+        } else {
+            None
+        };
+    }
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    Some(match segment.ident.to_string().as_str() {
+        "bool" => TypeKind::Bool,
+        "i8" => TypeKind::I8,
+        "i16" => TypeKind::I16,
+        "i32" => TypeKind::I32,
+        "i64" => TypeKind::I64,
+        "u8" => TypeKind::U8,
+        "u16" => TypeKind::U16,
+        "u32" => TypeKind::U32,
+        "u64" => TypeKind::U64,
+        "f32" => TypeKind::F32,
+        "f64" => TypeKind::F64,
+        "String" => TypeKind::String,
+        "Uuid" => TypeKind::Uuid,
+        "DateTime" => TypeKind::DateTime,
+        "NaiveDate" => TypeKind::Date,
+        _ => return None,
+    })
+}
+
+fn inferred_column_type(crate_path: &syn::Path, kind: TypeKind) -> TokenStream {
+    let schema = quote! { #crate_path::data::schema::ColumnType };
+    match kind {
+        TypeKind::Bool => quote! { #schema::Boolean },
+        TypeKind::I8 | TypeKind::I16 | TypeKind::I32 | TypeKind::U8 | TypeKind::U16 => {
+            quote! { #schema::Integer }
+        }
+        TypeKind::I64 | TypeKind::U32 | TypeKind::U64 => quote! { #schema::BigInt },
+        TypeKind::F32 => quote! { #schema::Float },
+        TypeKind::F64 => quote! { #schema::Double },
+        TypeKind::String => quote! { #schema::Text },
+        TypeKind::Uuid => quote! { #schema::Uuid },
+        TypeKind::DateTime => quote! { #schema::Timestamp },
+        TypeKind::Date => quote! { #schema::Custom("DATE") },
+        TypeKind::Bytes => quote! { #schema::Bytes },
+        TypeKind::StringArray => quote! { #schema::Custom("TEXT[]") },
+    }
+}
+
+fn value_tokens(
+    crate_path: &syn::Path,
+    ty: &Type,
+    access: TokenStream,
+) -> syn::Result<TokenStream> {
+    if let Some(inner) = crate::option_inner(ty) {
+        let kind = type_kind(inner).ok_or_else(|| unsupported(inner))?;
+        let (builder_fn, by_ref) = match kind {
+            TypeKind::Bool => ("optional_bool", false),
+            TypeKind::I8 => ("optional_i8", false),
+            TypeKind::I16 => ("optional_i16", false),
+            TypeKind::I32 => ("optional_i32", false),
+            TypeKind::I64 => ("optional_i64", false),
+            TypeKind::U8 => ("optional_u8", false),
+            TypeKind::U16 => ("optional_u16", false),
+            TypeKind::U32 => ("optional_u32", false),
+            TypeKind::U64 => ("optional_u64", false),
+            TypeKind::F32 => ("optional_f32", false),
+            TypeKind::F64 => ("optional_f64", false),
+            TypeKind::String => ("optional_string", true),
+            TypeKind::Uuid => ("optional_uuid", false),
+            TypeKind::DateTime => ("optional_datetime", true),
+            TypeKind::Date => ("optional_date", true),
+            TypeKind::Bytes => ("optional_bytes", true),
+            TypeKind::StringArray => ("optional_string_array", true),
+        };
+        let builder_fn = format_ident!("{}", builder_fn);
+        let argument = if by_ref {
+            quote! { &#access }
+        } else {
+            access
+        };
+        return Ok(quote! {
+            #crate_path::data::postgres::value_builder::PostgresValueBuilder::#builder_fn(#argument)
+        });
+    }
+
+    let value = quote! { #crate_path::data::query::Value };
+    let kind = type_kind(ty).ok_or_else(|| unsupported(ty))?;
+    Ok(match kind {
+        TypeKind::Bool => quote! { #value::Bool(#access) },
+        TypeKind::I8 | TypeKind::I32 => quote! { #value::Int(#access as i64) },
+        TypeKind::I16 => quote! { #value::I16(#access) },
+        TypeKind::I64 => quote! { #value::Int(#access) },
+        TypeKind::U8 | TypeKind::U32 => quote! { #value::UInt(#access as u64) },
+        TypeKind::U16 => quote! { #value::U16(#access) },
+        TypeKind::U64 => quote! { #value::UInt(#access) },
+        TypeKind::F32 => quote! { #value::Float(#access as f64) },
+        TypeKind::F64 => quote! { #value::Float(#access) },
+        TypeKind::String => quote! { #value::String(#access.clone()) },
+        TypeKind::Uuid => quote! { #value::Uuid(#access) },
+        TypeKind::DateTime => quote! { #value::DateTime(#access) },
+        TypeKind::Date => quote! { #value::Date(#access) },
+        TypeKind::Bytes => quote! { #value::Bytes(#access.clone()) },
+        TypeKind::StringArray => quote! { #value::StringArray(#access.clone()) },
+    })
+}
+
+fn unsupported(ty: &Type) -> syn::Error {
+    syn::Error::new(
+        ty.span(),
+        "unsupported column type; use bool, integers, floats, String, Uuid, DateTime<Utc>, \
+         NaiveDate, Vec<u8>, Vec<String> (optionally wrapped in Option) or #[column(skip)]",
+    )
+}
@@ -0,0 +1,103 @@
+use proc_macro2::{Span, TokenStream};
+use syn::spanned::Spanned;
+use syn::{Attribute, Fields, LitStr, Token};
+
+pub(crate) fn serialized_names(attrs: &[Attribute], fields: &Fields) -> syn::Result<Vec<String>> {
+    let rename_all = serde_rename(attrs, "rename_all")?;
+    let span = attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("serde"))
+        .map(|attr| attr.span())
+        .unwrap_or_else(Span::call_site);
+
+    let mut names = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let name = match serde_rename(&field.attrs, "rename")? {
+            Some(name) => name,
+            None => match &field.ident {
+                Some(ident) => {
+                    let name = ident.to_string().trim_start_matches("r#").to_string();
+                    match &rename_all {
+                        Some(rule) => apply_rename_rule("serde", rule, &name, span)?,
+                        None => name,
+                    }
+                }
+                None => index.to_string(),
+            },
+        };
+        names.push(name);
+    }
+    Ok(names)
+}
+
+fn serde_rename(attrs: &[Attribute], key: &str) -> syn::Result<Option<String>> {
+    let mut value = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(key) {
+                if meta.input.peek(Token![=]) {
+                    value = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else {
+                    meta.parse_nested_meta(|inner| {
+                        if inner.path.is_ident("serialize") {
+                            value = Some(inner.value()?.parse::<LitStr>()?.value());
+                        } else {
+                            let _ = inner.value()?.parse::<LitStr>()?;
+                        }
+                        Ok(())
+                    })?;
+                }
+            } else if meta.input.peek(Token![=]) {
+                let _ = meta.value()?.parse::<syn::Expr>()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                let _ = content.parse::<TokenStream>()?;
+            }
+            Ok(())
+        })?;
+    }
+    Ok(value)
+}
+
+pub(crate) fn apply_rename_rule(
+    attr: &str,
+    rule: &str,
+    field: &str,
+    span: Span,
+) -> syn::Result<String> {
+    let words: Vec<&str> = field.split('_').filter(|word| !word.is_empty()).collect();
+    let capitalize = |word: &str| {
+        let mut chars = word.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+            None => String::new(),
+        }
+    };
+    Ok(match rule {
+        "lowercase" => field.to_lowercase(),
+        "UPPERCASE" => field.to_uppercase(),
+        "snake_case" => field.to_string(),
+        "SCREAMING_SNAKE_CASE" => field.to_uppercase(),
+        "kebab-case" => field.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => field.replace('_', "-").to_uppercase(),
+        "PascalCase" => words.iter().map(|word| capitalize(word)).collect(),
+        "camelCase" => words
+            .iter()
+            .enumerate()
+            .map(|(index, word)| {
+                if index == 0 {
+                    word.to_string()
+                } else {
+                    capitalize(word)
+                }
+            })
+            .collect(),
+        _ => {
+            return Err(syn::Error::new(
+                span,
+                format!("unsupported {} rename_all rule `{}`", attr, rule),
+            ));
+        }
+    })
+}
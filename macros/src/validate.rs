@@ -0,0 +1,373 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::meta::ParseNestedMeta;
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, Expr, LitStr, Member, Type};
+
+use crate::serde_attrs::serialized_names;
+
+enum Rule {
+    Length {
+        min: Option<Expr>,
+        max: Option<Expr>,
+    },
+    Range {
+        min: Option<Expr>,
+        max: Option<Expr>,
+    },
+    Email,
+    Url,
+    Regex(LitStr),
+    Required,
+    Nested,
+    Custom(syn::Path),
+}
+
+struct FieldRule {
+    rule: Rule,
+    message: Option<LitStr>,
+}
+
+pub(crate) fn derive_validate(input: DeriveInput) -> syn::Result<TokenStream> {
+    let crate_path = crate::resolve_crate_path();
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            ident.span(),
+            "#[derive(Validate)] supports only structs",
+        ));
+    };
+    let names = serialized_names(&input.attrs, &data.fields)?;
+    let validation = quote! { #crate_path::validation };
+
+    let mut checks = Vec::new();
+    for (index, field) in data.fields.iter().enumerate() {
+        let mut rules = Vec::new();
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("validate"))
+        {
+            attr.parse_nested_meta(|meta| {
+                rules.push(parse_rule(&meta)?);
+                Ok(())
+            })?;
+        }
+        if rules.is_empty() {
+            continue;
+        }
+
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(index.into()),
+        };
+        let name = &names[index];
+        let option_inner = crate::option_inner(&field.ty);
+        let value_ty = option_inner.unwrap_or(&field.ty);
+
+        let mut outer = Vec::new();
+        let mut inner = Vec::new();
+        for field_rule in &rules {
+            if matches!(field_rule.rule, Rule::Required) {
+                let message = message_tokens(&field_rule.message, quote! { "is required" });
+                outer.push(quote! {
+                    if !#validation::rules::Required::is_present(&self.#member) {
+                        errors.push(#validation::FieldError::new(#name, &#message));
+                    }
+                });
+            } else {
+                inner.push(rule_tokens(&validation, name, value_ty, field_rule));
+            }
+        }
+
+        let inner = if inner.is_empty() {
+            TokenStream::new()
+        } else if option_inner.is_some() {
+            quote! {
+                if let ::std::option::Option::Some(value) = &self.#member {
+                    #(#inner)*
+                }
+            }
+        } else {
+            quote! {
+                {
+                    let value = &self.#member;
+                    #(#inner)*
+                }
+            }
+        };
+        checks.push(quote! {
+            #(#outer)*
+            #inner
+        });
+    }
+
+    Ok(quote! {
+        impl #impl_generics #validation::Validate for #ident #ty_generics #where_clause {
+            fn validate(&self) -> ::std::result::Result<(), #validation::ValidationError> {
+                let mut errors: ::std::vec::Vec<#validation::FieldError> = ::std::vec::Vec::new();
+                #(#checks)*
+                if errors.is_empty() {
+                    ::std::result::Result::Ok(())
+                } else {
+                    ::std::result::Result::Err(#validation::ValidationError::from_fields(errors))
+                }
+            }
+        }
+    })
+}
+
+fn parse_rule(meta: &ParseNestedMeta<'_>) -> syn::Result<FieldRule> {
+    let Some(kind) = meta.path.get_ident().map(|ident| ident.to_string()) else {
+        return Err(meta.error("expected a validation rule"));
+    };
+    let mut message = None;
+    let rule = match kind.as_str() {
+        "length" | "range" => {
+            let mut min = None;
+            let mut max = None;
+            let mut equal = None;
+            meta.parse_nested_meta(|inner| {
+                if inner.path.is_ident("min") {
+                    min = Some(inner.value()?.parse::<Expr>()?);
+                } else if inner.path.is_ident("max") {
+                    max = Some(inner.value()?.parse::<Expr>()?);
+                } else if inner.path.is_ident("equal") && kind == "length" {
+                    equal = Some(inner.value()?.parse::<Expr>()?);
+                } else if inner.path.is_ident("message") {
+                    message = Some(inner.value()?.parse::<LitStr>()?);
+                } else {
+                    return Err(inner.error("expected `min`, `max`, `equal` or `message`"));
+                }
+                Ok(())
+            })?;
+            if let Some(equal) = equal {
+                min = Some(equal.clone());
+                max = Some(equal);
+            }
+            if min.is_none() && max.is_none() {
+                return Err(meta.error(format!("`{}` needs `min` and/or `max`", kind)));
+            }
+            if kind == "length" {
+                Rule::Length { min, max }
+            } else {
+                Rule::Range { min, max }
+            }
+        }
+        "regex" => {
+            let pattern = if meta.input.peek(syn::Token![=]) {
+                Some(meta.value()?.parse::<LitStr>()?)
+            } else {
+                let mut pattern = None;
+                meta.parse_nested_meta(|inner| {
+                    if inner.path.is_ident("pattern") {
+                        pattern = Some(inner.value()?.parse::<LitStr>()?);
+                    } else if inner.path.is_ident("message") {
+                        message = Some(inner.value()?.parse::<LitStr>()?);
+                    } else {
+                        return Err(inner.error("expected `pattern` or `message`"));
+                    }
+                    Ok(())
+                })?;
+                pattern
+            };
+            let pattern = pattern.ok_or_else(|| meta.error("`regex` needs a pattern"))?;
+            if let Err(err) = regex::Regex::new(&pattern.value()) {
+                return Err(syn::Error::new(
+                    pattern.span(),
+                    format!("invalid regex: {}", err),
+                ));
+            }
+            Rule::Regex(pattern)
+        }
+        "custom" => {
+            let function = if meta.input.peek(syn::Token![=]) {
+                Some(parse_function(&meta.value()?.parse::<Expr>()?)?)
+            } else {
+                let mut function = None;
+                meta.parse_nested_meta(|inner| {
+                    if inner.path.is_ident("function") {
+                        function = Some(parse_function(&inner.value()?.parse::<Expr>()?)?);
+                    } else if inner.path.is_ident("message") {
+                        message = Some(inner.value()?.parse::<LitStr>()?);
+                    } else {
+                        return Err(inner.error("expected `function` or `message`"));
+                    }
+                    Ok(())
+                })?;
+                function
+            };
+            Rule::Custom(function.ok_or_else(|| meta.error("`custom` needs a function"))?)
+        }
+        "email" | "url" | "required" | "nested" => {
+            if meta.input.peek(syn::token::Paren) {
+                meta.parse_nested_meta(|inner| {
+                    if inner.path.is_ident("message") && kind != "nested" {
+                        message = Some(inner.value()?.parse::<LitStr>()?);
+                        Ok(())
+                    } else {
+                        Err(inner.error("expected `message`"))
+                    }
+                })?;
+            }
+            match kind.as_str() {
+                "email" => Rule::Email,
+                "url" => Rule::Url,
+                "required" => Rule::Required,
+                _ => Rule::Nested,
+            }
+        }
+        _ => {
+            return Err(meta.error(
+                "expected `length`, `range`, `email`, `url`, `regex`, `required`, `nested` or `custom`",
+            ));
+        }
+    };
+    Ok(FieldRule { rule, message })
+}
+
+fn parse_function(expr: &Expr) -> syn::Result<syn::Path> {
+    match expr {
+        Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(function),
+            ..
+        }) => function.parse(),
+        Expr::Path(path) => Ok(path.path.clone()),
+        other => Err(syn::Error::new(
+            other.span(),
+            "expected a function path such as `validate_slug` or \"validate_slug\"",
+        )),
+    }
+}
+
+fn rule_tokens(
+    validation: &TokenStream,
+    name: &str,
+    value_ty: &Type,
+    field_rule: &FieldRule,
+) -> TokenStream {
+    let push = |message: TokenStream| {
+        quote! { errors.push(#validation::FieldError::new(#name, &#message)); }
+    };
+    match &field_rule.rule {
+        Rule::Length { min, max } => {
+            let min = optional_tokens(min, quote! { as usize });
+            let max = optional_tokens(max, quote! { as usize });
+            let push = push(message_tokens(
+                &field_rule.message,
+                quote! { #validation::rules::length_message(#min, #max) },
+            ));
+            quote! {
+                if !#validation::rules::check_length(value, #min, #max) {
+                    #push
+                }
+            }
+        }
+        Rule::Range { min, max } => {
+            let lower = min
+                .as_ref()
+                .map(|min| quote! { *value < (#min) })
+                .unwrap_or_else(|| quote! { false });
+            let upper = max
+                .as_ref()
+                .map(|max| quote! { *value > (#max) })
+                .unwrap_or_else(|| quote! { false });
+            let min_text = optional_display(min);
+            let max_text = optional_display(max);
+            let push = push(message_tokens(
+                &field_rule.message,
+                quote! { #validation::rules::range_message(#min_text, #max_text) },
+            ));
+            quote! {
+                if #lower || #upper {
+                    #push
+                }
+            }
+        }
+        Rule::Email => {
+            let push = push(message_tokens(
+                &field_rule.message,
+                quote! { "must be a valid email address" },
+            ));
+            quote! {
+                if !#validation::rules::is_email(value) {
+                    #push
+                }
+            }
+        }
+        Rule::Url => {
+            let push = push(message_tokens(
+                &field_rule.message,
+                quote! { "must be a valid URL" },
+            ));
+            quote! {
+                if !#validation::rules::is_url(value) {
+                    #push
+                }
+            }
+        }
+        Rule::Regex(pattern) => {
+            let push = push(message_tokens(
+                &field_rule.message,
+                quote! { "has an invalid format" },
+            ));
+            quote! {
+                {
+                    static PATTERN: ::std::sync::OnceLock<#validation::rules::Regex> =
+                        ::std::sync::OnceLock::new();
+                    if !#validation::rules::matches_pattern(value, &PATTERN, #pattern) {
+                        #push
+                    }
+                }
+            }
+        }
+        Rule::Required => TokenStream::new(),
+        Rule::Nested => {
+            if crate::generic_inner(value_ty, "Vec").is_some() {
+                quote! {
+                    for (index, item) in value.iter().enumerate() {
+                        #validation::rules::nested(item, &format!("{}[{}]", #name, index), &mut errors);
+                    }
+                }
+            } else {
+                quote! { #validation::rules::nested(value, #name, &mut errors); }
+            }
+        }
+        Rule::Custom(function) => {
+            let message = match &field_rule.message {
+                Some(message) => quote! { #message },
+                None => quote! { error.message() },
+            };
+            quote! {
+                if let ::std::result::Result::Err(error) = #function(value) {
+                    errors.push(#validation::FieldError::new(#name, #message));
+                }
+            }
+        }
+    }
+}
+
+fn message_tokens(message: &Option<LitStr>, default: TokenStream) -> TokenStream {
+    match message {
+        Some(message) => quote! { #message },
+        None => default,
+    }
+}
+
+fn optional_tokens(value: &Option<Expr>, cast: TokenStream) -> TokenStream {
+    match value {
+        Some(value) => quote! { ::std::option::Option::Some((#value) #cast) },
+        None => quote! { ::std::option::Option::None },
+    }
+}
+
+fn optional_display(value: &Option<Expr>) -> TokenStream {
+    match value {
+        Some(value) => {
+            let text = value.to_token_stream().to_string();
+            quote! { ::std::option::Option::Some(::std::string::String::from(#text)) }
+        }
+        None => quote! { ::std::option::Option::None },
+    }
+}
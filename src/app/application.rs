@@ -10,13 +10,14 @@ use crate::background::in_memory_queue::InMemoryJobQueue;
 use crate::background::job_queue::JobQueue;
 use crate::background::runner::JobQueueRunner;
 use crate::config::Configuration;
-use crate::di::ServiceProvider;
+use crate::di::{InitializationError, ServiceProvider};
 use crate::http::context::HttpContext;
 use crate::http::request::HttpRequest;
 use crate::http::response::HttpResponse;
 use crate::http::response_body::ResponseBody;
 use crate::pipeline::pipeline::Pipeline;
 use crate::routing::default_router::DefaultRouter;
+use crate::routing::route_table::RouteTable;
 use crate::runtime::hyper_runtime::HyperRuntime;
 use crate::runtime::runtime::Runtime;
 
@@ -67,6 +68,7 @@ impl Application {
         shutdown: Pin<Box<dyn Future<Output = ()> + Send>>,
     ) -> Result<(), AppError> {
         let addr = self.parse_address()?;
+        self.initialize().await?;
         log::info!("Start application at {}", addr);
         let wants_random = addr.port() == 0;
 
@@ -87,10 +89,18 @@ impl Application {
             .await?;
         log::info!("Shutting down application");
         app.shutdown();
-        app.flush_jobs();
+        app.flush_jobs().await;
+        app.services.dispose().await;
         Ok(())
     }
 
+    pub async fn initialize(&self) -> Result<(), AppError> {
+        self.services
+            .initialize()
+            .await
+            .map_err(AppError::Initialization)
+    }
+
     pub fn shutdown(&self) {
         self.hosted_services.stop();
     }
@@ -107,13 +117,16 @@ impl Application {
         &self.router
     }
 
+    pub fn route_table(&self) -> Arc<RouteTable> {
+        self.services.resolve::<RouteTable>().unwrap_or_default()
+    }
+
     pub fn log_routes(&self) {
         self.router.log_routes();
     }
 
     pub(crate) fn create_context(&self, request: HttpRequest) -> HttpContext {
-        let services = self.services.clone();
-        HttpContext::new(request, services, self.config.clone())
+        HttpContext::with_scope(request, self.services.create_scope(), self.config.clone())
     }
 
     pub(crate) async fn handle_request_context(&self, context: &mut HttpContext) {
@@ -138,6 +151,7 @@ impl Application {
 
         let mut context = self.create_context(request);
         self.handle_request_context(&mut context).await;
+        context.scope().dispose().await;
         context.into_response()
     }
 
@@ -147,18 +161,18 @@ impl Application {
             .map_err(|_| AppError::InvalidAddress(self.address.clone()))
     }
 
-    fn flush_jobs(&self) {
+    async fn flush_jobs(&self) {
         let Some(queue) = self.job_queue.as_ref() else {
             return;
         };
 
         if let Some(in_memory) = queue.as_any().downcast_ref::<InMemoryJobQueue>() {
-            in_memory.run_all();
+            in_memory.run_all().await;
             return;
         }
 
         if let Some(runner) = queue.as_any().downcast_ref::<JobQueueRunner>() {
-            runner.run_pending_jobs();
+            runner.run_pending_jobs().await;
         }
     }
 
@@ -199,6 +213,7 @@ impl Application {
 #[derive(Debug)]
 pub enum AppError {
     InvalidAddress(String),
+    Initialization(InitializationError),
     Runtime(String),
 }
 
@@ -214,6 +229,7 @@ impl Display for AppError {
             AppError::InvalidAddress(address) => {
                 write!(f, "invalid address: {}", address)
             }
+            AppError::Initialization(err) => write!(f, "startup failed: {}", err),
             AppError::Runtime(message) => write!(f, "runtime error: {}", message),
         }
     }
@@ -1,9 +1,11 @@
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use std::future::Future;
 use std::hash::Hash;
 use std::path::Path;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::app::application::Application;
 use crate::background::config::JobQueueConfig;
@@ -11,12 +13,18 @@ use crate::background::hosted_service::{HostedService, HostedServiceHost};
 use crate::background::in_memory_queue::InMemoryJobQueue;
 use crate::background::job_queue::JobQueue;
 use crate::config::ConfigBuilder;
+use crate::config::{OptionsMonitor, ReloadableConfiguration};
 use crate::controller::controller::Controller;
 use crate::data::memory_repository::MemoryRepository;
 use crate::data::provider::DataProvider;
-use crate::di::{ServiceContainer, ServiceProvider};
+use crate::di::initializer::{InitializeError, ServiceInitializer};
+use crate::di::injectable::{self, Dependency};
+use crate::di::{Disposable, ServiceContainer, ServiceLifetime, ServiceProvider};
+use crate::endpoint::conflict::{RouteConflictReport, RouteOrigin};
+use crate::endpoint::group::RouteGroup;
 use crate::endpoint::http_handler::HttpHandler;
 use crate::endpoint::registry::EndpointRegistry;
+use crate::endpoint::route::RouteBuilder;
 use crate::entity::entity::Entity;
 use crate::entity::hooks::{DefaultEntityHooks, EntityHooks};
 use crate::entity::operation::{EntityOperation, OperationHandler};
@@ -26,10 +34,13 @@ use crate::middleware::routing::RoutingMiddleware;
 use crate::pipeline::middleware::{DynMiddleware, Middleware};
 use crate::pipeline::pipeline::Pipeline;
 use crate::routing::default_router::DefaultRouter;
+use crate::routing::link_generator::LinkGenerator;
+use crate::routing::route_table::{RouteTable, RouteTableHandler, DEFAULT_ROUTE_DIAGNOSTICS_PATH};
 use crate::routing::router::Router;
+use crate::routing::versioning::ApiVersioning;
 use crate::security::auth::AuthenticationMiddleware;
 use crate::security::policy::{AuthorizationMiddleware, Policy};
-use crate::validation::ValidationMiddleware;
+use crate::validation::{Validate, ValidationMiddleware};
 use crate::Configuration;
 
 #[cfg(feature = "redis")]
@@ -37,8 +48,9 @@ use crate::redis::RedisModule;
 
 #[cfg(feature = "postgres")]
 use {
-    crate::data::postgres::migration::PostgresMigrator, sqlx::postgres::PgPoolOptions,
-    std::time::Duration,
+    crate::data::postgres::migration::{PostgresMigration, PostgresMigrator},
+    crate::data::postgres::PostgresEntity,
+    sqlx::postgres::PgPoolOptions,
 };
 
 pub struct AppBuilder {
@@ -51,8 +63,15 @@ pub struct AppBuilder {
     entity_registry: EntityRegistry,
     address: Option<String>,
     config_builder: ConfigBuilder,
+    versioning: Option<ApiVersioning>,
+    route_diagnostics: Option<String>,
+    options: Vec<OptionsBinding>,
+    config_reload: Option<Duration>,
+    injectables: bool,
 }
 
+type OptionsBinding = Box<dyn FnOnce(&ReloadableConfiguration, &mut ServiceContainer)>;
+
 impl AppBuilder {
     pub fn new() -> Self {
         Self {
@@ -65,6 +84,11 @@ impl AppBuilder {
             entity_registry: EntityRegistry::new(),
             address: None,
             config_builder: ConfigBuilder::new(),
+            versioning: None,
+            route_diagnostics: None,
+            options: Vec::new(),
+            config_reload: None,
+            injectables: false,
         }
     }
 
@@ -81,6 +105,24 @@ impl AppBuilder {
         self
     }
 
+    pub fn register_scoped<T, F>(&mut self, factory: F) -> &mut Self
+    where
+        T: Send + Sync + 'static,
+        F: Fn(Arc<ServiceProvider>) -> T + Send + Sync + 'static,
+    {
+        self.services.register_scoped(factory);
+        self
+    }
+
+    pub fn register_transient<T, F>(&mut self, factory: F) -> &mut Self
+    where
+        T: Send + Sync + 'static,
+        F: Fn(Arc<ServiceProvider>) -> T + Send + Sync + 'static,
+    {
+        self.services.register_transient(factory);
+        self
+    }
+
     pub fn register_instance<T>(&mut self, instance: T) -> &mut Self
     where
         T: Clone + Send + Sync + 'static,
@@ -89,16 +131,124 @@ impl AppBuilder {
         self
     }
 
+    pub fn register_disposable<T, F>(&mut self, lifetime: ServiceLifetime, factory: F) -> &mut Self
+    where
+        T: Disposable,
+        F: Fn(Arc<ServiceProvider>) -> T + Send + Sync + 'static,
+    {
+        self.services.register_disposable(lifetime, factory);
+        self
+    }
+
+    pub fn configure<T>(&mut self, section: &str) -> &mut Self
+    where
+        T: DeserializeOwned + Send + Sync + 'static,
+    {
+        let section = section.to_string();
+        self.options.push(Box::new(move |reloadable, services| {
+            let monitor = OptionsMonitor::<T>::new(reloadable, &section)
+                .unwrap_or_else(|err| panic!("❌  {}", err));
+            services.register_instance(monitor);
+            let config = reloadable.current();
+            services.register_singleton::<T, _>(move |_| {
+                config
+                    .bind::<T>(&section)
+                    .expect("options were validated at build")
+            });
+        }));
+        self
+    }
+
+    pub fn register_with_dependencies<T, F>(
+        &mut self,
+        lifetime: ServiceLifetime,
+        dependencies: Vec<Dependency>,
+        factory: F,
+    ) -> &mut Self
+    where
+        T: Send + Sync + 'static,
+        F: Fn(Arc<ServiceProvider>) -> T + Send + Sync + 'static,
+    {
+        self.services
+            .register_with_dependencies(lifetime, dependencies, factory);
+        self
+    }
+
+    pub fn decorate<T, F>(&mut self, decorator: F) -> &mut Self
+    where
+        T: Send + Sync + 'static,
+        F: Fn(Arc<T>, Arc<ServiceProvider>) -> T + Send + Sync + 'static,
+    {
+        self.services.decorate(decorator);
+        self
+    }
+
+    pub fn register_singleton_async<T, F, Fut, E>(&mut self, factory: F) -> &mut Self
+    where
+        T: Send + Sync + 'static,
+        F: Fn(Arc<ServiceProvider>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, E>> + Send + 'static,
+        E: Into<InitializeError> + 'static,
+    {
+        self.services.register_singleton_async(factory);
+        self
+    }
+
+    pub fn initialize_on_startup<T>(&mut self) -> &mut Self
+    where
+        T: Send + Sync + 'static,
+    {
+        self.services.initialize_on_startup::<T>();
+        self
+    }
+
+    pub fn add_initializer<I: ServiceInitializer>(&mut self, initializer: I) -> &mut Self {
+        self.services.add_initializer(initializer);
+        self
+    }
+
+    pub fn register_keyed_singleton<T, F>(&mut self, key: &str, factory: F) -> &mut Self
+    where
+        T: Send + Sync + 'static,
+        F: Fn(Arc<ServiceProvider>) -> T + Send + Sync + 'static,
+    {
+        self.services.register_keyed_singleton(key, factory);
+        self
+    }
+
+    pub fn register_keyed_scoped<T, F>(&mut self, key: &str, factory: F) -> &mut Self
+    where
+        T: Send + Sync + 'static,
+        F: Fn(Arc<ServiceProvider>) -> T + Send + Sync + 'static,
+    {
+        self.services.register_keyed_scoped(key, factory);
+        self
+    }
+
+    pub fn register_keyed_transient<T, F>(&mut self, key: &str, factory: F) -> &mut Self
+    where
+        T: Send + Sync + 'static,
+        F: Fn(Arc<ServiceProvider>) -> T + Send + Sync + 'static,
+    {
+        self.services.register_keyed_transient(key, factory);
+        self
+    }
+
+    pub fn register_keyed_instance<T>(&mut self, key: &str, instance: T) -> &mut Self
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        self.services.register_keyed_instance(key, instance);
+        self
+    }
+
     pub fn use_middleware<M: Middleware + 'static>(&mut self, middleware: M) -> &mut Self {
         self.pipeline.add(middleware);
         self
     }
 
     pub fn use_controller<T: Controller>(&mut self) -> &mut Self {
-        let routes = T::routes();
-        for route in routes {
-            self.endpoint_registry.add_endpoint_route(route);
-        }
+        self.endpoint_registry.register::<T>();
         self
     }
 
@@ -112,11 +262,31 @@ impl AppBuilder {
         self
     }
 
+    // Registers every `#[injectable]` type linked into the binary at build time.
+    pub fn use_injectables(&mut self) -> &mut Self {
+        self.injectables = true;
+        self
+    }
+
     pub fn use_validation(&mut self) -> &mut Self {
         self.pipeline.add(ValidationMiddleware::new());
         self
     }
 
+    pub fn use_api_versioning(&mut self, versioning: ApiVersioning) -> &mut Self {
+        self.versioning = Some(versioning);
+        self
+    }
+
+    pub fn use_route_diagnostics(&mut self) -> &mut Self {
+        self.use_route_diagnostics_at(DEFAULT_ROUTE_DIAGNOSTICS_PATH)
+    }
+
+    pub fn use_route_diagnostics_at(&mut self, path: &str) -> &mut Self {
+        self.route_diagnostics = Some(path.to_string());
+        self
+    }
+
     pub fn use_hosted_service<T: HostedService>(&mut self, service: T) -> &mut Self {
         self.hosted_services.add(service);
         self
@@ -169,23 +339,20 @@ impl AppBuilder {
         self
     }
 
-    pub fn use_config<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
-        let path = path.as_ref().to_path_buf();
-        let path = if path.is_absolute() {
-            path
-        } else {
-            let base = std::env::current_exe()
-                .ok()
-                .and_then(|exe| exe.parent().map(|parent| parent.to_path_buf()))
-                .unwrap_or_else(|| std::path::PathBuf::from("."));
-            base.join(path)
-        };
+    pub fn use_environment(&mut self, environment: &str) -> &mut Self {
+        self.update_config(|builder| builder.with_environment(environment))
+    }
 
+    pub fn use_config<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+        let path = Self::config_path(path.as_ref());
         log::debug!("Using config file at {}", path.display());
+        self.update_config(|builder| builder.with_environment_files(&path))
+    }
 
-        let builder = std::mem::replace(&mut self.config_builder, ConfigBuilder::new());
-        self.config_builder = builder.with_file(&path);
-        self
+    pub fn use_optional_config<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+        let path = Self::config_path(path.as_ref());
+        log::debug!("Using optional config file at {}", path.display());
+        self.update_config(|builder| builder.with_optional_file(&path))
     }
 
     pub fn use_env(&mut self) -> &mut Self {
@@ -193,11 +360,55 @@ impl AppBuilder {
     }
 
     pub fn use_env_prefix(&mut self, prefix: &str) -> &mut Self {
-        let builder = std::mem::replace(&mut self.config_builder, ConfigBuilder::new());
-        self.config_builder = builder.with_env(prefix);
+        self.update_config(|builder| builder.with_env(prefix))
+    }
+
+    pub fn use_dotenv<P: AsRef<Path>>(&mut self, path: P, prefix: &str) -> &mut Self {
+        let path = Self::config_path(path.as_ref());
+        self.update_config(|builder| builder.with_dotenv(&path, prefix))
+    }
+
+    pub fn use_secrets<P: AsRef<Path>>(&mut self, dir: P) -> &mut Self {
+        let dir = Self::config_path(dir.as_ref());
+        self.update_config(|builder| builder.with_secrets(&dir))
+    }
+
+    pub fn use_command_line(&mut self) -> &mut Self {
+        self.update_config(|builder| builder.with_command_line())
+    }
+
+    // Polls config files at `interval` and refreshes `ReloadableConfiguration` and options monitors.
+    pub fn use_config_reload(&mut self, interval: Duration) -> &mut Self {
+        self.config_reload = Some(interval);
+        self
+    }
+
+    fn update_config(&mut self, update: impl FnOnce(ConfigBuilder) -> ConfigBuilder) -> &mut Self {
+        let builder = std::mem::take(&mut self.config_builder);
+        self.config_builder = update(builder);
         self
     }
 
+    fn config_path(path: &Path) -> std::path::PathBuf {
+        if path.is_absolute() {
+            return path.to_path_buf();
+        }
+        let base = std::env::current_exe()
+            .ok()
+            .and_then(|exe| exe.parent().map(|parent| parent.to_path_buf()))
+            .unwrap_or_else(|| std::path::PathBuf::from("."));
+        base.join(path)
+    }
+
+    pub fn validate(&self) -> Result<(), RouteConflictReport> {
+        let mut registry = self.endpoint_registry.clone();
+        if let Some(path) = self.route_diagnostics.as_deref() {
+            registry.get(path, RouteTableHandler);
+        }
+        registry.register_attribute_routes();
+        registry.validate()
+    }
+
     pub fn build(self) -> Application {
         let AppBuilder {
             pipeline,
@@ -209,27 +420,56 @@ impl AppBuilder {
             entity_registry,
             address,
             config_builder,
+            versioning,
+            route_diagnostics,
+            options,
+            config_reload,
+            injectables,
         } = self;
 
+        if let Some(path) = route_diagnostics.as_deref() {
+            endpoint_registry.get(path, RouteTableHandler);
+        }
         endpoint_registry.register_attribute_routes();
+        if let Err(report) = endpoint_registry.validate() {
+            panic!("❌  {}", report);
+        }
 
         for route in endpoint_registry.routes() {
             router.add_route(route.clone());
         }
 
+        let link_generator = LinkGenerator::from_registry(&endpoint_registry);
+        services.register_instance(link_generator);
+        services.register_instance(RouteTable::from_registry(&endpoint_registry));
+
         let has_routes = !endpoint_registry.routes().is_empty();
         let endpoint_registry = Arc::new(endpoint_registry);
         let entity_registry = Arc::new(entity_registry);
         services.register_singleton::<Arc<EntityRegistry>, _>(move |_| entity_registry.clone());
 
-        let config = config_builder.build();
+        let reloadable = config_builder.build_reloadable();
+        let config = reloadable.current();
+        log::debug!("Configuration {}", config.debug_view());
         let config_clone = config.clone();
         services.register_singleton::<Configuration, _>(move |_| config_clone.clone());
+        for bind in options {
+            bind(&reloadable, &mut services);
+        }
+        if let Some(interval) = config_reload {
+            reloadable.watch(interval);
+        }
+        services.register_instance(reloadable);
 
         #[cfg(feature = "redis")]
         RedisModule::register(&mut services, &config);
 
-        let services = services.build();
+        if injectables {
+            injectable::register_collected(&mut services);
+        }
+        let services = services
+            .build_validated()
+            .unwrap_or_else(|report| panic!("❌  {}", report));
         let job_queue = match job_queue {
             JobQueueConfig::None => None,
             JobQueueConfig::Provided(queue) => Some(queue),
@@ -243,10 +483,12 @@ impl AppBuilder {
         let pipeline = if has_routes {
             let mut middlewares: Vec<Box<dyn DynMiddleware>> = Vec::new();
 
-            middlewares.push(Box::new(RoutingMiddleware::with_registry(
-                router.clone(),
-                Arc::clone(&endpoint_registry),
-            )));
+            let mut routing =
+                RoutingMiddleware::with_registry(router.clone(), Arc::clone(&endpoint_registry));
+            if let Some(versioning) = versioning {
+                routing = routing.with_versioning(versioning);
+            }
+            middlewares.push(Box::new(routing));
 
             middlewares.extend(pipeline.into_middleware());
 
@@ -306,6 +548,22 @@ impl AppBuilder {
         self
     }
 
+    pub fn route_patch<H>(&mut self, path: &str, handler: H) -> &mut Self
+    where
+        H: HttpHandler + Send + Sync + 'static,
+    {
+        self.endpoint_registry.patch(path, handler);
+        self
+    }
+
+    pub fn route_group(&mut self, prefix: &str) -> RouteGroup<'_> {
+        self.endpoint_registry.group(prefix)
+    }
+
+    pub fn route_host(&mut self, host: &str) -> RouteGroup<'_> {
+        self.endpoint_registry.host(host)
+    }
+
     pub fn endpoint_registry_clone(&self) -> EndpointRegistry {
         self.endpoint_registry.clone()
     }
@@ -314,18 +572,22 @@ impl AppBuilder {
 #[cfg(feature = "postgres")]
 impl AppBuilder {
     pub fn use_postgres(&mut self) -> &mut Self {
+        // Connecting during startup initialization turns a bad URL or an unreachable
+        // server into `AppError::Initialization` instead of a panic on first use.
         log::info!("Registering pool options");
-        self.services.register_singleton(|provider| {
-            let config = provider
+        self.services.register_singleton_async(|provider| {
+            let pg_config = provider
                 .resolve::<Configuration>()
-                .expect("configuration not registered");
-            let pg_config = config.postgres_config();
-
-            PgPoolOptions::new()
-                .max_connections(pg_config.pool_size)
-                .acquire_timeout(Duration::from_secs(pg_config.timeout))
-                .connect_lazy(&pg_config.url)
-                .expect("❌  Invalid postgres configuration")
+                .map(|config| config.postgres_config());
+            async move {
+                let pg_config = pg_config.ok_or("configuration not registered")?;
+                PgPoolOptions::new()
+                    .max_connections(pg_config.pool_size)
+                    .acquire_timeout(Duration::from_secs(pg_config.timeout))
+                    .connect(&pg_config.url)
+                    .await
+                    .map_err(InitializeError::from)
+            }
         });
 
         log::info!("Registering postgres config");
@@ -340,12 +602,18 @@ impl AppBuilder {
         self.services.register_singleton(|provider| {
             let pool = provider
                 .resolve::<sqlx::PgPool>()
-                .expect("❌  Postgres pool not registered");
+                .expect("❌  Postgres pool is not connected until startup initialization");
             PostgresMigrator::new(pool.as_ref().clone())
         });
 
         self
     }
+
+    // Runs after the pool has connected, so call it after `use_postgres`.
+    pub fn migrate_on_startup<E: PostgresEntity>(&mut self) -> &mut Self {
+        self.services.add_initializer(PostgresMigration::<E>::new());
+        self
+    }
 }
 
 impl AppBuilder {
@@ -383,6 +651,26 @@ impl AppBuilder {
         )
     }
 
+    pub fn use_entity_with_validation<E>(&mut self, operations: &[EntityOperation]) -> &mut Self
+    where
+        E: Entity + Validate + Serialize + DeserializeOwned + 'static,
+        E::Id: FromStr + Send + Sync + 'static,
+    {
+        self.entity_registry.register::<E>();
+        let hooks = Arc::new(DefaultEntityHooks);
+        let base_path = format!("/api/{}", E::plural_name().to_lowercase());
+
+        for operation in operations {
+            let handler =
+                OperationHandler::<E, _>::new(*operation, hooks.clone()).with_validation();
+            let builder = Self::entity_route(*operation, &base_path, handler);
+            self.endpoint_registry
+                .add_endpoint_route_with_origin(builder.build(), RouteOrigin::Entity(E::name()));
+        }
+
+        self
+    }
+
     pub fn use_entity_with_hooks<E, H>(
         &mut self,
         hooks: H,
@@ -397,39 +685,9 @@ impl AppBuilder {
         let plural = E::plural_name().to_lowercase();
         let base_path = format!("/api/{}", plural);
 
-        for op in operations {
-            match op {
-                EntityOperation::List => {
-                    self.route_get(
-                        &format!("{}/{{page}}/{{pageSize}}", base_path),
-                        OperationHandler::new(EntityOperation::List, hooks.clone()),
-                    );
-                }
-                EntityOperation::Get => {
-                    self.route_get(
-                        &format!("{}/{{id}}", base_path),
-                        OperationHandler::new(EntityOperation::Get, hooks.clone()),
-                    );
-                }
-                EntityOperation::Create => {
-                    self.route_post(
-                        &base_path,
-                        OperationHandler::new(EntityOperation::Create, hooks.clone()),
-                    );
-                }
-                EntityOperation::Update => {
-                    self.route_put(
-                        &base_path,
-                        OperationHandler::new(EntityOperation::Update, hooks.clone()),
-                    );
-                }
-                EntityOperation::Delete => {
-                    self.route_delete(
-                        &format!("{}/{{id}}", base_path),
-                        OperationHandler::new(EntityOperation::Delete, hooks.clone()),
-                    );
-                }
-            }
+        for operation in operations {
+            let handler = OperationHandler::new(*operation, hooks.clone());
+            self.add_entity_route::<E, _>(*operation, &base_path, handler, None);
         }
 
         self
@@ -452,53 +710,54 @@ impl AppBuilder {
 
         for operation in operations {
             let handler = OperationHandler::new(*operation, hooks.clone());
-            match operation {
-                EntityOperation::List => {
-                    self.endpoint_registry.add_with_policy(
-                        "GET",
-                        &format!("{}/{{page}}/{{pageSize}}", base_path),
-                        handler,
-                        policy.clone(),
-                    );
-                }
-                EntityOperation::Get => {
-                    self.endpoint_registry.add_with_policy(
-                        "GET",
-                        &format!("{}/{{id}}", base_path),
-                        handler,
-                        policy.clone(),
-                    );
-                }
-                EntityOperation::Create => {
-                    self.endpoint_registry.add_with_policy(
-                        "POST",
-                        &base_path,
-                        handler,
-                        policy.clone(),
-                    );
-                }
-                EntityOperation::Update => {
-                    self.endpoint_registry.add_with_policy(
-                        "PUT",
-                        &base_path,
-                        handler,
-                        policy.clone(),
-                    );
-                }
-                EntityOperation::Delete => {
-                    self.endpoint_registry.add_with_policy(
-                        "DELETE",
-                        &format!("{}/{{id}}", base_path),
-                        handler,
-                        policy.clone(),
-                    );
-                }
-            }
+            self.add_entity_route::<E, _>(*operation, &base_path, handler, Some(policy.clone()));
         }
 
         self
     }
 
+    fn add_entity_route<E, H>(
+        &mut self,
+        operation: EntityOperation,
+        base_path: &str,
+        handler: H,
+        policy: Option<Policy>,
+    ) where
+        E: Entity,
+        H: HttpHandler + Send + Sync + 'static,
+    {
+        let mut builder = Self::entity_route(operation, base_path, handler);
+        if let Some(policy) = policy {
+            builder = builder.with_policy(policy);
+        }
+        self.endpoint_registry
+            .add_endpoint_route_with_origin(builder.build(), RouteOrigin::Entity(E::name()));
+    }
+
+    fn entity_route<H>(operation: EntityOperation, base_path: &str, handler: H) -> RouteBuilder
+    where
+        H: HttpHandler + Send + Sync + 'static,
+    {
+        match operation {
+            EntityOperation::List => RouteBuilder::new(
+                "GET",
+                &format!("{}/{{page}}/{{pageSize}}", base_path),
+                handler,
+            ),
+            EntityOperation::Get => {
+                RouteBuilder::new("GET", &format!("{}/{{id}}", base_path), handler)
+            }
+            EntityOperation::Create => RouteBuilder::new("POST", base_path, handler),
+            EntityOperation::Update => RouteBuilder::new("PUT", base_path, handler),
+            EntityOperation::Patch => {
+                RouteBuilder::new("PATCH", &format!("{}/{{id}}", base_path), handler)
+            }
+            EntityOperation::Delete => {
+                RouteBuilder::new("DELETE", &format!("{}/{{id}}", base_path), handler)
+            }
+        }
+    }
+
     pub fn use_memory_repository<E>(&mut self) -> &mut Self
     where
         E: Entity + Clone + Serialize + DeserializeOwned + Send + Sync + 'static,
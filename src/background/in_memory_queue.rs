@@ -20,14 +20,18 @@ impl InMemoryJobQueue {
         }
     }
 
-    pub fn run_next(&self) -> Option<JobResult> {
-        let job = self.jobs.lock().expect("jobs lock").pop_front();
-        job.map(|job| job.execute(JobContext::new(self.services.clone())))
+    // The job's scope is disposed before this returns, so its services never outlive the job.
+    pub async fn run_next(&self) -> Option<JobResult> {
+        let job = self.jobs.lock().expect("jobs lock").pop_front()?;
+        let scope = self.services.create_scope();
+        let result = job.execute(JobContext::new(Arc::new(scope.provider().clone())));
+        scope.dispose().await;
+        Some(result)
     }
 
-    pub fn run_all(&self) -> Vec<JobResult> {
+    pub async fn run_all(&self) -> Vec<JobResult> {
         let mut results = Vec::new();
-        while let Some(result) = self.run_next() {
+        while let Some(result) = self.run_next().await {
             results.push(result);
         }
         results
@@ -20,8 +20,8 @@ impl JobQueueRunner {
         }
     }
 
-    pub fn run_pending_jobs(&self) -> Vec<JobResult> {
-        self.queue.run_all()
+    pub async fn run_pending_jobs(&self) -> Vec<JobResult> {
+        self.queue.run_all().await
     }
 }
 
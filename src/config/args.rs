@@ -0,0 +1,53 @@
+use std::collections::HashMap;
+use std::env;
+
+use crate::config::source::ConfigSource;
+
+// Accepts `--Section:Key=value`, `--Section:Key value` and bare `--flag` (read as `true`).
+pub struct CommandLineSource {
+    args: Vec<String>,
+}
+
+impl CommandLineSource {
+    pub fn new<I, S>(args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            args: args.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(env::args().skip(1))
+    }
+}
+
+impl ConfigSource for CommandLineSource {
+    fn load(&self) -> HashMap<String, String> {
+        let mut values = HashMap::new();
+        let mut args = self.args.iter().peekable();
+        while let Some(arg) = args.next() {
+            let Some(option) = arg.strip_prefix("--") else {
+                continue;
+            };
+            if option.is_empty() {
+                break;
+            }
+            let (key, value) = match option.split_once('=') {
+                Some((key, value)) => (key, value.to_string()),
+                None => match args.next_if(|next| !next.starts_with("--")) {
+                    Some(value) => (option, value.clone()),
+                    None => (option, "true".to_string()),
+                },
+            };
+            values.insert(key.replace("__", ".").replace(':', "."), value);
+        }
+        values
+    }
+
+    fn describe(&self) -> String {
+        "command line arguments".to_string()
+    }
+}
@@ -0,0 +1,369 @@
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use serde::de::value::{MapDeserializer, SeqDeserializer};
+use serde::de::{self, DeserializeOwned, IntoDeserializer, Visitor};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BindError {
+    pub section: String,
+    pub message: String,
+}
+
+impl Display for BindError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "invalid configuration section `{}`: {}",
+            self.section, self.message
+        )
+    }
+}
+
+impl Error for BindError {}
+
+pub(crate) fn bind<T: DeserializeOwned>(
+    values: &HashMap<String, String>,
+    section: &str,
+) -> Result<T, BindError> {
+    let prefix = section.to_lowercase();
+    let mut root = Node::default();
+    for (key, value) in values {
+        let key = key.to_lowercase();
+        let rest = if prefix.is_empty() {
+            Some(key.as_str())
+        } else if key == prefix {
+            Some("")
+        } else {
+            key.strip_prefix(&prefix)
+                .and_then(|rest| rest.strip_prefix('.'))
+        };
+        if let Some(rest) = rest {
+            root.insert(rest, value);
+        }
+    }
+
+    T::deserialize(NodeDeserializer {
+        node: root,
+        path: prefix,
+    })
+    .map_err(|err| BindError {
+        section: section.to_string(),
+        message: err.0,
+    })
+}
+
+// Field names match keys regardless of case, `_` or `-`, so `PoolSize`, `poolSize`
+// and `pool_size` all bind to the same field.
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| *c != '_' && *c != '-')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[derive(Debug, Default)]
+struct Node {
+    value: Option<String>,
+    children: BTreeMap<String, Node>,
+}
+
+impl Node {
+    fn insert(&mut self, key: &str, value: &str) {
+        if key.is_empty() {
+            self.value = Some(value.to_string());
+            return;
+        }
+        let (head, tail) = key.split_once('.').unwrap_or((key, ""));
+        self.children
+            .entry(head.to_string())
+            .or_default()
+            .insert(tail, value);
+    }
+
+    fn is_sequence(&self) -> bool {
+        !self.children.is_empty()
+            && self
+                .children
+                .keys()
+                .all(|key| key.chars().all(|c| c.is_ascii_digit()))
+    }
+}
+
+#[derive(Debug)]
+struct DeError(String);
+
+impl Display for DeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(&self.0)
+    }
+}
+
+impl Error for DeError {}
+
+impl de::Error for DeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        DeError(msg.to_string())
+    }
+}
+
+struct NodeDeserializer {
+    node: Node,
+    path: String,
+}
+
+impl NodeDeserializer {
+    fn child(&self, key: &str, node: Node) -> Self {
+        let path = if self.path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", self.path, key)
+        };
+        Self { node, path }
+    }
+
+    fn text(&self) -> Result<&str, DeError> {
+        self.node
+            .value
+            .as_deref()
+            .ok_or_else(|| DeError(format!("`{}` must be a single value", self.path)))
+    }
+
+    fn parse<T: std::str::FromStr>(&self, expected: &str) -> Result<T, DeError> {
+        let text = self.text()?;
+        text.trim().parse().map_err(|_| {
+            DeError(format!(
+                "invalid value `{}` for `{}`: expected {}",
+                text, self.path, expected
+            ))
+        })
+    }
+
+    fn entries(self, fields: Option<&[&str]>) -> Vec<(String, NodeDeserializer)> {
+        let parent = NodeDeserializer {
+            node: Node::default(),
+            path: self.path,
+        };
+        self.node
+            .children
+            .into_iter()
+            .map(|(key, node)| {
+                let name = fields
+                    .and_then(|fields| {
+                        fields
+                            .iter()
+                            .find(|field| normalize(field) == normalize(&key))
+                    })
+                    .map_or_else(|| key.clone(), |field| field.to_string());
+                let child = parent.child(&key, node);
+                (name, child)
+            })
+            .collect()
+    }
+
+    fn items(self) -> Vec<NodeDeserializer> {
+        if !self.node.children.is_empty() {
+            let mut children: Vec<(usize, Node)> = self
+                .node
+                .children
+                .into_iter()
+                .filter_map(|(key, node)| key.parse().ok().map(|index| (index, node)))
+                .collect();
+            children.sort_by_key(|(index, _)| *index);
+            let parent = NodeDeserializer {
+                node: Node::default(),
+                path: self.path,
+            };
+            return children
+                .into_iter()
+                .map(|(index, node)| parent.child(&index.to_string(), node))
+                .collect();
+        }
+        // A single value binds to a list as comma separated items.
+        match self.node.value.as_deref().map(str::trim) {
+            None | Some("") => Vec::new(),
+            Some(text) => text
+                .split(',')
+                .enumerate()
+                .map(|(index, item)| NodeDeserializer {
+                    node: Node {
+                        value: Some(item.trim().to_string()),
+                        children: BTreeMap::new(),
+                    },
+                    path: format!("{}.{}", self.path, index),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<'de> IntoDeserializer<'de, DeError> for NodeDeserializer {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident: $ty:ty),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+                visitor.$visit(self.parse::<$ty>(stringify!($ty))?)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for NodeDeserializer {
+    type Error = DeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        if self.node.children.is_empty() {
+            match self.node.value {
+                Some(value) => visitor.visit_string(value),
+                None => visitor.visit_unit(),
+            }
+        } else if self.node.is_sequence() {
+            self.deserialize_seq(visitor)
+        } else {
+            self.deserialize_map(visitor)
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        let text = self.text()?;
+        match text.trim().to_ascii_lowercase().as_str() {
+            "true" | "yes" | "on" | "1" => visitor.visit_bool(true),
+            "false" | "no" | "off" | "0" => visitor.visit_bool(false),
+            _ => Err(DeError(format!(
+                "invalid value `{}` for `{}`: expected bool",
+                text, self.path
+            ))),
+        }
+    }
+
+    deserialize_parsed! {
+        deserialize_i8 => visit_i8: i8,
+        deserialize_i16 => visit_i16: i16,
+        deserialize_i32 => visit_i32: i32,
+        deserialize_i64 => visit_i64: i64,
+        deserialize_u8 => visit_u8: u8,
+        deserialize_u16 => visit_u16: u16,
+        deserialize_u32 => visit_u32: u32,
+        deserialize_u64 => visit_u64: u64,
+        deserialize_f32 => visit_f32: f32,
+        deserialize_f64 => visit_f64: f64,
+        deserialize_char => visit_char: char,
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        let text = self.text()?.to_string();
+        visitor.visit_string(text)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        let text = self.text()?.to_string();
+        visitor.visit_byte_buf(text.into_bytes())
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        match self.node.value.as_deref() {
+            Some("null") if self.node.children.is_empty() => visitor.visit_none(),
+            None if self.node.children.is_empty() => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_seq(SeqDeserializer::new(self.items().into_iter()))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_map(MapDeserializer::new(self.entries(None).into_iter()))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        visitor.visit_map(MapDeserializer::new(self.entries(Some(fields)).into_iter()))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        let text = self.text()?;
+        let variant = variants
+            .iter()
+            .find(|variant| normalize(variant) == normalize(text))
+            .ok_or_else(|| {
+                DeError(format!(
+                    "invalid value `{}` for `{}`: expected one of {}",
+                    text,
+                    self.path,
+                    variants.join(", ")
+                ))
+            })?;
+        visitor.visit_enum(IntoDeserializer::<DeError>::into_deserializer(*variant))
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_unit()
+    }
+}
@@ -1,60 +1,213 @@
 use std::collections::HashMap;
-use std::path::Path;
+use std::env;
+use std::path::{Path, PathBuf};
 
+use crate::config::args::CommandLineSource;
 use crate::config::config::Configuration;
 use crate::config::env::EnvConfigSource;
 use crate::config::file::FileSource;
-use crate::config::source::ConfigSource;
+use crate::config::interpolation;
+use crate::config::reload::ReloadableConfiguration;
+use crate::config::secrets::SecretsDirectorySource;
+use crate::config::source::{ConfigSource, ConfigSourceError};
+
+pub const ENVIRONMENT_VARIABLE: &str = "NIMBLE_ENVIRONMENT";
+pub const DEFAULT_ENVIRONMENT: &str = "Production";
+
+enum ConfigLayer {
+    Source(Box<dyn ConfigSource>),
+    // Expanded at build time, once the environment name is known.
+    EnvironmentFiles(PathBuf),
+}
 
 pub struct ConfigBuilder {
-    sources: Vec<Box<dyn ConfigSource>>,
+    sources: Vec<ConfigLayer>,
+    environment: Option<String>,
 }
 
 impl ConfigBuilder {
     pub fn new() -> Self {
         Self {
             sources: Vec::new(),
+            environment: None,
         }
     }
 
-    pub fn with_json<P: AsRef<Path>>(mut self, path: P) -> Self {
-        self.sources
-            .push(Box::new(FileSource::json(path.as_ref().to_path_buf())));
+    pub fn with_environment(mut self, environment: &str) -> Self {
+        self.environment = Some(environment.to_string());
         self
     }
 
-    pub fn with_toml<P: AsRef<Path>>(mut self, path: P) -> Self {
-        self.sources
-            .push(Box::new(FileSource::toml(path.as_ref().to_path_buf())));
-        self
+    pub fn with_json<P: AsRef<Path>>(self, path: P) -> Self {
+        self.with_source(FileSource::json(path.as_ref().to_path_buf()))
     }
 
-    pub fn with_file<P: AsRef<Path>>(mut self, path: P) -> Self {
+    pub fn with_toml<P: AsRef<Path>>(self, path: P) -> Self {
+        self.with_source(FileSource::toml(path.as_ref().to_path_buf()))
+    }
+
+    pub fn with_yaml<P: AsRef<Path>>(self, path: P) -> Self {
+        self.with_source(FileSource::yaml(path.as_ref().to_path_buf()))
+    }
+
+    pub fn with_file<P: AsRef<Path>>(self, path: P) -> Self {
         let path = path.as_ref().to_path_buf();
         match path.extension().and_then(|ext| ext.to_str()) {
-            Some("toml") => {
-                self.sources.push(Box::new(FileSource::toml(path)));
-            }
-            _ => {
-                self.sources.push(Box::new(FileSource::json(path)));
-            }
+            Some("toml") => self.with_source(FileSource::toml(path)),
+            Some("yaml" | "yml") => self.with_source(FileSource::yaml(path)),
+            _ => self.with_source(FileSource::json(path)),
+        }
+    }
+
+    pub fn with_optional_file<P: AsRef<Path>>(self, path: P) -> Self {
+        let path = path.as_ref().to_path_buf();
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => self.with_source(FileSource::optional_toml(path)),
+            Some("yaml" | "yml") => self.with_source(FileSource::optional_yaml(path)),
+            _ => self.with_source(FileSource::optional_json(path)),
         }
+    }
+
+    // Adds `path`, then the optional `{stem}.{environment}.json`, `.toml` and `.yaml` next to it.
+    pub fn with_environment_files<P: AsRef<Path>>(mut self, path: P) -> Self {
+        let path = path.as_ref().to_path_buf();
+        self = self.with_file(&path);
+        self.sources.push(ConfigLayer::EnvironmentFiles(path));
         self
     }
 
-    pub fn with_env(mut self, prefix: &str) -> Self {
-        self.sources.push(Box::new(EnvConfigSource::new(prefix)));
+    pub fn with_env(self, prefix: &str) -> Self {
+        self.with_source(EnvConfigSource::new(prefix))
+    }
+
+    // A missing `.env` file is not an error; it is usually only present in development.
+    pub fn with_dotenv<P: AsRef<Path>>(self, path: P, prefix: &str) -> Self {
+        self.with_source(FileSource::optional_dotenv(
+            path.as_ref().to_path_buf(),
+            prefix,
+        ))
+    }
+
+    pub fn with_secrets<P: AsRef<Path>>(self, dir: P) -> Self {
+        self.with_source(SecretsDirectorySource::optional(dir.as_ref().to_path_buf()))
+    }
+
+    pub fn with_args<I, S>(self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.with_source(CommandLineSource::new(args))
+    }
+
+    pub fn with_command_line(self) -> Self {
+        self.with_source(CommandLineSource::from_env())
+    }
+
+    pub fn with_source<S: ConfigSource + 'static>(mut self, source: S) -> Self {
+        self.sources.push(ConfigLayer::Source(Box::new(source)));
         self
     }
 
     pub fn build(self) -> Configuration {
+        self.into_layers().load()
+    }
+
+    pub fn build_reloadable(self) -> ReloadableConfiguration {
+        ReloadableConfiguration::new(self.into_layers())
+    }
+
+    fn into_layers(self) -> ConfigLayers {
+        let environment = self
+            .environment
+            .or_else(|| env::var(ENVIRONMENT_VARIABLE).ok())
+            .filter(|name| !name.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_ENVIRONMENT.to_string());
+        ConfigLayers {
+            sources: self.sources,
+            environment,
+        }
+    }
+}
+
+pub(crate) struct ConfigLayers {
+    sources: Vec<ConfigLayer>,
+    environment: String,
+}
+
+impl ConfigLayers {
+    pub(crate) fn load(&self) -> Configuration {
+        self.try_load().unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub(crate) fn try_load(&self) -> Result<Configuration, ConfigSourceError> {
         let mut values = HashMap::new();
-        for source in self.sources {
-            let next = source.load();
-            for (key, value) in next {
-                values.insert(key.to_lowercase(), value);
+        let mut sources = HashMap::new();
+        let mut failure = None;
+        self.each_source(|source| {
+            if failure.is_some() {
+                return;
+            }
+            let loaded = match source.try_load() {
+                Ok(loaded) => loaded,
+                Err(err) => {
+                    failure = Some(err);
+                    return;
+                }
+            };
+            let name = source.describe();
+            for (key, value) in loaded {
+                let key = key.to_lowercase();
+                sources.insert(key.clone(), name.clone());
+                values.insert(key, value);
             }
+        });
+        if let Some(err) = failure {
+            return Err(err);
         }
-        Configuration::new(values)
+        interpolation::interpolate(&mut values);
+        Ok(Configuration::new(
+            values,
+            sources,
+            self.environment.clone(),
+        ))
+    }
+
+    pub(crate) fn watch_paths(&self) -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+        self.each_source(|source| paths.extend(source.watch_paths()));
+        paths
     }
+
+    fn each_source(&self, mut visit: impl FnMut(&dyn ConfigSource)) {
+        for layer in &self.sources {
+            match layer {
+                ConfigLayer::Source(source) => visit(source.as_ref()),
+                ConfigLayer::EnvironmentFiles(path) => {
+                    for source in environment_files(path, &self.environment) {
+                        visit(source.as_ref());
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Default for ConfigBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn environment_files(path: &Path, environment: &str) -> Vec<Box<dyn ConfigSource>> {
+    let stem = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default();
+    let file = |ext: &str| path.with_file_name(format!("{}.{}.{}", stem, environment, ext));
+    vec![
+        Box::new(FileSource::optional_json(file("json"))),
+        Box::new(FileSource::optional_toml(file("toml"))),
+        Box::new(FileSource::optional_yaml(file("yaml"))),
+    ]
 }
@@ -1,5 +1,10 @@
 use std::collections::HashMap;
 
+use serde::de::DeserializeOwned;
+
+use crate::config::binder::{self, BindError};
+use crate::config::builder::DEFAULT_ENVIRONMENT;
+
 #[cfg(feature = "postgres")]
 use crate::config::postgres::PostgresConfig;
 #[cfg(feature = "redis")]
@@ -8,15 +13,65 @@ use crate::config::redis::RedisConfig;
 #[derive(Clone, Debug, Default)]
 pub struct Configuration {
     values: HashMap<String, String>,
+    sources: HashMap<String, String>,
+    environment: Option<String>,
 }
 
 impl Configuration {
-    pub(crate) fn new(values: HashMap<String, String>) -> Self {
-        Self { values }
+    pub(crate) fn new(
+        values: HashMap<String, String>,
+        sources: HashMap<String, String>,
+        environment: String,
+    ) -> Self {
+        Self {
+            values,
+            sources,
+            environment: Some(environment),
+        }
     }
 
     pub fn from_values(values: HashMap<String, String>) -> Self {
-        Self { values }
+        Self {
+            values,
+            ..Self::default()
+        }
+    }
+
+    pub fn environment(&self) -> &str {
+        self.environment.as_deref().unwrap_or(DEFAULT_ENVIRONMENT)
+    }
+
+    pub fn is_environment(&self, name: &str) -> bool {
+        self.environment().eq_ignore_ascii_case(name)
+    }
+
+    pub fn is_development(&self) -> bool {
+        self.is_environment("Development")
+    }
+
+    pub fn is_production(&self) -> bool {
+        self.is_environment("Production")
+    }
+
+    pub fn source(&self, key: &str) -> Option<&str> {
+        self.sources
+            .get(&key.to_lowercase())
+            .map(|source| source.as_str())
+    }
+
+    // Lists keys with the source that supplied them; values are left out as they may be secrets.
+    pub fn debug_view(&self) -> String {
+        let mut keys: Vec<&String> = self.values.keys().collect();
+        keys.sort();
+        let mut view = format!("environment: {}", self.environment());
+        for key in keys {
+            view.push_str(&format!(
+                "\n  {} <- {}",
+                key,
+                self.source(key).unwrap_or("values")
+            ));
+        }
+        view
     }
 
     pub fn get(&self, key: &str) -> Option<&str> {
@@ -37,6 +92,14 @@ impl Configuration {
         }
     }
 
+    pub fn bind<T: DeserializeOwned>(&self, section: &str) -> Result<T, BindError> {
+        binder::bind(&self.values, section)
+    }
+
+    pub(crate) fn same_values(&self, other: &Configuration) -> bool {
+        self.values == other.values
+    }
+
     #[cfg(feature = "redis")]
     pub fn redis_config(&self) -> RedisConfig {
         RedisConfig::from_configuration(self)
@@ -13,24 +13,38 @@ impl EnvConfigSource {
             prefix: prefix.to_string(),
         }
     }
+}
 
-    fn map_key(&self, key: &str) -> String {
-        let raw = key.strip_prefix(&self.prefix).unwrap_or("");
-        raw.to_ascii_lowercase().replace('_', ".")
+// Maps `PREFIX_SECTION__POOL_SIZE` to `section.pool_size` when the key uses `__`, and
+// `PREFIX_SECTION_KEY` to `section.key` otherwise; `None` when the prefix does not match.
+pub(crate) fn map_env_key(prefix: &str, key: &str) -> Option<String> {
+    if key.contains("__") {
+        return map_nested_env_key(prefix, key);
     }
+    let raw = key.strip_prefix(prefix)?;
+    let mapped = raw.to_ascii_lowercase().replace('_', ".");
+    (!mapped.is_empty()).then_some(mapped)
+}
+
+// Only `__` separates sections, so `_` stays part of a multi-word name.
+pub(crate) fn map_nested_env_key(prefix: &str, key: &str) -> Option<String> {
+    let raw = key.strip_prefix(prefix)?;
+    let mapped = raw.to_ascii_lowercase().replace("__", ".");
+    (!mapped.is_empty()).then_some(mapped)
 }
 
 impl ConfigSource for EnvConfigSource {
     fn load(&self) -> HashMap<String, String> {
         let mut values = HashMap::new();
         for (key, value) in env::vars() {
-            if key.starts_with(&self.prefix) {
-                let mapped = self.map_key(&key);
-                if !mapped.is_empty() {
-                    values.insert(mapped, value);
-                }
+            if let Some(mapped) = map_env_key(&self.prefix, &key) {
+                values.insert(mapped, value);
             }
         }
         values
     }
+
+    fn describe(&self) -> String {
+        format!("environment variables `{}*`", self.prefix)
+    }
 }
@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::env::map_nested_env_key;
+use crate::config::source::{ConfigSource, ConfigSourceError};
+
+// Reads `KEY=value` lines; `PREFIX_SECTION__POOL_SIZE` maps to `section.pool_size`.
+pub struct DotEnvFileSource {
+    path: PathBuf,
+    prefix: String,
+    optional: bool,
+}
+
+impl DotEnvFileSource {
+    pub fn new(path: PathBuf, prefix: &str) -> Self {
+        Self {
+            path,
+            prefix: prefix.to_string(),
+            optional: false,
+        }
+    }
+
+    pub fn optional(path: PathBuf, prefix: &str) -> Self {
+        Self {
+            optional: true,
+            ..Self::new(path, prefix)
+        }
+    }
+}
+
+impl ConfigSource for DotEnvFileSource {
+    fn load(&self) -> HashMap<String, String> {
+        self.try_load().unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_load(&self) -> Result<HashMap<String, String>, ConfigSourceError> {
+        if self.optional && !self.path.exists() {
+            return Ok(HashMap::new());
+        }
+        let content = fs::read_to_string(&self.path).map_err(|err| {
+            ConfigSourceError::new(self, format!("dotenv file read failed: {}", err))
+        })?;
+        let content = content.trim_start_matches('\u{feff}');
+        let mut out = HashMap::new();
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            if let Some(key) = map_nested_env_key(&self.prefix, key.trim()) {
+                out.insert(key, parse_value(value.trim()));
+            }
+        }
+        Ok(out)
+    }
+
+    fn describe(&self) -> String {
+        format!("dotenv file `{}`", self.path.display())
+    }
+
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        vec![self.path.clone()]
+    }
+}
+
+fn parse_value(value: &str) -> String {
+    if let Some(quoted) = value.strip_prefix('\'') {
+        return quoted.split('\'').next().unwrap_or_default().to_string();
+    }
+    if let Some(quoted) = value.strip_prefix('"') {
+        let mut out = String::new();
+        let mut chars = quoted.chars();
+        while let Some(ch) = chars.next() {
+            match ch {
+                '"' => break,
+                '\\' => match chars.next() {
+                    Some('n') => out.push('\n'),
+                    Some('t') => out.push('\t'),
+                    Some(other) => out.push(other),
+                    None => break,
+                },
+                _ => out.push(ch),
+            }
+        }
+        return out;
+    }
+    match value.find(" #") {
+        Some(idx) => value[..idx].trim_end().to_string(),
+        None => value.to_string(),
+    }
+}
@@ -4,15 +4,26 @@ use std::path::PathBuf;
 
 use serde_json::Value;
 
-use crate::config::source::ConfigSource;
+use crate::config::source::{ConfigSource, ConfigSourceError};
 
 pub struct JsonFileSource {
     path: PathBuf,
+    optional: bool,
 }
 
 impl JsonFileSource {
     pub fn new(path: PathBuf) -> Self {
-        Self { path }
+        Self {
+            path,
+            optional: false,
+        }
+    }
+
+    pub fn optional(path: PathBuf) -> Self {
+        Self {
+            path,
+            optional: true,
+        }
     }
 
     fn flatten_json(&self, prefix: Option<&str>, value: &Value, out: &mut HashMap<String, String>) {
@@ -53,13 +64,31 @@ impl JsonFileSource {
 
 impl ConfigSource for JsonFileSource {
     fn load(&self) -> HashMap<String, String> {
+        self.try_load().unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_load(&self) -> Result<HashMap<String, String>, ConfigSourceError> {
         log::debug!("Loading configuration from JSON file: {:?}", self.path);
 
-        let content = fs::read_to_string(&self.path).expect("json file read failed");
+        if self.optional && !self.path.exists() {
+            return Ok(HashMap::new());
+        }
+        let content = fs::read_to_string(&self.path).map_err(|err| {
+            ConfigSourceError::new(self, format!("json file read failed: {}", err))
+        })?;
         let content = content.trim_start_matches('\u{feff}');
-        let value: Value = serde_json::from_str(content).expect("json parse failed");
+        let value: Value = serde_json::from_str(content)
+            .map_err(|err| ConfigSourceError::new(self, format!("json parse failed: {}", err)))?;
         let mut out = HashMap::new();
         self.flatten_json(None, &value, &mut out);
-        out
+        Ok(out)
+    }
+
+    fn describe(&self) -> String {
+        format!("json file `{}`", self.path.display())
+    }
+
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        vec![self.path.clone()]
     }
 }
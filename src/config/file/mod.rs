@@ -1,15 +1,21 @@
 ﻿use std::path::PathBuf;
 
+use crate::config::file::dotenv::DotEnvFileSource;
 use crate::config::file::json::JsonFileSource;
 use crate::config::file::toml::TomlFileSource;
+use crate::config::file::yaml::YamlFileSource;
 use crate::config::source::ConfigSource;
 
+pub mod dotenv;
 pub mod json;
 pub mod toml;
+pub mod yaml;
 
 pub enum FileSource {
     Json(PathBuf),
     Toml(PathBuf),
+    Yaml(PathBuf),
+    DotEnv(PathBuf),
 }
 
 impl FileSource {
@@ -20,4 +26,28 @@ impl FileSource {
     pub fn toml(path: PathBuf) -> impl ConfigSource {
         TomlFileSource::new(path)
     }
+
+    pub fn optional_json(path: PathBuf) -> impl ConfigSource {
+        JsonFileSource::optional(path)
+    }
+
+    pub fn optional_toml(path: PathBuf) -> impl ConfigSource {
+        TomlFileSource::optional(path)
+    }
+
+    pub fn yaml(path: PathBuf) -> impl ConfigSource {
+        YamlFileSource::new(path)
+    }
+
+    pub fn optional_yaml(path: PathBuf) -> impl ConfigSource {
+        YamlFileSource::optional(path)
+    }
+
+    pub fn dotenv(path: PathBuf, prefix: &str) -> impl ConfigSource {
+        DotEnvFileSource::new(path, prefix)
+    }
+
+    pub fn optional_dotenv(path: PathBuf, prefix: &str) -> impl ConfigSource {
+        DotEnvFileSource::optional(path, prefix)
+    }
 }
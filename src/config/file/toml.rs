@@ -4,15 +4,26 @@ use std::path::PathBuf;
 
 use toml::Value;
 
-use crate::config::source::ConfigSource;
+use crate::config::source::{ConfigSource, ConfigSourceError};
 
 pub struct TomlFileSource {
     path: PathBuf,
+    optional: bool,
 }
 
 impl TomlFileSource {
     pub fn new(path: PathBuf) -> Self {
-        Self { path }
+        Self {
+            path,
+            optional: false,
+        }
+    }
+
+    pub fn optional(path: PathBuf) -> Self {
+        Self {
+            path,
+            optional: true,
+        }
     }
 
     fn flatten_toml(&self, prefix: Option<&str>, value: &Value, out: &mut HashMap<String, String>) {
@@ -54,11 +65,30 @@ impl TomlFileSource {
 
 impl ConfigSource for TomlFileSource {
     fn load(&self) -> HashMap<String, String> {
-        let content = fs::read_to_string(&self.path).expect("toml file read failed");
+        self.try_load().unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_load(&self) -> Result<HashMap<String, String>, ConfigSourceError> {
+        if self.optional && !self.path.exists() {
+            return Ok(HashMap::new());
+        }
+        let content = fs::read_to_string(&self.path).map_err(|err| {
+            ConfigSourceError::new(self, format!("toml file read failed: {}", err))
+        })?;
         let content = content.trim_start_matches('\u{feff}');
-        let value: Value = content.parse::<Value>().expect("toml parse failed");
+        let value: Value = content
+            .parse::<Value>()
+            .map_err(|err| ConfigSourceError::new(self, format!("toml parse failed: {}", err)))?;
         let mut out = HashMap::new();
         self.flatten_toml(None, &value, &mut out);
-        out
+        Ok(out)
+    }
+
+    fn describe(&self) -> String {
+        format!("toml file `{}`", self.path.display())
+    }
+
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        vec![self.path.clone()]
     }
 }
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use serde_norway::Value;
+
+use crate::config::source::{ConfigSource, ConfigSourceError};
+
+pub struct YamlFileSource {
+    path: PathBuf,
+    optional: bool,
+}
+
+impl YamlFileSource {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            optional: false,
+        }
+    }
+
+    pub fn optional(path: PathBuf) -> Self {
+        Self {
+            path,
+            optional: true,
+        }
+    }
+
+    // Nulls are skipped so `key: ~` leaves the key unset instead of binding "null".
+    fn flatten_yaml(
+        &self,
+        prefix: Option<&str>,
+        value: &Value,
+        out: &mut HashMap<String, String>,
+    ) -> Result<(), String> {
+        match value {
+            Value::Mapping(map) => {
+                for (key, child) in map {
+                    let key = match key {
+                        Value::String(s) => s.clone(),
+                        Value::Bool(b) => b.to_string(),
+                        Value::Number(n) => n.to_string(),
+                        other => return Err(format!("unsupported key `{:?}`", other)),
+                    };
+                    let next = match prefix {
+                        Some(p) => format!("{}.{}", p, key),
+                        None => key,
+                    };
+                    self.flatten_yaml(Some(&next), child, out)?;
+                }
+            }
+            Value::Sequence(items) => {
+                for (idx, child) in items.iter().enumerate() {
+                    let next = match prefix {
+                        Some(p) => format!("{}.{}", p, idx),
+                        None => idx.to_string(),
+                    };
+                    self.flatten_yaml(Some(&next), child, out)?;
+                }
+            }
+            Value::Tagged(tagged) => self.flatten_yaml(prefix, &tagged.value, out)?,
+            other => {
+                if let Some(key) = prefix {
+                    let text = match other {
+                        Value::String(s) => s.clone(),
+                        Value::Bool(b) => b.to_string(),
+                        Value::Number(n) => n.to_string(),
+                        _ => return Ok(()),
+                    };
+                    out.insert(key.to_string(), text);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ConfigSource for YamlFileSource {
+    fn load(&self) -> HashMap<String, String> {
+        self.try_load().unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_load(&self) -> Result<HashMap<String, String>, ConfigSourceError> {
+        log::debug!("Loading configuration from YAML file: {:?}", self.path);
+
+        if self.optional && !self.path.exists() {
+            return Ok(HashMap::new());
+        }
+        let parse_error =
+            |err: String| ConfigSourceError::new(self, format!("yaml parse failed: {}", err));
+        let content = fs::read_to_string(&self.path).map_err(|err| {
+            ConfigSourceError::new(self, format!("yaml file read failed: {}", err))
+        })?;
+        let content = content.trim_start_matches('\u{feff}');
+        let mut value: Value =
+            serde_norway::from_str(content).map_err(|err| parse_error(err.to_string()))?;
+        value
+            .apply_merge()
+            .map_err(|err| parse_error(err.to_string()))?;
+        let mut out = HashMap::new();
+        self.flatten_yaml(None, &value, &mut out)
+            .map_err(parse_error)?;
+        Ok(out)
+    }
+
+    fn describe(&self) -> String {
+        format!("yaml file `{}`", self.path.display())
+    }
+
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        vec![self.path.clone()]
+    }
+}
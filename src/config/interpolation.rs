@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+use std::env;
+
+const MAX_DEPTH: usize = 8;
+
+// Expands `${Section:Key}` from other keys, falling back to environment variables and then
+// to the `${Name:-default}` default. `$${` escapes a literal `${`.
+pub(crate) fn interpolate(values: &mut HashMap<String, String>) {
+    let expanded: Vec<(String, String)> = values
+        .iter()
+        .filter(|(_, value)| value.contains("${"))
+        .map(|(key, value)| (key.clone(), expand(value, values, 0)))
+        .collect();
+    values.extend(expanded);
+}
+
+fn expand(text: &str, values: &HashMap<String, String>, depth: usize) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        if rest[..start].ends_with('$') {
+            out.push_str(&rest[..start - 1]);
+            out.push_str("${");
+            rest = &rest[start + 2..];
+            continue;
+        }
+        out.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+            rest = &rest[start..];
+            break;
+        };
+        let placeholder = &rest[start + 2..end];
+        match resolve(placeholder, values, depth) {
+            Some(value) => out.push_str(&value),
+            None => {
+                log::warn!(
+                    "Unresolved configuration placeholder `${{{}}}`",
+                    placeholder
+                );
+                out.push_str(&rest[start..=end]);
+            }
+        }
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn resolve(placeholder: &str, values: &HashMap<String, String>, depth: usize) -> Option<String> {
+    if depth >= MAX_DEPTH {
+        return None;
+    }
+    let (name, default) = match placeholder.split_once(":-") {
+        Some((name, default)) => (name.trim(), Some(default)),
+        None => (placeholder.trim(), None),
+    };
+    let key = name.replace("__", ".").replace(':', ".").to_lowercase();
+    values
+        .get(&key)
+        .map(|value| expand(value, values, depth + 1))
+        .or_else(|| env::var(name).ok())
+        .or_else(|| default.map(|value| expand(value, values, depth + 1)))
+}
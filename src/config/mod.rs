@@ -1,7 +1,13 @@
+pub mod args;
+pub mod binder;
 pub mod builder;
 pub mod config;
 pub mod env;
 pub mod file;
+mod interpolation;
+pub mod options;
+pub mod reload;
+pub mod secrets;
 pub mod source;
 
 #[cfg(feature = "postgres")]
@@ -9,5 +15,11 @@ pub mod postgres;
 #[cfg(feature = "redis")]
 pub mod redis;
 
+pub use args::CommandLineSource;
+pub use binder::BindError;
 pub use builder::ConfigBuilder;
 pub use config::Configuration;
+pub use options::OptionsMonitor;
+pub use reload::ReloadableConfiguration;
+pub use secrets::SecretsDirectorySource;
+pub use source::ConfigSourceError;
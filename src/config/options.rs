@@ -0,0 +1,70 @@
+use std::sync::{Arc, Mutex, RwLock};
+
+use serde::de::DeserializeOwned;
+
+use crate::config::binder::BindError;
+use crate::config::reload::ReloadableConfiguration;
+
+type OptionsListener<T> = Arc<dyn Fn(&T) + Send + Sync>;
+
+pub struct OptionsMonitor<T> {
+    current: Arc<RwLock<Arc<T>>>,
+    listeners: Arc<Mutex<Vec<OptionsListener<T>>>>,
+}
+
+impl<T> Clone for OptionsMonitor<T> {
+    fn clone(&self) -> Self {
+        Self {
+            current: Arc::clone(&self.current),
+            listeners: Arc::clone(&self.listeners),
+        }
+    }
+}
+
+impl<T> OptionsMonitor<T>
+where
+    T: DeserializeOwned + Send + Sync + 'static,
+{
+    pub fn new(config: &ReloadableConfiguration, section: &str) -> Result<Self, BindError> {
+        let monitor = Self {
+            current: Arc::new(RwLock::new(Arc::new(config.current().bind(section)?))),
+            listeners: Arc::new(Mutex::new(Vec::new())),
+        };
+
+        let section = section.to_string();
+        let current = Arc::clone(&monitor.current);
+        let listeners = Arc::clone(&monitor.listeners);
+        config.on_change(move |config| {
+            let options = match config.bind::<T>(&section) {
+                Ok(options) => Arc::new(options),
+                Err(err) => {
+                    log::warn!("{}; keeping previous options", err);
+                    return;
+                }
+            };
+            *current.write().expect("options poisoned") = Arc::clone(&options);
+            let listeners = listeners
+                .lock()
+                .expect("options listeners poisoned")
+                .clone();
+            for listener in listeners {
+                listener(&options);
+            }
+        });
+        Ok(monitor)
+    }
+
+    pub fn current(&self) -> Arc<T> {
+        Arc::clone(&self.current.read().expect("options poisoned"))
+    }
+
+    pub fn on_change<F>(&self, listener: F)
+    where
+        F: Fn(&T) + Send + Sync + 'static,
+    {
+        self.listeners
+            .lock()
+            .expect("options listeners poisoned")
+            .push(Arc::new(listener));
+    }
+}
@@ -2,7 +2,7 @@ use crate::config::Configuration;
 use serde::Deserialize;
 
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct PostgresConfig {
     pub url: String,
     pub pool_size: u32,
@@ -23,19 +23,9 @@ impl Default for PostgresConfig {
 
 impl PostgresConfig {
     pub fn from_configuration(config: &Configuration) -> Self {
-        let mut pg_config = Self::default();
-        if let Some(url) = config.get("Postgres.Url") {
-            pg_config.url = url.to_string();
-        }
-        if let Some(pool) = config.get("Postgres.PoolSize").and_then(|v| v.parse().ok()) {
-            pg_config.pool_size = pool;
-        }
-        if let Some(timeout) = config.get("Postgres.Timeout").and_then(|v| v.parse().ok()) {
-            pg_config.timeout = timeout;
-        }
-        if let Some(schema) = config.get("Postgres.Schema") {
-            pg_config.schema = Some(schema.to_string());
-        }
+        let pg_config: Self = config
+            .bind("Postgres")
+            .unwrap_or_else(|err| panic!("❌  {}", err));
 
         log::trace!("PostgresConfig loaded: {:?}", pg_config);
         pg_config
@@ -1,6 +1,8 @@
 use crate::config::Configuration;
+use serde::Deserialize;
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default)]
 pub struct RedisConfig {
     pub url: String,
     pub pool_size: usize,
@@ -19,19 +21,8 @@ impl Default for RedisConfig {
 
 impl RedisConfig {
     pub fn from_configuration(config: &Configuration) -> Self {
-        let mut cfg = Self::default();
-        if let Some(url) = config.get("redis.url") {
-            cfg.url = url.to_string();
-        }
-        if let Some(pool) = config.get("redis.pool_size").and_then(|v| v.parse().ok()) {
-            cfg.pool_size = pool;
-        }
-        if let Some(ttl) = config
-            .get("redis.default_ttl_seconds")
-            .and_then(|v| v.parse().ok())
-        {
-            cfg.default_ttl_seconds = ttl;
-        }
-        cfg
+        config
+            .bind("Redis")
+            .unwrap_or_else(|err| panic!("❌  {}", err))
     }
 }
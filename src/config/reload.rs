@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, RwLock, Weak};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::config::builder::ConfigLayers;
+use crate::config::config::Configuration;
+
+type ChangeListener = Arc<dyn Fn(&Configuration) + Send + Sync>;
+
+struct ReloadState {
+    layers: ConfigLayers,
+    current: RwLock<Configuration>,
+    listeners: Mutex<Vec<ChangeListener>>,
+}
+
+#[derive(Clone)]
+pub struct ReloadableConfiguration {
+    state: Arc<ReloadState>,
+}
+
+impl ReloadableConfiguration {
+    pub(crate) fn new(layers: ConfigLayers) -> Self {
+        let current = layers.load();
+        Self {
+            state: Arc::new(ReloadState {
+                layers,
+                current: RwLock::new(current),
+                listeners: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    pub fn current(&self) -> Configuration {
+        self.state.current.read().expect("config poisoned").clone()
+    }
+
+    pub fn on_change<F>(&self, listener: F)
+    where
+        F: Fn(&Configuration) + Send + Sync + 'static,
+    {
+        self.state
+            .listeners
+            .lock()
+            .expect("config listeners poisoned")
+            .push(Arc::new(listener));
+    }
+
+    // Returns whether any value changed; a source that fails to load keeps the previous values.
+    pub fn reload(&self) -> bool {
+        let next = match self.state.layers.try_load() {
+            Ok(next) => next,
+            Err(err) => {
+                log::warn!(
+                    "Configuration reload failed, keeping previous values: {}",
+                    err
+                );
+                return false;
+            }
+        };
+        {
+            let mut current = self.state.current.write().expect("config poisoned");
+            if current.same_values(&next) {
+                return false;
+            }
+            *current = next.clone();
+        }
+
+        log::info!("Configuration reloaded");
+        let listeners = self
+            .state
+            .listeners
+            .lock()
+            .expect("config listeners poisoned")
+            .clone();
+        for listener in listeners {
+            listener(&next);
+        }
+        true
+    }
+
+    // Polls the watched files; the thread stops once every handle has been dropped.
+    pub fn watch(&self, interval: Duration) {
+        let state = Arc::downgrade(&self.state);
+        let mut stamps = modified_times(&self.state.layers.watch_paths());
+        thread::spawn(move || loop {
+            thread::sleep(interval);
+            let Some(state) = Weak::upgrade(&state) else {
+                break;
+            };
+            let next = modified_times(&state.layers.watch_paths());
+            if next != stamps {
+                stamps = next;
+                ReloadableConfiguration { state }.reload();
+            }
+        });
+    }
+}
+
+fn modified_times(paths: &[PathBuf]) -> HashMap<PathBuf, Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| {
+            let modified = fs::metadata(path)
+                .and_then(|metadata| metadata.modified())
+                .ok();
+            (path.clone(), modified)
+        })
+        .collect()
+}
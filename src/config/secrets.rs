@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::source::{ConfigSource, ConfigSourceError};
+
+// One file per key, as mounted by Docker and Kubernetes secrets: `postgres.url` or `Postgres__Url`.
+pub struct SecretsDirectorySource {
+    dir: PathBuf,
+    optional: bool,
+}
+
+impl SecretsDirectorySource {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            optional: false,
+        }
+    }
+
+    pub fn optional(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            optional: true,
+        }
+    }
+
+    fn files(&self) -> Result<Vec<(String, PathBuf)>, ConfigSourceError> {
+        if self.optional && !self.dir.is_dir() {
+            return Ok(Vec::new());
+        }
+        let entries = fs::read_dir(&self.dir).map_err(|err| {
+            ConfigSourceError::new(self, format!("secrets directory read failed: {}", err))
+        })?;
+        Ok(entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name().to_str()?.to_string();
+                // Kubernetes keeps its bookkeeping in `..data`-style hidden entries.
+                let path = entry.path();
+                (!name.starts_with('.') && path.is_file()).then_some((name, path))
+            })
+            .collect())
+    }
+}
+
+impl ConfigSource for SecretsDirectorySource {
+    fn load(&self) -> HashMap<String, String> {
+        self.try_load().unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_load(&self) -> Result<HashMap<String, String>, ConfigSourceError> {
+        self.files()?
+            .into_iter()
+            .map(|(name, path)| {
+                let value = fs::read_to_string(&path).map_err(|err| {
+                    ConfigSourceError::new(self, format!("secret file read failed: {}", err))
+                })?;
+                let value = value.trim_end_matches(['\r', '\n']).to_string();
+                Ok((name.replace("__", "."), value))
+            })
+            .collect()
+    }
+
+    fn describe(&self) -> String {
+        format!("secrets directory `{}`", self.dir.display())
+    }
+
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        let mut paths = vec![self.dir.clone()];
+        paths.extend(
+            self.files()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(_, path)| path),
+        );
+        paths
+    }
+}
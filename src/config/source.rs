@@ -1,5 +1,44 @@
 ﻿use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigSourceError {
+    pub source: String,
+    pub message: String,
+}
+
+impl ConfigSourceError {
+    pub fn new(source: &dyn ConfigSource, message: impl Into<String>) -> Self {
+        Self {
+            source: source.describe(),
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ConfigSourceError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "cannot load {}: {}", self.source, self.message)
+    }
+}
+
+impl Error for ConfigSourceError {}
 
 pub trait ConfigSource: Send + Sync {
     fn load(&self) -> HashMap<String, String>;
+
+    // Reloads use this so a broken file keeps the previous values instead of panicking.
+    fn try_load(&self) -> Result<HashMap<String, String>, ConfigSourceError> {
+        Ok(self.load())
+    }
+
+    fn describe(&self) -> String {
+        "custom source".to_string()
+    }
+
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
 }
@@ -2,6 +2,8 @@ use crate::endpoint::http_handler::HttpHandler;
 use crate::endpoint::route::{EndpointRoute, RouteBuilder};
 
 pub trait HttpRoute: HttpHandler + Sized + Send + Sync + 'static {
+    const PARAMS: &'static [&'static str] = &[];
+
     fn route() -> RouteBuilder;
 
     fn endpoint() -> EndpointRoute {
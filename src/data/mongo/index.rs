@@ -0,0 +1,66 @@
+use mongodb::bson::{Bson, Document};
+use mongodb::options::IndexOptions;
+use mongodb::IndexModel;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MongoIndex {
+    keys: Vec<(String, i32)>,
+    unique: bool,
+    name: Option<String>,
+}
+
+impl MongoIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn ascending(mut self, field: &str) -> Self {
+        self.keys.push((field.to_string(), 1));
+        self
+    }
+
+    pub fn descending(mut self, field: &str) -> Self {
+        self.keys.push((field.to_string(), -1));
+        self
+    }
+
+    pub fn unique(mut self) -> Self {
+        self.unique = true;
+        self
+    }
+
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    pub fn keys(&self) -> &[(String, i32)] {
+        &self.keys
+    }
+
+    pub fn is_unique(&self) -> bool {
+        self.unique
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn keys_document(&self) -> Document {
+        let mut keys = Document::new();
+        for (field, direction) in &self.keys {
+            keys.insert(field.clone(), Bson::Int32(*direction));
+        }
+        keys
+    }
+
+    pub fn to_model(&self) -> IndexModel {
+        let mut options = IndexOptions::default();
+        options.unique = self.unique.then_some(true);
+        options.name = self.name.clone();
+        IndexModel::builder()
+            .keys(self.keys_document())
+            .options(options)
+            .build()
+    }
+}
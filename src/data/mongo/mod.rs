@@ -1,11 +1,12 @@
 use async_trait::async_trait;
 use chrono::NaiveDate;
 use futures_util::TryStreamExt;
-use mongodb::bson::{self, Bson, Document, Regex};
+use mongodb::bson::{Bson, Document, Regex};
 use mongodb::options::FindOptions;
 use mongodb::{Collection, Database};
 use uuid::Uuid;
 
+use crate::data::mongo::index::MongoIndex;
 use crate::data::paging::Page;
 use crate::data::provider::{DataError, DataProvider, DataResult};
 use crate::data::query::{
@@ -13,11 +14,21 @@ use crate::data::query::{
 };
 use crate::entity::entity::Entity;
 
+pub mod index;
+
+pub use mongodb::bson;
+pub use nimble_web_macros::MongoEntity;
+
 pub trait MongoEntity:
     Entity + serde::Serialize + serde::de::DeserializeOwned + Send + Sync
 {
     fn id_field() -> &'static str;
-    fn id_bson(id: &Self::Id) -> Bson;
+    // Fails when the id cannot be represented in BSON (e.g. a nested `u64`).
+    fn id_bson(id: &Self::Id) -> DataResult<Bson>;
+
+    fn indexes() -> Vec<MongoIndex> {
+        Vec::new()
+    }
 }
 
 pub struct MongoProvider<E: Entity> {
@@ -86,7 +97,7 @@ where
 
     async fn get(&self, id: &E::Id) -> DataResult<Option<E>> {
         let collection = self.collection();
-        let filter = Self::id_filter(id);
+        let filter = Self::id_filter(id)?;
         let doc = collection
             .find_one(filter)
             .await
@@ -103,7 +114,7 @@ where
 
     async fn update(&self, entity: E) -> DataResult<E> {
         let collection = self.collection();
-        let filter = Self::id_filter(entity.id());
+        let filter = Self::id_filter(entity.id())?;
         let doc = bson::to_document(&entity).map_err(|err| DataError::Provider(err.to_string()))?;
         let result = collection
             .replace_one(filter, doc)
@@ -117,7 +128,7 @@ where
 
     async fn delete(&self, id: &E::Id) -> DataResult<bool> {
         let collection = self.collection();
-        let filter = Self::id_filter(id);
+        let filter = Self::id_filter(id)?;
         let result = collection
             .delete_one(filter)
             .await
@@ -254,6 +265,18 @@ where
         Ok(0)
     }
 
+    pub async fn ensure_indexes(&self) -> DataResult<()> {
+        let indexes = E::indexes();
+        if indexes.is_empty() {
+            return Ok(());
+        }
+        self.collection()
+            .create_indexes(indexes.iter().map(MongoIndex::to_model))
+            .await
+            .map_err(Self::map_mongo_error)?;
+        Ok(())
+    }
+
     pub fn build_filter_doc(query: &Query<E>) -> DataResult<Document> {
         Self::build_filter_doc_from_filters(&query.filters)
     }
@@ -300,10 +323,10 @@ where
         Ok(pipeline)
     }
 
-    fn id_filter(id: &E::Id) -> Document {
+    fn id_filter(id: &E::Id) -> DataResult<Document> {
         let mut doc = Document::new();
-        doc.insert(E::id_field(), E::id_bson(id));
-        doc
+        doc.insert(E::id_field(), E::id_bson(id)?);
+        Ok(doc)
     }
 
     fn build_find_options(query: &Query<E>) -> FindOptions {
@@ -1,8 +1,15 @@
+use std::any::type_name;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use async_trait::async_trait;
 use sqlx::{Executor, PgPool, Row};
 
 use crate::data::postgres::PostgresEntity;
 use crate::data::provider::{DataError, DataResult};
 use crate::data::schema::ColumnDef;
+use crate::di::initializer::{InitializeError, ServiceInitializer};
+use crate::di::ServiceProvider;
 
 #[derive(Clone)]
 pub struct PostgresMigrator {
@@ -143,3 +150,32 @@ impl MigrationBuilder {
         sql
     }
 }
+
+pub(crate) struct PostgresMigration<E> {
+    _marker: PhantomData<fn() -> E>,
+}
+
+impl<E> PostgresMigration<E> {
+    pub(crate) fn new() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[async_trait]
+impl<E: PostgresEntity> ServiceInitializer for PostgresMigration<E> {
+    fn name(&self) -> String {
+        format!("migration for `{}`", type_name::<E>())
+    }
+
+    async fn initialize(&self, services: Arc<ServiceProvider>) -> Result<(), InitializeError> {
+        let migrator = services
+            .resolve::<PostgresMigrator>()
+            .ok_or("PostgresMigrator not registered. Did you call builder.use_postgres()?")?;
+        migrator
+            .migrate::<E>()
+            .await
+            .map_err(|err| format!("{:?}", err).into())
+    }
+}
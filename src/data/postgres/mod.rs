@@ -15,6 +15,8 @@ pub mod migration;
 #[cfg(feature = "postgres")]
 pub mod value_builder;
 
+pub use nimble_web_macros::PostgresEntity;
+
 pub trait PostgresEntity:
     Entity + for<'r> sqlx::FromRow<'r, PgRow> + Send + Sync + Unpin + 'static
 {
@@ -109,4 +109,18 @@ impl PostgresValueBuilder {
             None => Value::Null,
         }
     }
+
+    pub fn optional_bytes(value: &Option<Vec<u8>>) -> Value {
+        match value {
+            Some(v) => Value::Bytes(v.clone()),
+            None => Value::Null,
+        }
+    }
+
+    pub fn optional_string_array(value: &Option<Vec<String>>) -> Value {
+        match value {
+            Some(v) => Value::StringArray(v.clone()),
+            None => Value::Null,
+        }
+    }
 }
@@ -1,20 +1,30 @@
-use std::any::{Any, TypeId};
+use std::any::{type_name, Any, TypeId};
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::future::Future;
+use std::sync::{Arc, OnceLock};
 
+use crate::di::disposable::{as_disposable, Disposable};
+use crate::di::initializer::{AsyncSingleton, EagerSingleton, InitializeError, ServiceInitializer};
+use crate::di::injectable::{Dependency, Injectable};
+use crate::di::lazy::{Factory, Lazy};
 use crate::di::lifetime::ServiceLifetime;
 use crate::di::provider::ServiceProvider;
-use crate::di::registration::Registration;
+use crate::di::registration::{Decorator, Registration, ServiceKey};
+use crate::di::validation::{validate_registrations, ContainerValidationError};
 
 #[derive(Default)]
 pub struct ServiceContainer {
-    registrations: HashMap<TypeId, Registration>,
+    registrations: HashMap<ServiceKey, Vec<Registration>>,
+    initializers: Vec<Arc<dyn ServiceInitializer>>,
+    decorators: Vec<(ServiceKey, Decorator)>,
 }
 
 impl ServiceContainer {
     pub fn new() -> Self {
         Self {
             registrations: HashMap::new(),
+            initializers: Vec::new(),
+            decorators: Vec::new(),
         }
     }
 
@@ -22,14 +32,248 @@ impl ServiceContainer {
     where
         T: Send + Sync + 'static,
         F: Fn(Arc<ServiceProvider>) -> T + Send + Sync + 'static,
+    {
+        self.add(ServiceKey::of::<T>(), lifetime, Vec::new(), factory);
+    }
+
+    pub fn register_with_dependencies<T, F>(
+        &mut self,
+        lifetime: ServiceLifetime,
+        dependencies: Vec<Dependency>,
+        factory: F,
+    ) where
+        T: Send + Sync + 'static,
+        F: Fn(Arc<ServiceProvider>) -> T + Send + Sync + 'static,
+    {
+        self.add(ServiceKey::of::<T>(), lifetime, dependencies, factory);
+    }
+
+    pub fn register_disposable<T, F>(&mut self, lifetime: ServiceLifetime, factory: F)
+    where
+        T: Disposable,
+        F: Fn(Arc<ServiceProvider>) -> T + Send + Sync + 'static,
+    {
+        self.register(lifetime, factory);
+        if let Some(registration) = self
+            .registrations
+            .get_mut(&ServiceKey::of::<T>())
+            .and_then(|registrations| registrations.last_mut())
+        {
+            registration.disposer = Some(as_disposable::<T>);
+        }
+    }
+
+    pub fn register_keyed<T, F>(&mut self, key: &str, lifetime: ServiceLifetime, factory: F)
+    where
+        T: Send + Sync + 'static,
+        F: Fn(Arc<ServiceProvider>) -> T + Send + Sync + 'static,
+    {
+        self.add(ServiceKey::keyed::<T>(key), lifetime, Vec::new(), factory);
+    }
+
+    pub fn register_keyed_singleton<T, F>(&mut self, key: &str, factory: F)
+    where
+        T: Send + Sync + 'static,
+        F: Fn(Arc<ServiceProvider>) -> T + Send + Sync + 'static,
+    {
+        self.register_keyed(key, ServiceLifetime::Singleton, factory);
+    }
+
+    pub fn register_keyed_scoped<T, F>(&mut self, key: &str, factory: F)
+    where
+        T: Send + Sync + 'static,
+        F: Fn(Arc<ServiceProvider>) -> T + Send + Sync + 'static,
+    {
+        self.register_keyed(key, ServiceLifetime::Scoped, factory);
+    }
+
+    pub fn register_keyed_transient<T, F>(&mut self, key: &str, factory: F)
+    where
+        T: Send + Sync + 'static,
+        F: Fn(Arc<ServiceProvider>) -> T + Send + Sync + 'static,
+    {
+        self.register_keyed(key, ServiceLifetime::Transient, factory);
+    }
+
+    pub fn register_keyed_instance<T>(&mut self, key: &str, instance: T)
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        self.register_keyed_singleton(key, move |_| instance.clone());
+    }
+
+    fn add<T, F>(
+        &mut self,
+        key: ServiceKey,
+        lifetime: ServiceLifetime,
+        dependencies: Vec<Dependency>,
+        factory: F,
+    ) where
+        T: Send + Sync + 'static,
+        F: Fn(Arc<ServiceProvider>) -> T + Send + Sync + 'static,
+    {
+        if key.key.is_none() {
+            self.add_deferred::<T>();
+        }
+        self.push(key, lifetime, dependencies, factory);
+    }
+
+    fn push<T, F>(
+        &mut self,
+        key: ServiceKey,
+        lifetime: ServiceLifetime,
+        dependencies: Vec<Dependency>,
+        factory: F,
+    ) where
+        T: Send + Sync + 'static,
+        F: Fn(Arc<ServiceProvider>) -> T + Send + Sync + 'static,
+    {
+        self.push_fallible(key, lifetime, dependencies, move |provider| {
+            Some(factory(provider))
+        });
+    }
+
+    fn push_fallible<T, F>(
+        &mut self,
+        key: ServiceKey,
+        lifetime: ServiceLifetime,
+        dependencies: Vec<Dependency>,
+        factory: F,
+    ) where
+        T: Send + Sync + 'static,
+        F: Fn(Arc<ServiceProvider>) -> Option<T> + Send + Sync + 'static,
     {
         let factory = Arc::new(move |provider: Arc<ServiceProvider>| {
-            let value = factory(provider.clone());
-            Arc::new(value) as Arc<dyn Any + Send + Sync>
+            let value = factory(provider.clone())?;
+            Some(Arc::new(value) as Arc<dyn Any + Send + Sync>)
+        });
+
+        self.registrations.entry(key).or_default().push(
+            Registration::new(type_name::<T>(), lifetime, factory).with_dependencies(dependencies),
+        );
+    }
+
+    // The service resolves to `None` until `ServiceProvider::initialize` has built it.
+    pub fn register_singleton_async<T, F, Fut, E>(&mut self, factory: F)
+    where
+        T: Send + Sync + 'static,
+        F: Fn(Arc<ServiceProvider>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, E>> + Send + 'static,
+        E: Into<InitializeError> + 'static,
+    {
+        let cell = Arc::new(OnceLock::<Arc<T>>::new());
+        let created = cell.clone();
+        let factory_fn = Arc::new(move |_: Arc<ServiceProvider>| {
+            let value = created.get().cloned()?;
+            Some(value as Arc<dyn Any + Send + Sync>)
         });
 
+        self.add_deferred::<T>();
         self.registrations
-            .insert(TypeId::of::<T>(), Registration::new(lifetime, factory));
+            .entry(ServiceKey::of::<T>())
+            .or_default()
+            .push(Registration::new(
+                type_name::<T>(),
+                ServiceLifetime::Singleton,
+                factory_fn,
+            ));
+        self.initializers
+            .push(Arc::new(AsyncSingleton::new(cell, factory)));
+    }
+
+    // Every service can also be injected as `Lazy<T>` or `Factory<T>`, bound to the resolving
+    // scope. `Lazy<T>` depends on `T` so a singleton cannot capture a scoped service through
+    // it; `Factory<T>` declares nothing because it is meant for `create_in(scope)`.
+    fn add_deferred<T>(&mut self)
+    where
+        T: Send + Sync + 'static,
+    {
+        if !self.has_registration::<Lazy<T>>() {
+            let key = ServiceKey::of::<Lazy<T>>();
+            self.push(
+                key.clone(),
+                ServiceLifetime::Transient,
+                vec![Dependency::required::<T>("value")],
+                |provider| Lazy::<T>::new((*provider).clone()),
+            );
+            if let Some(registration) = self
+                .registrations
+                .get_mut(&key)
+                .and_then(|registrations| registrations.last_mut())
+            {
+                registration.deferred = true;
+            }
+        }
+        if !self.has_registration::<Factory<T>>() {
+            self.push(
+                ServiceKey::of::<Factory<T>>(),
+                ServiceLifetime::Transient,
+                Vec::new(),
+                |provider| Factory::<T>::new((*provider).clone()),
+            );
+        }
+    }
+
+    // Decorators are applied at build time, so they may be added before the service itself.
+    pub fn decorate<T, F>(&mut self, decorator: F)
+    where
+        T: Send + Sync + 'static,
+        F: Fn(Arc<T>, Arc<ServiceProvider>) -> T + Send + Sync + 'static,
+    {
+        let decorator: Decorator = Arc::new(move |inner, provider| {
+            let inner = inner.downcast::<T>().unwrap_or_else(|_| {
+                panic!("Service `{}` has an unexpected type", type_name::<T>())
+            });
+            Arc::new(decorator(inner, provider)) as Arc<dyn Any + Send + Sync>
+        });
+        self.decorators.push((ServiceKey::of::<T>(), decorator));
+    }
+
+    pub fn initialize_on_startup<T>(&mut self)
+    where
+        T: Send + Sync + 'static,
+    {
+        self.initializers.push(Arc::new(EagerSingleton::<T>::new()));
+    }
+
+    pub fn add_initializer<I: ServiceInitializer>(&mut self, initializer: I) {
+        self.initializers.push(Arc::new(initializer));
+    }
+
+    pub fn try_add<T, F>(&mut self, lifetime: ServiceLifetime, factory: F) -> bool
+    where
+        T: Send + Sync + 'static,
+        F: Fn(Arc<ServiceProvider>) -> T + Send + Sync + 'static,
+    {
+        if self.has_registration::<T>() {
+            return false;
+        }
+        self.register(lifetime, factory);
+        true
+    }
+
+    pub fn try_add_singleton<T, F>(&mut self, factory: F) -> bool
+    where
+        T: Send + Sync + 'static,
+        F: Fn(Arc<ServiceProvider>) -> T + Send + Sync + 'static,
+    {
+        self.try_add(ServiceLifetime::Singleton, factory)
+    }
+
+    pub fn try_add_scoped<T, F>(&mut self, factory: F) -> bool
+    where
+        T: Send + Sync + 'static,
+        F: Fn(Arc<ServiceProvider>) -> T + Send + Sync + 'static,
+    {
+        self.try_add(ServiceLifetime::Scoped, factory)
+    }
+
+    pub fn try_add_transient<T, F>(&mut self, factory: F) -> bool
+    where
+        T: Send + Sync + 'static,
+        F: Fn(Arc<ServiceProvider>) -> T + Send + Sync + 'static,
+    {
+        self.try_add(ServiceLifetime::Transient, factory)
     }
 
     pub fn register_singleton<T, F>(&mut self, factory: F)
@@ -63,11 +307,65 @@ impl ServiceContainer {
         self.register_singleton(move |_| instance.clone());
     }
 
+    // A constructor that fails resolves to `None`; `build_validated` reports the missing
+    // dependencies up front.
+    pub fn register_injectable<T: Injectable>(&mut self) {
+        self.add_deferred::<T>();
+        self.push_fallible(
+            ServiceKey::of::<T>(),
+            T::LIFETIME,
+            T::dependencies(),
+            |provider| match T::create(&provider) {
+                Ok(service) => Some(service),
+                Err(err) => {
+                    log::error!("{}", err);
+                    None
+                }
+            },
+        );
+    }
+
     pub fn has_registration<T: 'static>(&self) -> bool {
-        self.registrations.contains_key(&TypeId::of::<T>())
+        self.contains(TypeId::of::<T>())
     }
 
-    pub fn build(self) -> ServiceProvider {
-        ServiceProvider::from_registrations(self.registrations)
+    pub fn has_keyed_registration<T: 'static>(&self, key: &str) -> bool {
+        self.registrations
+            .contains_key(&ServiceKey::keyed::<T>(key))
+    }
+
+    pub fn registration_count<T: 'static>(&self) -> usize {
+        self.registrations
+            .get(&ServiceKey::of::<T>())
+            .map_or(0, Vec::len)
+    }
+
+    pub(crate) fn contains(&self, type_id: TypeId) -> bool {
+        self.contains_keyed(type_id, None)
+    }
+
+    pub(crate) fn contains_keyed(&self, type_id: TypeId, key: Option<&str>) -> bool {
+        self.registrations.contains_key(&ServiceKey {
+            type_id,
+            key: key.map(str::to_string),
+        })
+    }
+
+    pub fn validate(&self) -> Result<(), ContainerValidationError> {
+        validate_registrations(&self.registrations)
+    }
+
+    pub fn build_validated(self) -> Result<ServiceProvider, ContainerValidationError> {
+        self.validate()?;
+        Ok(self.build())
+    }
+
+    pub fn build(mut self) -> ServiceProvider {
+        for (key, decorator) in self.decorators {
+            for registration in self.registrations.get_mut(&key).into_iter().flatten() {
+                registration.decorate(Arc::clone(&decorator));
+            }
+        }
+        ServiceProvider::from_registrations(self.registrations, self.initializers)
     }
 }
@@ -0,0 +1,42 @@
+use std::any::Any;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait Disposable: Send + Sync + 'static {
+    async fn dispose(&self);
+}
+
+pub(crate) type Disposer = fn(Arc<dyn Any + Send + Sync>) -> Option<Arc<dyn Disposable>>;
+
+pub(crate) fn as_disposable<T: Disposable>(
+    value: Arc<dyn Any + Send + Sync>,
+) -> Option<Arc<dyn Disposable>> {
+    value
+        .downcast::<T>()
+        .ok()
+        .map(|value| value as Arc<dyn Disposable>)
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct DisposeList {
+    entries: Arc<Mutex<Vec<Arc<dyn Disposable>>>>,
+}
+
+impl DisposeList {
+    pub(crate) fn push(&self, disposable: Arc<dyn Disposable>) {
+        self.entries
+            .lock()
+            .expect("dispose list poisoned")
+            .push(disposable);
+    }
+
+    // Later instances may depend on earlier ones, so they go first.
+    pub(crate) async fn dispose(&self) {
+        let entries = std::mem::take(&mut *self.entries.lock().expect("dispose list poisoned"));
+        for disposable in entries.into_iter().rev() {
+            disposable.dispose().await;
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use std::any::{type_name, Any};
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::future::Future;
+use std::marker::PhantomData;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{Arc, OnceLock};
+
+use async_trait::async_trait;
+
+use crate::di::provider::ServiceProvider;
+
+pub type InitializeError = Box<dyn Error + Send + Sync>;
+
+#[async_trait]
+pub trait ServiceInitializer: Send + Sync + 'static {
+    fn name(&self) -> String;
+
+    async fn initialize(&self, services: Arc<ServiceProvider>) -> Result<(), InitializeError>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InitializationError {
+    pub name: String,
+    pub message: String,
+}
+
+impl Display for InitializationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "failed to initialize `{}`: {}", self.name, self.message)
+    }
+}
+
+impl Error for InitializationError {}
+
+pub(crate) struct AsyncSingleton<T, F> {
+    cell: Arc<OnceLock<Arc<T>>>,
+    factory: F,
+}
+
+impl<T, F> AsyncSingleton<T, F> {
+    pub(crate) fn new(cell: Arc<OnceLock<Arc<T>>>, factory: F) -> Self {
+        Self { cell, factory }
+    }
+}
+
+#[async_trait]
+impl<T, F, Fut, E> ServiceInitializer for AsyncSingleton<T, F>
+where
+    T: Send + Sync + 'static,
+    F: Fn(Arc<ServiceProvider>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<T, E>> + Send + 'static,
+    E: Into<InitializeError> + 'static,
+{
+    fn name(&self) -> String {
+        type_name::<T>().to_string()
+    }
+
+    async fn initialize(&self, services: Arc<ServiceProvider>) -> Result<(), InitializeError> {
+        if self.cell.get().is_some() {
+            return Ok(());
+        }
+        let value = (self.factory)(services).await.map_err(Into::into)?;
+        let _ = self.cell.set(Arc::new(value));
+        Ok(())
+    }
+}
+
+pub(crate) struct EagerSingleton<T> {
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> EagerSingleton<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[async_trait]
+impl<T: Send + Sync + 'static> ServiceInitializer for EagerSingleton<T> {
+    fn name(&self) -> String {
+        type_name::<T>().to_string()
+    }
+
+    async fn initialize(&self, services: Arc<ServiceProvider>) -> Result<(), InitializeError> {
+        match catch_unwind(AssertUnwindSafe(|| services.resolve::<T>())) {
+            Ok(Some(_)) => Ok(()),
+            Ok(None) => Err("service is not registered".into()),
+            Err(panic) => Err(panic_message(panic).into()),
+        }
+    }
+}
+
+fn panic_message(panic: Box<dyn Any + Send>) -> String {
+    match panic.downcast::<String>() {
+        Ok(message) => *message,
+        Err(panic) => match panic.downcast::<&'static str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "factory panicked".to_string(),
+        },
+    }
+}
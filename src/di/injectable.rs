@@ -0,0 +1,116 @@
+use std::any::{type_name, TypeId};
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use crate::di::container::ServiceContainer;
+use crate::di::lifetime::ServiceLifetime;
+use crate::di::provider::ServiceProvider;
+
+pub trait Injectable: Sized + Send + Sync + 'static {
+    const LIFETIME: ServiceLifetime;
+
+    fn dependencies() -> Vec<Dependency>;
+
+    fn create(provider: &ServiceProvider) -> Result<Self, MissingDependency>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dependency {
+    pub field: &'static str,
+    pub type_name: &'static str,
+    pub type_id: TypeId,
+    pub key: Option<&'static str>,
+    pub optional: bool,
+}
+
+impl Dependency {
+    pub fn required<T: 'static>(field: &'static str) -> Self {
+        Self {
+            field,
+            type_name: type_name::<T>(),
+            type_id: TypeId::of::<T>(),
+            key: None,
+            optional: false,
+        }
+    }
+
+    pub fn optional<T: 'static>(field: &'static str) -> Self {
+        Self {
+            optional: true,
+            ..Self::required::<T>(field)
+        }
+    }
+
+    pub fn with_key(mut self, key: &'static str) -> Self {
+        self.key = Some(key);
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingDependency {
+    pub service: &'static str,
+    pub field: &'static str,
+    pub dependency: &'static str,
+}
+
+impl MissingDependency {
+    pub fn new<S: 'static, D: 'static>(field: &'static str) -> Self {
+        Self {
+            service: type_name::<S>(),
+            field,
+            dependency: type_name::<D>(),
+        }
+    }
+}
+
+impl Display for MissingDependency {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "cannot create `{}`: dependency `{}` for field `{}` is not registered",
+            self.service, self.dependency, self.field
+        )
+    }
+}
+
+impl Error for MissingDependency {}
+
+pub struct RegisteredInjectable {
+    type_id: fn() -> TypeId,
+    register: fn(&mut ServiceContainer),
+}
+
+impl RegisteredInjectable {
+    pub const fn of<T: Injectable>() -> Self {
+        Self {
+            type_id: TypeId::of::<T>,
+            register: ServiceContainer::register_injectable::<T>,
+        }
+    }
+}
+
+inventory::collect!(RegisteredInjectable);
+
+pub fn missing_dependencies<T: Injectable>(container: &ServiceContainer) -> Vec<MissingDependency> {
+    T::dependencies()
+        .into_iter()
+        .filter(|dependency| {
+            !dependency.optional && !container.contains_keyed(dependency.type_id, dependency.key)
+        })
+        .map(|dependency| MissingDependency {
+            service: type_name::<T>(),
+            field: dependency.field,
+            dependency: dependency.type_name,
+        })
+        .collect()
+}
+
+// Explicit registrations win over auto-registration.
+pub(crate) fn register_collected(container: &mut ServiceContainer) {
+    for entry in inventory::iter::<RegisteredInjectable> {
+        if !container.contains((entry.type_id)()) {
+            (entry.register)(container);
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use std::any::type_name;
+use std::marker::PhantomData;
+use std::sync::{Arc, OnceLock};
+
+use crate::di::provider::ServiceProvider;
+use crate::di::service_scope::ServiceScope;
+
+pub struct Lazy<T> {
+    provider: ServiceProvider,
+    value: OnceLock<Arc<T>>,
+}
+
+impl<T> Lazy<T>
+where
+    T: Send + Sync + 'static,
+{
+    pub fn new(provider: ServiceProvider) -> Self {
+        Self {
+            provider,
+            value: OnceLock::new(),
+        }
+    }
+
+    pub fn get(&self) -> Arc<T> {
+        self.value.get_or_init(|| self.provider.get::<T>()).clone()
+    }
+
+    pub fn is_created(&self) -> bool {
+        self.value.get().is_some()
+    }
+}
+
+pub struct Factory<T> {
+    provider: ServiceProvider,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Factory<T>
+where
+    T: Send + Sync + 'static,
+{
+    pub fn new(provider: ServiceProvider) -> Self {
+        Self {
+            provider,
+            _marker: PhantomData,
+        }
+    }
+
+    // Every call builds a new instance, even for scoped and singleton services;
+    // disposables are released with the scope the factory is bound to.
+    pub fn create(&self) -> Arc<T> {
+        Self::create_from(&self.provider)
+    }
+
+    // Lets long-lived services build instances from the caller's scope.
+    pub fn create_in(&self, scope: &ServiceScope) -> Arc<T> {
+        Self::create_from(scope.provider())
+    }
+
+    fn create_from(provider: &ServiceProvider) -> Arc<T> {
+        provider
+            .create_new::<T>()
+            .unwrap_or_else(|| panic!("Service `{}` is not registered", type_name::<T>()))
+    }
+}
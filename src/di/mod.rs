@@ -1,11 +1,22 @@
 pub mod container;
 pub mod data;
+pub mod disposable;
+pub mod initializer;
+pub mod injectable;
+pub mod lazy;
 pub mod lifetime;
 pub mod provider;
 pub mod registration;
 pub mod service_scope;
+pub mod validation;
 
 pub use container::ServiceContainer;
 pub use data::DataProviderRegistry;
+pub use disposable::Disposable;
+pub use initializer::{InitializationError, ServiceInitializer};
+pub use injectable::Injectable;
+pub use lazy::{Factory, Lazy};
 pub use lifetime::ServiceLifetime;
 pub use provider::ServiceProvider;
+pub use service_scope::ServiceScope;
+pub use validation::{ContainerValidationError, DependencyIssue};
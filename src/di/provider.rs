@@ -1,47 +1,71 @@
 use std::any::type_name;
-use std::any::{Any, TypeId};
+use std::any::Any;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
 use std::sync::{Arc, Mutex};
 
+use crate::di::disposable::DisposeList;
+use crate::di::initializer::{InitializationError, ServiceInitializer};
 use crate::di::lifetime::ServiceLifetime;
-use crate::di::registration::Registration;
+use crate::di::registration::{Registration, ServiceKey};
 use crate::di::service_scope::ServiceScope;
 
 thread_local! {
-    static RESOLVE_STACK: RefCell<Vec<TypeId>> = RefCell::new(Vec::new());
-    static RESOLVE_NAME_STACK: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+    static RESOLVE_STACK: RefCell<Vec<ServiceKey>> = RefCell::new(Vec::new());
+    static RESOLVE_NAME_STACK: RefCell<Vec<String>> = RefCell::new(Vec::new());
 }
 
+type ServiceCache = Arc<Mutex<HashMap<(ServiceKey, usize), Arc<dyn Any + Send + Sync>>>>;
+
 pub struct ServiceProvider {
-    registrations: Arc<HashMap<TypeId, Registration>>,
-    singletons: Arc<Mutex<HashMap<TypeId, Arc<dyn Any + Send + Sync>>>>,
-    scoped: Arc<Mutex<HashMap<TypeId, Arc<dyn Any + Send + Sync>>>>,
+    registrations: Arc<HashMap<ServiceKey, Vec<Registration>>>,
+    initializers: Arc<Vec<Arc<dyn ServiceInitializer>>>,
+    initialized: Arc<tokio::sync::Mutex<bool>>,
+    singletons: ServiceCache,
+    singleton_disposables: DisposeList,
+    root_scoped: ServiceCache,
+    scoped: ServiceCache,
+    disposables: DisposeList,
 }
 
+// Clones share the scope they were taken from; use `create_scope` for a new one.
 impl Clone for ServiceProvider {
     fn clone(&self) -> Self {
         Self {
             registrations: Arc::clone(&self.registrations),
+            initializers: Arc::clone(&self.initializers),
+            initialized: Arc::clone(&self.initialized),
             singletons: Arc::clone(&self.singletons),
-            scoped: Arc::new(Mutex::new(HashMap::new())),
+            singleton_disposables: self.singleton_disposables.clone(),
+            root_scoped: Arc::clone(&self.root_scoped),
+            scoped: Arc::clone(&self.scoped),
+            disposables: self.disposables.clone(),
         }
     }
 }
 
 impl Default for ServiceProvider {
     fn default() -> Self {
-        Self::from_registrations(HashMap::new())
+        Self::from_registrations(HashMap::new(), Vec::new())
     }
 }
 
 impl ServiceProvider {
-    pub(crate) fn from_registrations(registrations: HashMap<TypeId, Registration>) -> Self {
+    pub(crate) fn from_registrations(
+        registrations: HashMap<ServiceKey, Vec<Registration>>,
+        initializers: Vec<Arc<dyn ServiceInitializer>>,
+    ) -> Self {
+        let root_scoped: ServiceCache = Arc::new(Mutex::new(HashMap::new()));
         Self {
             registrations: Arc::new(registrations),
+            initializers: Arc::new(initializers),
+            initialized: Arc::new(tokio::sync::Mutex::new(false)),
             singletons: Arc::new(Mutex::new(HashMap::new())),
-            scoped: Arc::new(Mutex::new(HashMap::new())),
+            singleton_disposables: DisposeList::default(),
+            root_scoped: Arc::clone(&root_scoped),
+            scoped: root_scoped,
+            disposables: DisposeList::default(),
         }
     }
 
@@ -49,13 +73,140 @@ impl ServiceProvider {
     where
         T: Send + Sync + 'static,
     {
-        let type_id = TypeId::of::<T>();
-        let type_name = Self::short_type_name::<T>();
+        self.resolve_key(ServiceKey::of::<T>())
+    }
+
+    pub fn resolve_keyed<T>(&self, key: &str) -> Option<Arc<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.resolve_key(ServiceKey::keyed::<T>(key))
+    }
+
+    pub fn resolve_all<T>(&self) -> Vec<Arc<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        let key = ServiceKey::of::<T>();
+        self.resolving::<T, _>(&key, || {
+            self.registrations
+                .get(&key)
+                .map(|registrations| {
+                    registrations
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(index, registration)| {
+                            self.resolve_registration(&key, index, registration)
+                        })
+                        .collect()
+                })
+                .unwrap_or_default()
+        })
+    }
+
+    pub fn get<T>(&self) -> Arc<T>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.resolve::<T>().unwrap_or_else(|| {
+            panic!(
+                "Service `{}` is not registered",
+                Self::short_type_name::<T>()
+            )
+        })
+    }
+
+    pub fn get_keyed<T>(&self, key: &str) -> Arc<T>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.resolve_keyed::<T>(key).unwrap_or_else(|| {
+            panic!(
+                "Service `{}` with key `{}` is not registered",
+                Self::short_type_name::<T>(),
+                key
+            )
+        })
+    }
 
-        if RESOLVE_STACK.with(|stack| stack.borrow().contains(&type_id)) {
+    // Runs the factory of the last registration whatever its lifetime, skipping the caches.
+    pub(crate) fn create_new<T>(&self) -> Option<Arc<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        let key = ServiceKey::of::<T>();
+        self.resolving::<T, _>(&key, || {
+            let registration = self.registrations.get(&key)?.last()?;
+            self.resolve_transient(registration)
+        })
+    }
+
+    pub async fn initialize(&self) -> Result<(), InitializationError> {
+        let mut initialized = self.initialized.lock().await;
+        if *initialized {
+            return Ok(());
+        }
+        for initializer in self.initializers.iter() {
+            log::debug!("Initializing {}", initializer.name());
+            initializer
+                .initialize(self.as_arc())
+                .await
+                .map_err(|err| InitializationError {
+                    name: initializer.name(),
+                    message: err.to_string(),
+                })?;
+        }
+        *initialized = true;
+        Ok(())
+    }
+
+    pub fn create_scope(&self) -> ServiceScope {
+        ServiceScope {
+            provider: ServiceProvider {
+                scoped: Arc::new(Mutex::new(HashMap::new())),
+                disposables: DisposeList::default(),
+                ..self.clone()
+            },
+        }
+    }
+
+    // Disposes this provider's own scoped and transient instances, then singletons.
+    pub async fn dispose(&self) {
+        self.dispose_scope().await;
+        self.singleton_disposables.dispose().await;
+        self.singletons.lock().expect("cache poisoned").clear();
+    }
+
+    // Cached services may hold `Lazy`/`Factory` handles back to this scope, so clear them.
+    pub(crate) async fn dispose_scope(&self) {
+        self.disposables.dispose().await;
+        self.scoped.lock().expect("cache poisoned").clear();
+    }
+
+    fn resolve_key<T>(&self, key: ServiceKey) -> Option<Arc<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.resolving::<T, _>(&key, || {
+            let registrations = self.registrations.get(&key)?;
+            let index = registrations.len().checked_sub(1)?;
+            self.resolve_registration(&key, index, &registrations[index])
+        })
+    }
+
+    fn resolving<T, R>(&self, key: &ServiceKey, resolve: impl FnOnce() -> R) -> R
+    where
+        T: 'static,
+    {
+        let type_name = match &key.key {
+            Some(name) => format!("{}[\"{}\"]", Self::short_type_name::<T>(), name),
+            None => Self::short_type_name::<T>().to_string(),
+        };
+
+        if RESOLVE_STACK.with(|stack| stack.borrow().contains(key)) {
             let cycle = RESOLVE_NAME_STACK.with(|names| {
                 let mut names = names.borrow().clone();
-                names.push(type_name);
+                names.push(type_name.clone());
                 names.join(" -> ")
             });
             panic!(
@@ -65,17 +216,9 @@ impl ServiceProvider {
         }
 
         RESOLVE_NAME_STACK.with(|names| names.borrow_mut().push(type_name));
-        push_resolving(type_id);
+        push_resolving(key.clone());
 
-        let result = catch_unwind(AssertUnwindSafe(|| {
-            let registration = self.registrations.get(&type_id).cloned()?;
-
-            match registration.lifetime {
-                ServiceLifetime::Singleton => self.resolve_cached(&registration, &self.singletons),
-                ServiceLifetime::Scoped => self.resolve_cached(&registration, &self.scoped),
-                ServiceLifetime::Transient => self.resolve_transient(&registration),
-            }
-        }));
+        let result = catch_unwind(AssertUnwindSafe(resolve));
 
         pop_resolving();
         RESOLVE_NAME_STACK.with(|names| {
@@ -89,54 +232,62 @@ impl ServiceProvider {
         }
     }
 
-    pub fn get<T>(&self) -> Arc<T>
+    fn resolve_registration<T>(
+        &self,
+        key: &ServiceKey,
+        index: usize,
+        registration: &Registration,
+    ) -> Option<Arc<T>>
     where
         T: Send + Sync + 'static,
     {
-        self.resolve::<T>().unwrap_or_else(|| {
-            panic!(
-                "Service `{}` is not registered",
-                Self::short_type_name::<T>()
-            )
-        })
-    }
-
-    pub fn create_scope(&self) -> ServiceScope {
-        ServiceScope {
-            provider: ServiceProvider {
-                registrations: Arc::clone(&self.registrations),
-                singletons: Arc::clone(&self.singletons),
-                scoped: Arc::new(Mutex::new(HashMap::new())),
-            },
+        match registration.lifetime {
+            ServiceLifetime::Singleton => {
+                let root = self.singleton_root();
+                root.resolve_cached(
+                    key,
+                    index,
+                    registration,
+                    &root.singletons,
+                    &root.singleton_disposables,
+                )
+            }
+            ServiceLifetime::Scoped => {
+                self.resolve_cached(key, index, registration, &self.scoped, &self.disposables)
+            }
+            ServiceLifetime::Transient => self.resolve_transient(registration),
         }
     }
 
     fn resolve_cached<T>(
         &self,
+        key: &ServiceKey,
+        index: usize,
         registration: &Registration,
-        cache: &Arc<Mutex<HashMap<TypeId, Arc<dyn Any + Send + Sync>>>>,
+        cache: &ServiceCache,
+        disposables: &DisposeList,
     ) -> Option<Arc<T>>
     where
         T: Send + Sync + 'static,
     {
-        let type_id = TypeId::of::<T>();
+        let key = (key.clone(), index);
 
         if let Some(existing) = {
             let guard = cache.lock().expect("cache poisoned");
-            guard.get(&type_id).cloned()
+            guard.get(&key).cloned()
         } {
             return existing.downcast::<T>().ok();
         }
 
-        let created = (registration.factory)(self.as_arc());
+        let created = (registration.factory)(self.as_arc())?;
 
         let stored = {
             let mut guard = cache.lock().expect("cache poisoned");
-            guard
-                .entry(type_id)
-                .or_insert_with(|| created.clone())
-                .clone()
+            guard.entry(key).or_insert_with(|| created.clone()).clone()
         };
+        if Arc::ptr_eq(&stored, &created) {
+            track_disposable(registration, &created, disposables);
+        }
 
         stored.downcast::<T>().ok()
     }
@@ -145,7 +296,9 @@ impl ServiceProvider {
     where
         T: Send + Sync + 'static,
     {
-        (registration.factory)(self.as_arc()).downcast::<T>().ok()
+        let created = (registration.factory)(self.as_arc())?;
+        track_disposable(registration, &created, &self.disposables);
+        created.downcast::<T>().ok()
     }
 
     fn short_type_name<T>() -> &'static str {
@@ -155,17 +308,36 @@ impl ServiceProvider {
             .unwrap_or(type_name::<T>())
     }
 
+    // Singletons outlive every request scope, so their factories resolve from the root
+    // scope and anything they create is disposed along with the singletons.
+    fn singleton_root(&self) -> ServiceProvider {
+        ServiceProvider {
+            scoped: Arc::clone(&self.root_scoped),
+            disposables: self.singleton_disposables.clone(),
+            ..self.clone()
+        }
+    }
+
     fn as_arc(&self) -> Arc<ServiceProvider> {
-        Arc::new(ServiceProvider {
-            registrations: Arc::clone(&self.registrations),
-            singletons: Arc::clone(&self.singletons),
-            scoped: Arc::clone(&self.scoped),
-        })
+        Arc::new(self.clone())
+    }
+}
+
+fn track_disposable(
+    registration: &Registration,
+    created: &Arc<dyn Any + Send + Sync>,
+    disposables: &DisposeList,
+) {
+    if let Some(disposable) = registration
+        .disposer
+        .and_then(|disposer| disposer(created.clone()))
+    {
+        disposables.push(disposable);
     }
 }
 
-fn push_resolving(type_id: TypeId) {
-    RESOLVE_STACK.with(|stack| stack.borrow_mut().push(type_id));
+fn push_resolving(key: ServiceKey) {
+    RESOLVE_STACK.with(|stack| stack.borrow_mut().push(key));
 }
 
 fn pop_resolving() {
@@ -1,21 +1,86 @@
-﻿use std::any::Any;
+﻿use std::any::{Any, TypeId};
 use std::sync::Arc;
 
+use crate::di::disposable::Disposer;
+use crate::di::injectable::Dependency;
 use crate::di::lifetime::ServiceLifetime;
 use crate::di::provider::ServiceProvider;
 
+// `None` means the service could not be created; it resolves as if it were not registered.
+pub(crate) type ServiceFactory =
+    Arc<dyn Fn(Arc<ServiceProvider>) -> Option<Arc<dyn Any + Send + Sync>> + Send + Sync>;
+
+pub(crate) type Decorator = Arc<
+    dyn Fn(Arc<dyn Any + Send + Sync>, Arc<ServiceProvider>) -> Arc<dyn Any + Send + Sync>
+        + Send
+        + Sync,
+>;
+
 #[derive(Clone)]
 pub(crate) struct Registration {
+    pub(crate) service: &'static str,
     pub(crate) lifetime: ServiceLifetime,
-    pub(crate) factory:
-        Arc<dyn Fn(Arc<ServiceProvider>) -> Arc<dyn Any + Send + Sync> + Send + Sync>,
+    pub(crate) dependencies: Vec<Dependency>,
+    // `Lazy<T>` resolves its dependency on first use, so it does not close a cycle.
+    pub(crate) deferred: bool,
+    pub(crate) disposer: Option<Disposer>,
+    pub(crate) factory: ServiceFactory,
 }
 
 impl Registration {
     pub(crate) fn new(
+        service: &'static str,
         lifetime: ServiceLifetime,
-        factory: Arc<dyn Fn(Arc<ServiceProvider>) -> Arc<dyn Any + Send + Sync> + Send + Sync>,
+        factory: ServiceFactory,
     ) -> Self {
-        Self { lifetime, factory }
+        Self {
+            service,
+            lifetime,
+            dependencies: Vec::new(),
+            deferred: false,
+            disposer: None,
+            factory,
+        }
+    }
+
+    pub(crate) fn with_dependencies(mut self, dependencies: Vec<Dependency>) -> Self {
+        self.dependencies = dependencies;
+        self
+    }
+
+    pub(crate) fn decorate(&mut self, decorator: Decorator) {
+        let inner = Arc::clone(&self.factory);
+        self.factory = Arc::new(move |provider: Arc<ServiceProvider>| {
+            Some(decorator(inner(provider.clone())?, provider))
+        });
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct ServiceKey {
+    pub(crate) type_id: TypeId,
+    pub(crate) key: Option<String>,
+}
+
+impl ServiceKey {
+    pub(crate) fn of<T: 'static>() -> Self {
+        Self {
+            type_id: TypeId::of::<T>(),
+            key: None,
+        }
+    }
+
+    pub(crate) fn keyed<T: 'static>(key: &str) -> Self {
+        Self {
+            type_id: TypeId::of::<T>(),
+            key: Some(key.to_string()),
+        }
+    }
+
+    pub(crate) fn of_dependency(dependency: &Dependency) -> Self {
+        Self {
+            type_id: dependency.type_id,
+            key: dependency.key.map(str::to_string),
+        }
     }
 }
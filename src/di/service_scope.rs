@@ -7,10 +7,32 @@ pub struct ServiceScope {
 }
 
 impl ServiceScope {
+    pub fn provider(&self) -> &ServiceProvider {
+        &self.provider
+    }
+
     pub fn resolve<T>(&self) -> Option<Arc<T>>
     where
         T: Send + Sync + 'static,
     {
         self.provider.resolve::<T>()
     }
+
+    pub fn resolve_keyed<T>(&self, key: &str) -> Option<Arc<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.provider.resolve_keyed::<T>(key)
+    }
+
+    pub fn resolve_all<T>(&self) -> Vec<Arc<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.provider.resolve_all::<T>()
+    }
+
+    pub async fn dispose(&self) {
+        self.provider.dispose_scope().await;
+    }
 }
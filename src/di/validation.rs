@@ -0,0 +1,185 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use crate::di::injectable::MissingDependency;
+use crate::di::lifetime::ServiceLifetime;
+use crate::di::registration::{Registration, ServiceKey};
+
+type Registrations = HashMap<ServiceKey, Vec<Registration>>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DependencyIssue {
+    Missing(MissingDependency),
+    Cycle(Vec<String>),
+    Captive { service: String, path: Vec<String> },
+}
+
+impl Display for DependencyIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            DependencyIssue::Missing(missing) => write!(f, "{}", missing),
+            DependencyIssue::Cycle(path) => {
+                write!(f, "dependency cycle: {}", quoted(path).join(" -> "))
+            }
+            DependencyIssue::Captive { service, path } => {
+                let (scoped, through) = path.split_last().expect("captive path is empty");
+                write!(
+                    f,
+                    "captive dependency: singleton `{}` depends on scoped `{}`",
+                    service, scoped
+                )?;
+                if !through.is_empty() {
+                    write!(f, " through {}", quoted(through).join(" -> "))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerValidationError {
+    pub issues: Vec<DependencyIssue>,
+}
+
+impl Display for ContainerValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "Service container validation failed:")?;
+        for issue in &self.issues {
+            write!(f, "\n  - {}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ContainerValidationError {}
+
+pub(crate) fn validate_registrations(
+    registrations: &Registrations,
+) -> Result<(), ContainerValidationError> {
+    let mut keys: Vec<&ServiceKey> = registrations.keys().collect();
+    keys.sort_by_cached_key(|key| display_name(registrations, key));
+
+    let mut issues = Vec::new();
+    for key in &keys {
+        for registration in &registrations[*key] {
+            check_registration(registrations, key, registration, &mut issues);
+        }
+    }
+
+    let mut visited = HashSet::new();
+    let mut stack = Vec::new();
+    for key in &keys {
+        find_cycles(registrations, key, &mut visited, &mut stack, &mut issues);
+    }
+
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(ContainerValidationError { issues })
+    }
+}
+
+fn check_registration(
+    registrations: &Registrations,
+    key: &ServiceKey,
+    registration: &Registration,
+    issues: &mut Vec<DependencyIssue>,
+) {
+    for dependency in &registration.dependencies {
+        let dependency_key = ServiceKey::of_dependency(dependency);
+        if !registrations.contains_key(&dependency_key) {
+            if !dependency.optional {
+                issues.push(DependencyIssue::Missing(MissingDependency {
+                    service: registration.service,
+                    field: dependency.field,
+                    dependency: dependency.type_name,
+                }));
+            }
+            continue;
+        }
+        if registration.lifetime == ServiceLifetime::Singleton {
+            let mut seen = HashSet::new();
+            if let Some(path) = scoped_path(registrations, &dependency_key, &mut seen) {
+                issues.push(DependencyIssue::Captive {
+                    service: display_name(registrations, key),
+                    path,
+                });
+            }
+        }
+    }
+}
+
+// Transients resolved from a singleton factory live as long as the singleton,
+// so a scoped service behind them is captured just the same.
+fn scoped_path(
+    registrations: &Registrations,
+    key: &ServiceKey,
+    seen: &mut HashSet<ServiceKey>,
+) -> Option<Vec<String>> {
+    if !seen.insert(key.clone()) {
+        return None;
+    }
+    let registration = registrations.get(key)?.last()?;
+    match registration.lifetime {
+        ServiceLifetime::Scoped => Some(vec![display_name(registrations, key)]),
+        ServiceLifetime::Singleton => None,
+        ServiceLifetime::Transient => registration.dependencies.iter().find_map(|dependency| {
+            let mut path =
+                scoped_path(registrations, &ServiceKey::of_dependency(dependency), seen)?;
+            path.insert(0, display_name(registrations, key));
+            Some(path)
+        }),
+    }
+}
+
+fn find_cycles(
+    registrations: &Registrations,
+    key: &ServiceKey,
+    visited: &mut HashSet<ServiceKey>,
+    stack: &mut Vec<ServiceKey>,
+    issues: &mut Vec<DependencyIssue>,
+) {
+    if let Some(start) = stack.iter().position(|entry| entry == key) {
+        let mut path: Vec<String> = stack[start..]
+            .iter()
+            .map(|entry| display_name(registrations, entry))
+            .collect();
+        path.push(display_name(registrations, key));
+        issues.push(DependencyIssue::Cycle(path));
+        return;
+    }
+    if !visited.insert(key.clone()) {
+        return;
+    }
+
+    stack.push(key.clone());
+    for registration in registrations.get(key).into_iter().flatten() {
+        if registration.deferred {
+            continue;
+        }
+        for dependency in &registration.dependencies {
+            let dependency_key = ServiceKey::of_dependency(dependency);
+            if registrations.contains_key(&dependency_key) {
+                find_cycles(registrations, &dependency_key, visited, stack, issues);
+            }
+        }
+    }
+    stack.pop();
+}
+
+fn display_name(registrations: &Registrations, key: &ServiceKey) -> String {
+    let service = registrations
+        .get(key)
+        .and_then(|entries| entries.last())
+        .map_or("<unknown>", |registration| registration.service);
+    match &key.key {
+        Some(name) => format!("{}[\"{}\"]", service, name),
+        None => service.to_string(),
+    }
+}
+
+fn quoted(names: &[String]) -> Vec<String> {
+    names.iter().map(|name| format!("`{}`", name)).collect()
+}
@@ -0,0 +1,76 @@
+use serde::de::DeserializeOwned;
+use std::str::FromStr;
+
+use crate::http::context::HttpContext;
+use crate::pipeline::pipeline::PipelineError;
+use crate::result::into_response::{IntoResponse, ResponseValue};
+use crate::result::HttpError;
+
+pub fn route_param<T: FromStr>(context: &HttpContext, name: &str) -> Result<T, HttpError> {
+    optional_route_param(context, name)?
+        .ok_or_else(|| HttpError::new(400, &format!("missing route parameter `{}`", name)))
+}
+
+pub fn optional_route_param<T: FromStr>(
+    context: &HttpContext,
+    name: &str,
+) -> Result<Option<T>, HttpError> {
+    let value = context
+        .route()
+        .and_then(|route| route.params().get(name).cloned());
+    parse("route parameter", name, value)
+}
+
+pub fn query_param<T: FromStr>(context: &HttpContext, name: &str) -> Result<T, HttpError> {
+    optional_query_param(context, name)?
+        .ok_or_else(|| HttpError::new(400, &format!("missing query parameter `{}`", name)))
+}
+
+pub fn optional_query_param<T: FromStr>(
+    context: &HttpContext,
+    name: &str,
+) -> Result<Option<T>, HttpError> {
+    parse("query parameter", name, context.request().query_param(name))
+}
+
+pub fn body<T: DeserializeOwned>(context: &HttpContext) -> Result<T, HttpError> {
+    context
+        .read_json()
+        .map_err(|err| HttpError::new(400, &format!("invalid request body: {}", err.message())))
+}
+
+pub trait IntoHandlerResult {
+    fn into_handler_result(self) -> Result<ResponseValue, PipelineError>;
+}
+
+impl<T> IntoHandlerResult for T
+where
+    T: IntoResponse + Send + Sync + 'static,
+{
+    fn into_handler_result(self) -> Result<ResponseValue, PipelineError> {
+        Ok(ResponseValue::new(self))
+    }
+}
+
+impl<T> IntoHandlerResult for Result<T, PipelineError>
+where
+    T: IntoResponse + Send + Sync + 'static,
+{
+    fn into_handler_result(self) -> Result<ResponseValue, PipelineError> {
+        self.map(ResponseValue::new)
+    }
+}
+
+fn parse<T: FromStr>(
+    kind: &str,
+    name: &str,
+    value: Option<String>,
+) -> Result<Option<T>, HttpError> {
+    match value {
+        Some(raw) => raw
+            .parse::<T>()
+            .map(Some)
+            .map_err(|_| HttpError::new(400, &format!("invalid {} `{}`: `{}`", kind, name, raw))),
+        None => Ok(None),
+    }
+}
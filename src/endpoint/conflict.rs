@@ -0,0 +1,202 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use crate::endpoint::metadata::EndpointMetadata;
+use crate::routing::route::Route;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RouteOrigin {
+    Manual,
+    Controller(&'static str),
+    Attribute,
+    Entity(&'static str),
+}
+
+impl Display for RouteOrigin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            RouteOrigin::Manual => write!(f, "manual registration"),
+            RouteOrigin::Controller(name) => write!(f, "controller `{}`", name),
+            RouteOrigin::Attribute => write!(f, "route attribute"),
+            RouteOrigin::Entity(name) => write!(f, "entity `{}`", name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteRegistration {
+    pub route: Route,
+    pub origin: RouteOrigin,
+    pub handler: Option<&'static str>,
+}
+
+impl Display for RouteRegistration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{} from {}", self.route, self.origin)?;
+        if let Some(handler) = self.handler {
+            write!(f, " (handler `{}`)", handler)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteConflictKind {
+    Duplicate,
+    Ambiguous,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteConflict {
+    pub kind: RouteConflictKind,
+    pub first: RouteRegistration,
+    pub second: RouteRegistration,
+}
+
+impl Display for RouteConflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let label = match self.kind {
+            RouteConflictKind::Duplicate => "duplicate route",
+            RouteConflictKind::Ambiguous => "ambiguous routes",
+        };
+        write!(f, "{}:\n    {}\n    {}", label, self.first, self.second)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteConflictReport {
+    conflicts: Vec<RouteConflict>,
+}
+
+impl RouteConflictReport {
+    pub(crate) fn new(conflicts: Vec<RouteConflict>) -> Self {
+        Self { conflicts }
+    }
+
+    pub fn conflicts(&self) -> &[RouteConflict] {
+        &self.conflicts
+    }
+}
+
+impl Display for RouteConflictReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{} route conflict(s) detected", self.conflicts.len())?;
+        for conflict in &self.conflicts {
+            write!(f, "\n  - {}", conflict)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for RouteConflictReport {}
+
+pub(crate) fn conflict_kind(
+    left: (&Route, &EndpointMetadata),
+    right: (&Route, &EndpointMetadata),
+) -> Option<RouteConflictKind> {
+    let (left_route, left_metadata) = left;
+    let (right_route, right_metadata) = right;
+
+    if !left_route
+        .method()
+        .eq_ignore_ascii_case(right_route.method())
+    {
+        return None;
+    }
+    if !hosts_overlap(left_route.host(), right_route.host()) {
+        return None;
+    }
+    if !versions_overlap(left_metadata, right_metadata) {
+        return None;
+    }
+
+    let left_segments = segments(left_route.path());
+    let right_segments = segments(right_route.path());
+    if left_segments.len() != right_segments.len() {
+        return None;
+    }
+
+    let mut identical = true;
+    let mut left_static = 0;
+    let mut right_static = 0;
+    for (left, right) in left_segments.iter().zip(right_segments.iter()) {
+        match (param_name(left), param_name(right)) {
+            (None, None) => {
+                if left != right {
+                    return None;
+                }
+                left_static += 1;
+                right_static += 1;
+            }
+            (Some(left), Some(right)) => {
+                identical &= left == right;
+            }
+            (None, Some(_)) => {
+                identical = false;
+                left_static += 1;
+            }
+            (Some(_), None) => {
+                identical = false;
+                right_static += 1;
+            }
+        }
+    }
+
+    if identical {
+        Some(RouteConflictKind::Duplicate)
+    } else if left_static == right_static {
+        Some(RouteConflictKind::Ambiguous)
+    } else {
+        None
+    }
+}
+
+fn hosts_overlap(left: Option<&str>, right: Option<&str>) -> bool {
+    match (left, right) {
+        (None, None) => true,
+        (Some(left), Some(right)) => {
+            let left_labels: Vec<&str> = left.split('.').collect();
+            let right_labels: Vec<&str> = right.split('.').collect();
+            left_labels.len() == right_labels.len()
+                && left_labels
+                    .iter()
+                    .zip(right_labels.iter())
+                    .all(
+                        |(left, right)| match (param_name(left), param_name(right)) {
+                            (Some(_), Some(_)) => true,
+                            (None, None) => left.eq_ignore_ascii_case(right),
+                            _ => false,
+                        },
+                    )
+        }
+        _ => false,
+    }
+}
+
+fn versions_overlap(left: &EndpointMetadata, right: &EndpointMetadata) -> bool {
+    match (left.is_version_neutral(), right.is_version_neutral()) {
+        (true, true) => true,
+        (false, false) => left
+            .api_versions()
+            .iter()
+            .any(|version| right.supports_api_version(version)),
+        _ => false,
+    }
+}
+
+fn segments(path: &str) -> Vec<&str> {
+    let trimmed = path.trim_matches('/');
+    if trimmed.is_empty() {
+        Vec::new()
+    } else {
+        trimmed.split('/').collect()
+    }
+}
+
+fn param_name(segment: &str) -> Option<&str> {
+    if segment.len() >= 3 && segment.starts_with('{') && segment.ends_with('}') {
+        Some(&segment[1..segment.len() - 1])
+    } else {
+        None
+    }
+}
@@ -0,0 +1,96 @@
+use crate::endpoint::http_handler::HttpHandler;
+use crate::endpoint::registry::EndpointRegistry;
+use crate::endpoint::route::{join_paths, RouteBuilder};
+use crate::security::policy::Policy;
+
+pub struct RouteGroup<'a> {
+    registry: &'a mut EndpointRegistry,
+    prefix: String,
+    host: Option<String>,
+    policy: Option<Policy>,
+}
+
+impl<'a> RouteGroup<'a> {
+    pub(crate) fn new(registry: &'a mut EndpointRegistry, prefix: &str) -> Self {
+        Self {
+            registry,
+            prefix: prefix.to_string(),
+            host: None,
+            policy: None,
+        }
+    }
+
+    pub fn with_host(mut self, host: &str) -> Self {
+        self.host = Some(host.to_string());
+        self
+    }
+
+    pub fn with_policy(mut self, policy: Policy) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    pub fn group(&mut self, prefix: &str) -> RouteGroup<'_> {
+        RouteGroup {
+            prefix: join_paths(&self.prefix, prefix),
+            host: self.host.clone(),
+            policy: self.policy.clone(),
+            registry: self.registry,
+        }
+    }
+
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    pub fn host(&self) -> Option<&str> {
+        self.host.as_deref()
+    }
+
+    pub fn route(&mut self, builder: RouteBuilder) -> &mut Self {
+        let mut builder = builder.with_path_prefix(&self.prefix);
+        if let Some(host) = self.host.as_deref() {
+            builder = builder.with_host(host);
+        }
+        if let Some(policy) = self.policy.clone() {
+            builder = builder.with_policy(policy);
+        }
+        self.registry.add_endpoint_route(builder.build());
+        self
+    }
+
+    pub fn get<H>(&mut self, path: &str, handler: H) -> &mut Self
+    where
+        H: HttpHandler + Send + Sync + 'static,
+    {
+        self.route(RouteBuilder::new("GET", path, handler))
+    }
+
+    pub fn post<H>(&mut self, path: &str, handler: H) -> &mut Self
+    where
+        H: HttpHandler + Send + Sync + 'static,
+    {
+        self.route(RouteBuilder::new("POST", path, handler))
+    }
+
+    pub fn put<H>(&mut self, path: &str, handler: H) -> &mut Self
+    where
+        H: HttpHandler + Send + Sync + 'static,
+    {
+        self.route(RouteBuilder::new("PUT", path, handler))
+    }
+
+    pub fn delete<H>(&mut self, path: &str, handler: H) -> &mut Self
+    where
+        H: HttpHandler + Send + Sync + 'static,
+    {
+        self.route(RouteBuilder::new("DELETE", path, handler))
+    }
+
+    pub fn patch<H>(&mut self, path: &str, handler: H) -> &mut Self
+    where
+        H: HttpHandler + Send + Sync + 'static,
+    {
+        self.route(RouteBuilder::new("PATCH", path, handler))
+    }
+}
@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use crate::routing::versioning::ApiVersion;
 use crate::security::policy::Policy;
 use crate::validation::AnyValidator;
 
@@ -11,6 +12,9 @@ pub struct EndpointMetadata {
     tags: Vec<String>,
     policy: Option<Policy>,
     validators: Vec<Arc<dyn AnyValidator>>,
+    api_versions: Vec<ApiVersion>,
+    deprecated_api_versions: Vec<ApiVersion>,
+    handler_type: Option<&'static str>,
 }
 
 impl EndpointMetadata {
@@ -22,6 +26,9 @@ impl EndpointMetadata {
             tags: Vec::new(),
             policy: None,
             validators: Vec::new(),
+            api_versions: Vec::new(),
+            deprecated_api_versions: Vec::new(),
+            handler_type: None,
         }
     }
 
@@ -33,6 +40,11 @@ impl EndpointMetadata {
         &self.route_pattern
     }
 
+    pub(crate) fn with_route_pattern(mut self, route_pattern: &str) -> Self {
+        self.route_pattern = route_pattern.to_string();
+        self
+    }
+
     pub fn name(&self) -> Option<&str> {
         self.name.as_deref()
     }
@@ -75,4 +87,45 @@ impl EndpointMetadata {
         self.tags = tags.into_iter().map(Into::into).collect();
         self
     }
+
+    pub fn with_api_versions<I>(mut self, versions: I) -> Self
+    where
+        I: IntoIterator<Item = ApiVersion>,
+    {
+        self.api_versions = versions.into_iter().collect();
+        self
+    }
+
+    pub fn deprecate_api_versions<I>(mut self, versions: I) -> Self
+    where
+        I: IntoIterator<Item = ApiVersion>,
+    {
+        self.deprecated_api_versions = versions.into_iter().collect();
+        self
+    }
+
+    pub fn api_versions(&self) -> &[ApiVersion] {
+        &self.api_versions
+    }
+
+    pub fn deprecated_api_versions(&self) -> &[ApiVersion] {
+        &self.deprecated_api_versions
+    }
+
+    pub fn is_version_neutral(&self) -> bool {
+        self.api_versions.is_empty()
+    }
+
+    pub fn supports_api_version(&self, version: &ApiVersion) -> bool {
+        self.api_versions.contains(version)
+    }
+
+    pub fn with_handler_type(mut self, handler_type: &'static str) -> Self {
+        self.handler_type = Some(handler_type);
+        self
+    }
+
+    pub fn handler_type(&self) -> Option<&'static str> {
+        self.handler_type
+    }
 }
@@ -1,4 +1,7 @@
+pub mod binding;
+pub mod conflict;
 pub mod endpoint;
+pub mod group;
 pub mod http_endpoint;
 pub mod http_endpoint_handler;
 pub mod http_handler;
@@ -1,8 +1,13 @@
+use std::any::type_name;
 use std::sync::Arc;
 
 use crate::controller::attribute_route;
 use crate::controller::controller::Controller;
+use crate::endpoint::conflict::{
+    conflict_kind, RouteConflict, RouteConflictReport, RouteOrigin, RouteRegistration,
+};
 use crate::endpoint::endpoint::Endpoint;
+use crate::endpoint::group::RouteGroup;
 use crate::endpoint::http_endpoint::HttpEndpoint;
 use crate::endpoint::http_endpoint_handler::HttpEndpointHandler;
 use crate::endpoint::http_handler::HttpHandler;
@@ -14,6 +19,7 @@ use crate::security::policy::Policy;
 pub struct EndpointRegistry {
     routes: Vec<Route>,
     endpoints: Vec<Arc<dyn Endpoint>>,
+    origins: Vec<RouteOrigin>,
 }
 
 impl EndpointRegistry {
@@ -22,25 +28,22 @@ impl EndpointRegistry {
     }
 
     pub fn register<C: Controller>(&mut self) {
-        let routes = C::routes();
-        for endpoint_route in routes {
-            self.add_endpoint_route(endpoint_route);
+        let origin = RouteOrigin::Controller(short_type_name::<C>());
+        for endpoint_route in C::routes() {
+            self.add_endpoint_route_with_origin(endpoint_route, origin.clone());
         }
     }
 
     pub fn register_attribute_routes(&mut self) {
         for endpoint_route in attribute_route::collected_routes() {
-            let method = endpoint_route.route.method().to_string();
-            let path = endpoint_route.route.path().to_string();
-            if self.has_route(&method, &path) {
+            if self.has_same_registration(&endpoint_route) {
                 log::debug!(
-                    "Skipping duplicate attribute route registration for {} {}",
-                    method,
-                    path
+                    "Skipping duplicate attribute route registration for {}",
+                    endpoint_route.route
                 );
                 continue;
             }
-            self.add_endpoint_route(endpoint_route);
+            self.add_endpoint_route_with_origin(endpoint_route, RouteOrigin::Attribute);
         }
     }
 
@@ -72,12 +75,27 @@ impl EndpointRegistry {
         self.add_endpoint_route(RouteBuilder::new("DELETE", path, handler).build());
     }
 
+    pub fn patch<H>(&mut self, path: &str, handler: H)
+    where
+        H: HttpHandler + Send + Sync + 'static,
+    {
+        self.add_endpoint_route(RouteBuilder::new("PATCH", path, handler).build());
+    }
+
+    pub fn group(&mut self, prefix: &str) -> RouteGroup<'_> {
+        RouteGroup::new(self, prefix)
+    }
+
+    pub fn host(&mut self, host: &str) -> RouteGroup<'_> {
+        RouteGroup::new(self, "").with_host(host)
+    }
+
     pub fn add<H>(&mut self, method: &str, path: &str, handler: H)
     where
         H: HttpHandler + Send + Sync + 'static,
     {
         let route = Route::new(method, path);
-        let metadata = EndpointMetadata::new(method, path);
+        let metadata = EndpointMetadata::new(method, path).with_handler_type(type_name::<H>());
         let endpoint = Arc::new(HttpEndpoint::new(
             HttpEndpointHandler::new(handler),
             metadata,
@@ -90,7 +108,9 @@ impl EndpointRegistry {
         H: HttpHandler + Send + Sync + 'static,
     {
         let route = Route::new(method, path);
-        let metadata = EndpointMetadata::new(method, path).require_policy(policy);
+        let metadata = EndpointMetadata::new(method, path)
+            .with_handler_type(type_name::<H>())
+            .require_policy(policy);
         let endpoint = Arc::new(HttpEndpoint::new(
             HttpEndpointHandler::new(handler),
             metadata,
@@ -99,18 +119,90 @@ impl EndpointRegistry {
     }
 
     pub fn add_route(&mut self, route: Route, endpoint: Arc<dyn Endpoint>) {
+        self.add_route_with_origin(route, endpoint, RouteOrigin::Manual);
+    }
+
+    pub fn add_route_with_origin(
+        &mut self,
+        route: Route,
+        endpoint: Arc<dyn Endpoint>,
+        origin: RouteOrigin,
+    ) {
         self.routes.push(route);
         self.endpoints.push(endpoint);
+        self.origins.push(origin);
     }
 
     pub fn add_endpoint_route(&mut self, endpoint_route: EndpointRoute) {
         self.add_route(endpoint_route.route, endpoint_route.endpoint);
     }
 
-    fn has_route(&self, method: &str, path: &str) -> bool {
+    pub fn add_endpoint_route_with_origin(
+        &mut self,
+        endpoint_route: EndpointRoute,
+        origin: RouteOrigin,
+    ) {
+        self.add_route_with_origin(endpoint_route.route, endpoint_route.endpoint, origin);
+    }
+
+    fn has_same_registration(&self, endpoint_route: &EndpointRoute) -> bool {
+        let handler = endpoint_route.endpoint.metadata().handler_type();
         self.routes
             .iter()
-            .any(|route| route.method() == method && route.path() == path)
+            .zip(self.endpoints.iter())
+            .any(|(route, endpoint)| {
+                route == &endpoint_route.route && endpoint.metadata().handler_type() == handler
+            })
+    }
+
+    pub fn origins(&self) -> &[RouteOrigin] {
+        &self.origins
+    }
+
+    pub fn registrations(&self) -> Vec<RouteRegistration> {
+        (0..self.routes.len())
+            .map(|index| self.registration(index))
+            .collect()
+    }
+
+    pub fn validate(&self) -> Result<(), RouteConflictReport> {
+        let mut conflicts = Vec::new();
+        for (left, (left_route, left_endpoint)) in
+            self.routes.iter().zip(self.endpoints.iter()).enumerate()
+        {
+            for (right, (right_route, right_endpoint)) in self
+                .routes
+                .iter()
+                .zip(self.endpoints.iter())
+                .enumerate()
+                .skip(left + 1)
+            {
+                if let Some(kind) = conflict_kind(
+                    (left_route, left_endpoint.metadata()),
+                    (right_route, right_endpoint.metadata()),
+                ) {
+                    conflicts.push(RouteConflict {
+                        kind,
+                        first: self.registration(left),
+                        second: self.registration(right),
+                    });
+                }
+            }
+        }
+
+        if conflicts.is_empty() {
+            Ok(())
+        } else {
+            Err(RouteConflictReport::new(conflicts))
+        }
+    }
+
+    fn registration(&self, index: usize) -> RouteRegistration {
+        RouteRegistration {
+            route: self.routes[index].clone(),
+            origin: self.origins[index].clone(),
+            handler: self.endpoints[index].metadata().handler_type(),
+        }
     }
 
     pub fn routes(&self) -> &[Route] {
@@ -127,6 +219,15 @@ impl EndpointRegistry {
             .position(|candidate| candidate == route)
             .and_then(|index| self.endpoints.get(index).cloned())
     }
+
+    pub fn find_endpoints(&self, route: &Route) -> Vec<Arc<dyn Endpoint>> {
+        self.routes
+            .iter()
+            .zip(self.endpoints.iter())
+            .filter(|(candidate, _)| *candidate == route)
+            .map(|(_, endpoint)| endpoint.clone())
+            .collect()
+    }
 }
 
 impl Default for EndpointRegistry {
@@ -134,6 +235,7 @@ impl Default for EndpointRegistry {
         Self {
             routes: Vec::new(),
             endpoints: Vec::new(),
+            origins: Vec::new(),
         }
     }
 }
@@ -143,6 +245,14 @@ impl Clone for EndpointRegistry {
         Self {
             routes: self.routes.clone(),
             endpoints: self.endpoints.clone(),
+            origins: self.origins.clone(),
         }
     }
 }
+
+fn short_type_name<T>() -> &'static str {
+    type_name::<T>()
+        .rsplit("::")
+        .next()
+        .unwrap_or(type_name::<T>())
+}
@@ -1,3 +1,4 @@
+use std::any::type_name;
 use std::sync::Arc;
 
 use crate::endpoint::endpoint::Endpoint;
@@ -6,6 +7,7 @@ use crate::endpoint::http_endpoint_handler::HttpEndpointHandler;
 use crate::endpoint::http_handler::HttpHandler;
 use crate::endpoint::metadata::EndpointMetadata;
 use crate::routing::route::Route;
+use crate::routing::versioning::ApiVersion;
 use crate::security::policy::Policy;
 
 pub struct EndpointRoute {
@@ -45,11 +47,19 @@ impl EndpointRoute {
     {
         RouteBuilder::new("DELETE", path, handler)
     }
+
+    pub fn patch<H>(path: &str, handler: H) -> RouteBuilder
+    where
+        H: HttpHandler + Send + Sync + 'static,
+    {
+        RouteBuilder::new("PATCH", path, handler)
+    }
 }
 
 pub struct RouteBuilder {
     method: &'static str,
     path: String,
+    host: Option<String>,
     handler: HttpEndpointHandler,
     metadata: EndpointMetadata,
 }
@@ -59,10 +69,11 @@ impl RouteBuilder {
     where
         H: HttpHandler + Send + Sync + 'static,
     {
-        let metadata = EndpointMetadata::new(method, path);
+        let metadata = EndpointMetadata::new(method, path).with_handler_type(type_name::<H>());
         Self {
             method,
             path: path.to_string(),
+            host: None,
             handler: HttpEndpointHandler::new(handler),
             metadata,
         }
@@ -81,9 +92,64 @@ impl RouteBuilder {
         self
     }
 
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.metadata = self.metadata.with_name(name);
+        self
+    }
+
+    pub fn with_tags<I, S>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.metadata = self.metadata.with_tags(tags);
+        self
+    }
+
+    pub fn with_host(mut self, host: &str) -> Self {
+        self.host = Some(host.to_string());
+        self
+    }
+
+    pub(crate) fn with_path_prefix(mut self, prefix: &str) -> Self {
+        self.path = join_paths(prefix, &self.path);
+        self.metadata = self.metadata.with_route_pattern(&self.path);
+        self
+    }
+
+    pub fn with_api_versions<I>(mut self, versions: I) -> Self
+    where
+        I: IntoIterator<Item = ApiVersion>,
+    {
+        self.metadata = self.metadata.with_api_versions(versions);
+        self
+    }
+
+    pub fn deprecate_api_versions<I>(mut self, versions: I) -> Self
+    where
+        I: IntoIterator<Item = ApiVersion>,
+    {
+        self.metadata = self.metadata.deprecate_api_versions(versions);
+        self
+    }
+
     pub fn build(self) -> EndpointRoute {
-        let route = Route::new(self.method, &self.path);
+        let mut route = Route::new(self.method, &self.path);
+        if let Some(host) = self.host.as_deref() {
+            route = route.with_host(host);
+        }
         let endpoint = Arc::new(HttpEndpoint::new(self.handler, self.metadata));
         EndpointRoute::new(route, endpoint)
     }
 }
+
+pub(crate) fn join_paths(prefix: &str, path: &str) -> String {
+    let prefix = prefix.trim_end_matches('/');
+    let path = path.trim_start_matches('/');
+    match (prefix.is_empty(), path.is_empty()) {
+        (true, true) => "/".to_string(),
+        (true, false) => format!("/{}", path),
+        (false, true) => prefix.to_string(),
+        (false, false) => format!("{}/{}", prefix, path),
+    }
+}
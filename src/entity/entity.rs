@@ -1,3 +1,5 @@
+pub use nimble_web_macros::Entity;
+
 pub trait Entity: Send + Sync + 'static {
     type Id: Send + Sync + Clone + 'static;
 
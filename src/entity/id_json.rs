@@ -0,0 +1,214 @@
+use serde::ser::{self, Impossible, Serialize, SerializeStruct, SerializeTupleStruct};
+use serde_json::{Error, Value};
+
+use crate::entity::entity::Entity;
+
+// `Entity::Id` carries no `Serialize` bound, so the id is picked out by address
+// while the entity serializes its fields. `None` means the id is not serialized.
+pub(crate) fn serialized_id<E: Entity + Serialize>(entity: &E) -> Option<Value> {
+    let mut probe = IdProbe {
+        id: entity.id() as *const E::Id as *const (),
+        found: None,
+    };
+    entity.serialize(&mut probe).ok()?;
+    probe.found
+}
+
+struct IdProbe {
+    id: *const (),
+    found: Option<Value>,
+}
+
+impl IdProbe {
+    fn field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        if self.found.is_none() && value as *const T as *const () == self.id {
+            self.found = Some(serde_json::to_value(value)?);
+        }
+        Ok(())
+    }
+}
+
+impl SerializeStruct for &mut IdProbe {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.field(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl SerializeTupleStruct for &mut IdProbe {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.field(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+fn unsupported() -> Error {
+    ser::Error::custom("entity is not serialized as a struct")
+}
+
+impl ser::Serializer for &mut IdProbe {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Impossible<(), Error>;
+    type SerializeTuple = Impossible<(), Error>;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = Impossible<(), Error>;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self, Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self, Error> {
+        Ok(self)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.field(value)
+    }
+
+    fn serialize_bool(self, _v: bool) -> Result<(), Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_i8(self, _v: i8) -> Result<(), Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_i16(self, _v: i16) -> Result<(), Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_i32(self, _v: i32) -> Result<(), Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_i64(self, _v: i64) -> Result<(), Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_u8(self, _v: u8) -> Result<(), Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_u16(self, _v: u16) -> Result<(), Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_u32(self, _v: u32) -> Result<(), Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_u64(self, _v: u64) -> Result<(), Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<(), Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<(), Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_char(self, _v: char) -> Result<(), Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_str(self, _v: &str) -> Result<(), Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<(), Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, _value: &T) -> Result<(), Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+    ) -> Result<(), Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<(), Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Err(unsupported())
+    }
+}
@@ -1,5 +1,7 @@
 pub mod entity;
 pub mod hooks;
+mod id_json;
 pub mod metadata;
 pub mod operation;
+pub mod patch;
 pub mod registry;
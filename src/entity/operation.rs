@@ -11,10 +11,13 @@ use crate::data::repository::Repository;
 use crate::endpoint::http_handler::HttpHandler;
 use crate::entity::entity::Entity;
 use crate::entity::hooks::EntityHooks;
+use crate::entity::id_json::serialized_id;
+use crate::entity::patch::{PatchDocument, PatchError};
 use crate::http::context::HttpContext;
 use crate::pipeline::pipeline::PipelineError;
 use crate::result::into_response::ResponseValue;
-use crate::result::Json;
+use crate::result::{HttpError, Json};
+use crate::validation::{Validate, ValidationError};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum EntityOperation {
@@ -22,10 +25,12 @@ pub enum EntityOperation {
     Get,
     Create,
     Update,
+    Patch,
     Delete,
 }
 
 impl EntityOperation {
+    // PATCH is opt-in: pass `EntityOperation::Patch` explicitly to expose it.
     pub fn all() -> &'static [Self] {
         &[
             Self::List,
@@ -37,6 +42,8 @@ impl EntityOperation {
     }
 }
 
+type EntityValidator<E> = fn(&E) -> Result<(), ValidationError>;
+
 pub struct OperationHandler<E, H>
 where
     E: Entity,
@@ -44,6 +51,7 @@ where
 {
     operation: EntityOperation,
     hooks: Arc<H>,
+    validator: Option<EntityValidator<E>>,
     _entity: PhantomData<E>,
 }
 
@@ -56,9 +64,20 @@ where
         Self {
             operation,
             hooks,
+            validator: None,
             _entity: PhantomData,
         }
     }
+
+    // Validates the entity the handler is about to store, so it does not depend on
+    // `use_validation`. PATCH bodies are partial, so the merged entity is validated there.
+    pub fn with_validation(mut self) -> Self
+    where
+        E: Validate,
+    {
+        self.validator = Some(E::validate);
+        self
+    }
 }
 
 #[async_trait]
@@ -119,6 +138,11 @@ where
                 let mut entity: E = context
                     .read_json()
                     .map_err(|e| PipelineError::message(e.message()))?;
+                if let Some(validate) = self.validator {
+                    if let Err(error) = validate(&entity) {
+                        return Ok(ResponseValue::new(error));
+                    }
+                }
 
                 self.hooks
                     .before_insert(context, &mut entity)
@@ -141,6 +165,75 @@ where
                 let mut entity: E = context
                     .read_json()
                     .map_err(|e| PipelineError::message(e.message()))?;
+                if let Some(validate) = self.validator {
+                    if let Err(error) = validate(&entity) {
+                        return Ok(ResponseValue::new(error));
+                    }
+                }
+
+                self.hooks
+                    .before_update(context, &mut entity)
+                    .await
+                    .map_err(|e| PipelineError::message(&e.to_string()))?;
+
+                let result = repository
+                    .update(entity)
+                    .await
+                    .map_err(|e| PipelineError::message(&format!("{:?}", e)))?;
+
+                self.hooks
+                    .after_update(context, &result)
+                    .await
+                    .map_err(|e| PipelineError::message(&e.to_string()))?;
+
+                Ok(ResponseValue::new(Json(result)))
+            }
+            EntityOperation::Patch => {
+                let id_str = context
+                    .route()
+                    .and_then(|r| r.params().get("id"))
+                    .ok_or_else(|| PipelineError::message("id parameter missing"))?;
+
+                let id = E::Id::from_str(id_str)
+                    .map_err(|_| PipelineError::message("invalid id format"))?;
+
+                let current = repository
+                    .get(&id)
+                    .await
+                    .map_err(|e| PipelineError::message(&format!("{:?}", e)))?
+                    .ok_or_else(|| PipelineError::message("not found"))?;
+
+                let body: serde_json::Value = match context.read_json() {
+                    Ok(body) => body,
+                    Err(e) => return Ok(ResponseValue::new(HttpError::new(400, e.message()))),
+                };
+                let content_type = context.request().headers().get("content-type");
+                let patch = match PatchDocument::from_body(content_type, body) {
+                    Ok(patch) => patch,
+                    Err(e) => return Ok(ResponseValue::new(patch_error(e))),
+                };
+
+                let mut document = serde_json::to_value(&current)
+                    .map_err(|e| PipelineError::message(&e.to_string()))?;
+                if let Err(e) = patch.apply(&mut document) {
+                    return Ok(ResponseValue::new(patch_error(e)));
+                }
+                let mut entity: E = match serde_json::from_value(document) {
+                    Ok(entity) => entity,
+                    Err(e) => {
+                        let message = format!("patched {} is invalid: {}", E::name(), e);
+                        return Ok(ResponseValue::new(HttpError::new(422, &message)));
+                    }
+                };
+                if serialized_id(&entity) != serialized_id(&current) {
+                    let error = HttpError::new(422, "patch cannot change the entity id");
+                    return Ok(ResponseValue::new(error));
+                }
+                if let Some(validate) = self.validator {
+                    if let Err(error) = validate(&entity) {
+                        return Ok(ResponseValue::new(error));
+                    }
+                }
 
                 self.hooks
                     .before_update(context, &mut entity)
@@ -191,3 +284,12 @@ where
         }
     }
 }
+
+// A failed `test` operation means the entity changed since the client read it.
+fn patch_error(error: PatchError) -> HttpError {
+    let status = match error {
+        PatchError::TestFailed(_) => 409,
+        _ => 400,
+    };
+    HttpError::new(status, &error.to_string())
+}
@@ -0,0 +1,214 @@
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+pub const MERGE_PATCH_CONTENT_TYPE: &str = "application/merge-patch+json";
+pub const JSON_PATCH_CONTENT_TYPE: &str = "application/json-patch+json";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatchError {
+    InvalidDocument(String),
+    InvalidPointer(String),
+    PathNotFound(String),
+    TestFailed(String),
+}
+
+impl Display for PatchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            PatchError::InvalidDocument(message) => {
+                write!(f, "invalid patch document: {}", message)
+            }
+            PatchError::InvalidPointer(path) => write!(f, "invalid JSON pointer `{}`", path),
+            PatchError::PathNotFound(path) => write!(f, "path `{}` does not exist", path),
+            PatchError::TestFailed(path) => write!(f, "test operation failed at `{}`", path),
+        }
+    }
+}
+
+impl Error for PatchError {}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOperation {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+    Move { from: String, path: String },
+    Copy { from: String, path: String },
+    Test { path: String, value: Value },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatchDocument {
+    Merge(Value),
+    Json(Vec<PatchOperation>),
+}
+
+impl PatchDocument {
+    pub fn from_body(content_type: Option<&str>, body: Value) -> Result<Self, PatchError> {
+        let media_type = content_type
+            .and_then(|value| value.split(';').next())
+            .map(|value| value.trim().to_ascii_lowercase());
+        let is_json_patch = match media_type.as_deref() {
+            Some(JSON_PATCH_CONTENT_TYPE) => true,
+            Some(MERGE_PATCH_CONTENT_TYPE) => false,
+            _ => body.is_array(),
+        };
+
+        if is_json_patch {
+            serde_json::from_value(body)
+                .map(PatchDocument::Json)
+                .map_err(|err| PatchError::InvalidDocument(err.to_string()))
+        } else {
+            Ok(PatchDocument::Merge(body))
+        }
+    }
+
+    pub fn apply(&self, target: &mut Value) -> Result<(), PatchError> {
+        match self {
+            PatchDocument::Merge(patch) => {
+                apply_merge_patch(target, patch);
+                Ok(())
+            }
+            PatchDocument::Json(operations) => apply_json_patch(target, operations),
+        }
+    }
+}
+
+pub fn apply_merge_patch(target: &mut Value, patch: &Value) {
+    let Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = Value::Object(Map::new());
+    }
+    if let Value::Object(target) = target {
+        for (key, value) in patch {
+            if value.is_null() {
+                target.remove(key);
+            } else {
+                apply_merge_patch(target.entry(key.clone()).or_insert(Value::Null), value);
+            }
+        }
+    }
+}
+
+pub fn apply_json_patch(
+    target: &mut Value,
+    operations: &[PatchOperation],
+) -> Result<(), PatchError> {
+    let mut document = target.clone();
+    for operation in operations {
+        match operation {
+            PatchOperation::Add { path, value } => add(&mut document, path, value.clone())?,
+            PatchOperation::Remove { path } => {
+                remove(&mut document, path)?;
+            }
+            PatchOperation::Replace { path, value } => {
+                let slot = pointer_mut(&mut document, path)?;
+                *slot = value.clone();
+            }
+            PatchOperation::Move { from, path } => {
+                if path.starts_with(&format!("{}/", from)) {
+                    return Err(PatchError::InvalidPointer(path.clone()));
+                }
+                let value = remove(&mut document, from)?;
+                add(&mut document, path, value)?;
+            }
+            PatchOperation::Copy { from, path } => {
+                let value = pointer(&document, from)?.clone();
+                add(&mut document, path, value)?;
+            }
+            PatchOperation::Test { path, value } => {
+                if pointer(&document, path)? != value {
+                    return Err(PatchError::TestFailed(path.clone()));
+                }
+            }
+        }
+    }
+    *target = document;
+    Ok(())
+}
+
+fn pointer<'a>(document: &'a Value, path: &str) -> Result<&'a Value, PatchError> {
+    validate_pointer(path)?;
+    document
+        .pointer(path)
+        .ok_or_else(|| PatchError::PathNotFound(path.to_string()))
+}
+
+fn pointer_mut<'a>(document: &'a mut Value, path: &str) -> Result<&'a mut Value, PatchError> {
+    validate_pointer(path)?;
+    document
+        .pointer_mut(path)
+        .ok_or_else(|| PatchError::PathNotFound(path.to_string()))
+}
+
+fn add(document: &mut Value, path: &str, value: Value) -> Result<(), PatchError> {
+    let Some((parent, token)) = split_pointer(path)? else {
+        *document = value;
+        return Ok(());
+    };
+    match pointer_mut(document, parent)? {
+        Value::Object(map) => {
+            map.insert(token, value);
+            Ok(())
+        }
+        Value::Array(items) => {
+            let index = if token == "-" {
+                items.len()
+            } else {
+                array_index(&token, path)?
+            };
+            if index > items.len() {
+                return Err(PatchError::PathNotFound(path.to_string()));
+            }
+            items.insert(index, value);
+            Ok(())
+        }
+        _ => Err(PatchError::PathNotFound(path.to_string())),
+    }
+}
+
+fn remove(document: &mut Value, path: &str) -> Result<Value, PatchError> {
+    let Some((parent, token)) = split_pointer(path)? else {
+        return Ok(std::mem::take(document));
+    };
+    let removed = match pointer_mut(document, parent)? {
+        Value::Object(map) => map.remove(&token),
+        Value::Array(items) => {
+            let index = array_index(&token, path)?;
+            (index < items.len()).then(|| items.remove(index))
+        }
+        _ => None,
+    };
+    removed.ok_or_else(|| PatchError::PathNotFound(path.to_string()))
+}
+
+fn split_pointer(path: &str) -> Result<Option<(&str, String)>, PatchError> {
+    validate_pointer(path)?;
+    Ok(path.rfind('/').map(|index| {
+        let token = path[index + 1..].replace("~1", "/").replace("~0", "~");
+        (&path[..index], token)
+    }))
+}
+
+fn validate_pointer(path: &str) -> Result<(), PatchError> {
+    if path.is_empty() || path.starts_with('/') {
+        Ok(())
+    } else {
+        Err(PatchError::InvalidPointer(path.to_string()))
+    }
+}
+
+fn array_index(token: &str, path: &str) -> Result<usize, PatchError> {
+    if token.len() > 1 && token.starts_with('0') {
+        return Err(PatchError::InvalidPointer(path.to_string()));
+    }
+    token
+        .parse::<usize>()
+        .map_err(|_| PatchError::InvalidPointer(path.to_string()))
+}
@@ -5,7 +5,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::config::Configuration;
-use crate::di::ServiceProvider;
+use crate::di::{ServiceProvider, ServiceScope};
 use crate::endpoint::endpoint::Endpoint;
 use crate::http::request::HttpRequest;
 use crate::http::response::HttpResponse;
@@ -17,7 +17,7 @@ use crate::validation::ValidationError;
 pub struct HttpContext {
     request: HttpRequest,
     response: HttpResponse,
-    services: ServiceProvider,
+    scope: ServiceScope,
     config: Arc<Configuration>,
     items: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
     route: Option<RouteData>,
@@ -27,12 +27,16 @@ pub struct HttpContext {
 
 impl HttpContext {
     pub fn new(request: HttpRequest, services: ServiceProvider, config: Configuration) -> Self {
+        Self::with_scope(request, ServiceScope { provider: services }, config)
+    }
+
+    pub fn with_scope(request: HttpRequest, scope: ServiceScope, config: Configuration) -> Self {
         let mut response = HttpResponse::default();
         response.set_status(404);
         Self {
             request,
             response,
-            services,
+            scope,
             config: Arc::new(config),
             items: HashMap::new(),
             route: None,
@@ -69,7 +73,11 @@ impl HttpContext {
     }
 
     pub fn services(&self) -> &ServiceProvider {
-        &self.services
+        self.scope.provider()
+    }
+
+    pub fn scope(&self) -> &ServiceScope {
+        &self.scope
     }
 
     pub fn config(&self) -> &Configuration {
@@ -177,6 +185,19 @@ impl HttpContext {
         })
     }
 
+    pub fn keyed_service<T>(&self, key: &str) -> Result<Arc<T>, PipelineError>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.services().resolve_keyed::<T>(key).ok_or_else(|| {
+            PipelineError::message(&format!(
+                "Service `{}` with key `{}` is not registered",
+                Self::short_type_name::<T>(),
+                key
+            ))
+        })
+    }
+
     fn short_type_name<T>() -> &'static str {
         type_name::<T>()
             .rsplit("::")
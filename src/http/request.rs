@@ -30,6 +30,10 @@ impl HttpRequest {
         &self.path
     }
 
+    pub fn host(&self) -> Option<&str> {
+        self.headers.get("host")
+    }
+
     pub fn set_query(&mut self, query: Option<String>) {
         self.query = query;
     }
@@ -29,7 +29,8 @@ pub mod testbot;
 pub mod testkit;
 pub mod validation;
 pub mod websocket;
+pub use async_trait;
 pub use inventory;
 pub use prelude::*;
 mod runtime;
-pub use nimble_web_macros::{delete, get, post, put};
+pub use nimble_web_macros::{controller, delete, get, injectable, patch, post, put, route};
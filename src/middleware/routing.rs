@@ -1,16 +1,29 @@
 use async_trait::async_trait;
 use std::sync::Arc;
 
+use crate::endpoint::endpoint::Endpoint;
 use crate::endpoint::registry::EndpointRegistry;
 use crate::http::context::HttpContext;
+use crate::http::request::HttpRequest;
+use crate::http::response_body::ResponseBody;
 use crate::pipeline::middleware::Middleware;
 use crate::pipeline::next::Next;
 use crate::pipeline::pipeline::PipelineError;
+use crate::routing::route_data::RouteData;
 use crate::routing::router::Router;
+use crate::routing::versioning::{ApiVersion, ApiVersioning};
 
 pub struct RoutingMiddleware {
     router: Arc<dyn Router + Send + Sync>,
     endpoint_registry: Option<Arc<EndpointRegistry>>,
+    versioning: Option<Arc<ApiVersioning>>,
+}
+
+struct VersionSelection {
+    version: ApiVersion,
+    supported: Vec<ApiVersion>,
+    deprecated: Vec<ApiVersion>,
+    sunset: Option<String>,
 }
 
 impl RoutingMiddleware {
@@ -21,6 +34,7 @@ impl RoutingMiddleware {
         Self {
             router: Arc::new(router),
             endpoint_registry: None,
+            versioning: None,
         }
     }
 
@@ -31,6 +45,110 @@ impl RoutingMiddleware {
         Self {
             router: Arc::new(router),
             endpoint_registry: Some(registry),
+            versioning: None,
+        }
+    }
+
+    pub fn with_versioning(mut self, versioning: ApiVersioning) -> Self {
+        self.versioning = Some(Arc::new(versioning));
+        self
+    }
+
+    fn select_endpoint(
+        &self,
+        request: &HttpRequest,
+        route_data: &RouteData,
+        candidates: &[Arc<dyn Endpoint>],
+    ) -> Result<(Arc<dyn Endpoint>, Option<VersionSelection>), String> {
+        let first = candidates[0].clone();
+        let Some(versioning) = self.versioning.as_ref() else {
+            return Ok((first, None));
+        };
+        if candidates
+            .iter()
+            .all(|endpoint| endpoint.metadata().is_version_neutral())
+        {
+            return Ok((first, None));
+        }
+
+        let mut supported: Vec<ApiVersion> = candidates
+            .iter()
+            .flat_map(|endpoint| endpoint.metadata().api_versions().iter().copied())
+            .collect();
+        supported.sort();
+        supported.dedup();
+
+        let mut deprecated: Vec<ApiVersion> = candidates
+            .iter()
+            .flat_map(|endpoint| {
+                endpoint
+                    .metadata()
+                    .deprecated_api_versions()
+                    .iter()
+                    .copied()
+            })
+            .chain(
+                supported
+                    .iter()
+                    .copied()
+                    .filter(|version| versioning.is_deprecated(version)),
+            )
+            .collect();
+        deprecated.sort();
+        deprecated.dedup();
+
+        let version = versioning
+            .resolve(request, route_data)?
+            .ok_or_else(|| "API version is required".to_string())?;
+
+        let endpoint = candidates
+            .iter()
+            .find(|endpoint| endpoint.metadata().supports_api_version(&version))
+            .or_else(|| {
+                candidates
+                    .iter()
+                    .find(|endpoint| endpoint.metadata().is_version_neutral())
+            })
+            .cloned()
+            .ok_or_else(|| {
+                let versions: Vec<String> = supported.iter().map(ToString::to_string).collect();
+                format!(
+                    "API version {} is not supported. Supported versions: {}",
+                    version,
+                    versions.join(", ")
+                )
+            })?;
+
+        Ok((
+            endpoint,
+            Some(VersionSelection {
+                version,
+                supported,
+                deprecated,
+                sunset: versioning.sunset_date(&version).map(str::to_string),
+            }),
+        ))
+    }
+
+    fn apply_version_headers(context: &mut HttpContext, selection: &VersionSelection) {
+        let join = |versions: &[ApiVersion]| {
+            versions
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+
+        let headers = context.response_mut().headers_mut();
+        headers.insert("api-supported-versions", &join(&selection.supported));
+        if !selection.deprecated.is_empty() {
+            headers.insert("api-deprecated-versions", &join(&selection.deprecated));
+        }
+        if selection.deprecated.contains(&selection.version) {
+            headers.insert("deprecation", "true");
+        }
+        if let Some(sunset) = selection.sunset.as_deref() {
+            headers.insert("sunset", sunset);
         }
     }
 }
@@ -38,26 +156,49 @@ impl RoutingMiddleware {
 #[async_trait]
 impl Middleware for RoutingMiddleware {
     async fn handle(&self, context: &mut HttpContext, next: Next<'_>) -> Result<(), PipelineError> {
+        let mut version_selection = None;
+
         if let Some(route_data) = self.router.match_request(context.request()) {
             if let Some(registry) = self.endpoint_registry.as_ref() {
-                if let Some(endpoint) = registry.find_endpoint(route_data.route()) {
-                    context.set_endpoint(endpoint);
-                } else {
+                let candidates = registry.find_endpoints(route_data.route());
+                if candidates.is_empty() {
                     log::debug!(
                         "❌ No endpoint found for route: {}",
                         route_data.route().path()
                     );
 
-                    self.endpoint_registry.as_ref().map(|registry| {
-                        log::debug!(
-                            "Available endpoints: {:?}",
-                            registry
-                                .endpoints()
-                                .iter()
-                                .map(|ep| ep.metadata().route_pattern())
-                                .collect::<Vec<&str>>()
-                        )
-                    });
+                    log::debug!(
+                        "Available endpoints: {:?}",
+                        registry
+                            .endpoints()
+                            .iter()
+                            .map(|ep| ep.metadata().route_pattern())
+                            .collect::<Vec<&str>>()
+                    );
+                } else {
+                    match self.select_endpoint(context.request(), &route_data, &candidates) {
+                        Ok((endpoint, selection)) => {
+                            context.set_endpoint(endpoint);
+                            if let Some(selection) = selection {
+                                context.insert(selection.version);
+                                version_selection = Some(selection);
+                            }
+                        }
+                        Err(message) => {
+                            log::debug!(
+                                "❌ API version rejected for {}: {}",
+                                route_data.route(),
+                                message
+                            );
+                            let response = context.response_mut();
+                            response.set_status(400);
+                            response.set_body(ResponseBody::Text(message));
+                            response
+                                .headers_mut()
+                                .insert("content-type", "text/plain; charset=utf-8");
+                            return Ok(());
+                        }
+                    }
                 }
             } else {
                 log::debug!("❌ No endpoint registry configured in RoutingMiddleware");
@@ -65,6 +206,10 @@ impl Middleware for RoutingMiddleware {
             context.set_route(route_data);
         }
 
-        next.run(context).await
+        let result = next.run(context).await;
+        if let Some(selection) = version_selection.as_ref() {
+            Self::apply_version_headers(context, selection);
+        }
+        result
     }
 }
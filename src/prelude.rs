@@ -19,6 +19,8 @@ pub use crate::controller::invoker::*;
 pub use crate::controller::route::*;
 pub use crate::data::memory_repository::*;
 #[cfg(feature = "mongodb")]
+pub use crate::data::mongo::index::*;
+#[cfg(feature = "mongodb")]
 pub use crate::data::mongo::*;
 pub use crate::data::paging::*;
 #[cfg(feature = "postgres")]
@@ -31,7 +33,9 @@ pub use crate::data::query_builder::*;
 pub use crate::data::repository::*;
 pub use crate::data::schema::*;
 pub use crate::di::*;
+pub use crate::endpoint::conflict::*;
 pub use crate::endpoint::endpoint::*;
+pub use crate::endpoint::group::*;
 pub use crate::endpoint::http_endpoint::*;
 pub use crate::endpoint::http_endpoint_handler::*;
 pub use crate::endpoint::http_handler::*;
@@ -44,6 +48,7 @@ pub use crate::entity::entity::*;
 pub use crate::entity::hooks::*;
 pub use crate::entity::metadata::*;
 pub use crate::entity::operation::*;
+pub use crate::entity::patch::*;
 pub use crate::entity::registry::*;
 pub use crate::http::context::*;
 pub use crate::http::headers::*;
@@ -75,9 +80,12 @@ pub use crate::redis::redis_module::*;
 pub use crate::redis::*;
 pub use crate::result::*;
 pub use crate::routing::default_router::*;
+pub use crate::routing::link_generator::*;
 pub use crate::routing::route::*;
 pub use crate::routing::route_data::*;
+pub use crate::routing::route_table::*;
 pub use crate::routing::router::*;
+pub use crate::routing::versioning::*;
 pub use crate::security::auth::*;
 pub use crate::security::policy::*;
 pub use crate::security::token::*;
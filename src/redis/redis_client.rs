@@ -3,7 +3,10 @@ use deadpool::managed::PoolError;
 #[cfg(feature = "redis")]
 use deadpool_redis::redis::RedisError;
 #[cfg(feature = "redis")]
-use deadpool_redis::{Config, Pool, Runtime};
+use deadpool_redis::{Config, CreatePoolError, Pool, Runtime};
+
+#[cfg(feature = "redis")]
+use crate::di::initializer::InitializeError;
 
 #[cfg(feature = "redis")]
 #[derive(Clone)]
@@ -16,19 +19,29 @@ pub struct RedisClient {
 #[cfg(feature = "redis")]
 impl RedisClient {
     pub fn new(url: &str, pool_size: usize) -> Self {
+        Self::try_new(url, pool_size).expect("failed to create redis pool")
+    }
+
+    pub fn try_new(url: &str, pool_size: usize) -> Result<Self, CreatePoolError> {
         let mut config = Config::from_url(url);
         let mut pool_config = config.pool.unwrap_or_default();
         pool_config.max_size = pool_size;
         config.pool = Some(pool_config);
-        let pool = config
-            .create_pool(Some(Runtime::Tokio1))
-            .expect("failed to create redis pool");
+        let pool = config.create_pool(Some(Runtime::Tokio1))?;
 
-        Self {
+        Ok(Self {
             pool,
             pool_size,
             url: url.to_string(),
-        }
+        })
+    }
+
+    // Creates the pool and checks out one connection, so an unreachable server is
+    // reported when the client is built rather than on the first command.
+    pub async fn connect(url: &str, pool_size: usize) -> Result<Self, InitializeError> {
+        let client = Self::try_new(url, pool_size)?;
+        client.get().await?;
+        Ok(client)
     }
 
     pub async fn get(&self) -> Result<deadpool_redis::Connection, PoolError<RedisError>> {
@@ -11,12 +11,22 @@ pub struct RedisModule;
 impl RedisModule {
     pub fn register(container: &mut ServiceContainer, config: &Configuration) {
         let redis_config = config.redis_config();
-        let client = RedisClient::new(&redis_config.url, redis_config.pool_size);
         let redis_config_clone = redis_config.clone();
-        let client_clone = client.clone();
 
         container.register_singleton::<RedisConfig, _>(move |_| redis_config_clone.clone());
-        container.register_singleton::<RedisClient, _>(move |_| client_clone.clone());
+        // A configured server is connected during startup initialization, so a bad URL or an
+        // unreachable server fails `Application::initialize` instead of panicking on first use.
+        // Without `Redis:Url` the default client connects lazily, so apps that never use Redis
+        // still start.
+        if config.get("redis.url").is_some() {
+            container.register_singleton_async::<RedisClient, _, _, _>(move |_| {
+                let redis_config = redis_config.clone();
+                async move { RedisClient::connect(&redis_config.url, redis_config.pool_size).await }
+            });
+        } else {
+            let client = RedisClient::new(&redis_config.url, redis_config.pool_size);
+            container.register_singleton::<RedisClient, _>(move |_| client.clone());
+        }
 
         container.register_singleton::<RedisCache, _>(move |provider| {
             let client = provider
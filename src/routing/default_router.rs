@@ -70,7 +70,12 @@ impl DefaultRouter {
             );
 
             for r in routes.iter() {
-                log::info!("    ⇢  Path: {:<8} {}", r.method(), r.path());
+                log::info!(
+                    "    ⇢  Path: {:<8} {}{}",
+                    r.method(),
+                    r.host().unwrap_or_default(),
+                    r.path()
+                );
             }
 
             log::info!("");
@@ -102,24 +107,32 @@ impl Router for DefaultRouter {
             (static_count, segments.len())
         }
 
-        let mut best_match: Option<(RouteData, usize, usize)> = None;
+        fn host_specificity(route: &Route) -> usize {
+            route.host().map_or(0, |host| {
+                1 + host
+                    .split('.')
+                    .filter(|label| !(label.starts_with('{') && label.ends_with('}')))
+                    .count()
+            })
+        }
+
+        let mut best_match: Option<(RouteData, (usize, usize, usize))> = None;
 
         for route in &self.routes {
-            if let Some(data) = route.match_path(request.method(), request.path()) {
+            if let Some(data) = route.match_request(request) {
                 let (static_count, segment_count) = route_specificity(route.path());
+                let rank = (host_specificity(route), static_count, segment_count);
                 match &best_match {
-                    None => best_match = Some((data, static_count, segment_count)),
-                    Some((_, best_static, best_segments)) => {
-                        if static_count > *best_static
-                            || (static_count == *best_static && segment_count > *best_segments)
-                        {
-                            best_match = Some((data, static_count, segment_count));
+                    None => best_match = Some((data, rank)),
+                    Some((_, best_rank)) => {
+                        if rank > *best_rank {
+                            best_match = Some((data, rank));
                         }
                     }
                 }
             }
         }
 
-        best_match.map(|(data, _, _)| data)
+        best_match.map(|(data, _)| data)
     }
 }
@@ -0,0 +1,219 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use crate::endpoint::registry::EndpointRegistry;
+use crate::http::request::HttpRequest;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkError {
+    UnknownRoute(String),
+    MissingParameter { route: String, parameter: String },
+    UnexpectedParameter { route: String, parameter: String },
+    MissingHost,
+}
+
+impl Display for LinkError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            LinkError::UnknownRoute(name) => write!(f, "no route named `{}`", name),
+            LinkError::MissingParameter { route, parameter } => {
+                write!(f, "route `{}` requires parameter `{}`", route, parameter)
+            }
+            LinkError::UnexpectedParameter { route, parameter } => {
+                write!(f, "route `{}` has no parameter `{}`", route, parameter)
+            }
+            LinkError::MissingHost => write!(f, "request has no host to build an absolute url"),
+        }
+    }
+}
+
+impl Error for LinkError {}
+
+#[derive(Clone, Debug, Default)]
+pub struct LinkGenerator {
+    routes: HashMap<String, String>,
+}
+
+impl LinkGenerator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_registry(registry: &EndpointRegistry) -> Self {
+        let mut generator = Self::new();
+        for endpoint in registry.endpoints() {
+            let metadata = endpoint.metadata();
+            if let Some(name) = metadata.name() {
+                generator.add(name, metadata.route_pattern());
+            }
+        }
+        generator
+    }
+
+    pub fn add(&mut self, name: &str, pattern: &str) -> &mut Self {
+        if let Some(existing) = self.routes.get(name) {
+            log::warn!(
+                "Route name `{}` already maps to {}, ignoring {}",
+                name,
+                existing,
+                pattern
+            );
+            return self;
+        }
+        self.routes.insert(name.to_string(), pattern.to_string());
+        self
+    }
+
+    pub fn route_pattern(&self, name: &str) -> Option<&str> {
+        self.routes.get(name).map(String::as_str)
+    }
+
+    pub fn path<K, V>(&self, name: &str, params: &[(K, V)]) -> Result<String, LinkError>
+    where
+        K: AsRef<str>,
+        V: ToString,
+    {
+        self.path_with_query::<K, V, &str, &str>(name, params, &[])
+    }
+
+    pub fn path_with_query<K, V, QK, QV>(
+        &self,
+        name: &str,
+        params: &[(K, V)],
+        query: &[(QK, QV)],
+    ) -> Result<String, LinkError>
+    where
+        K: AsRef<str>,
+        V: ToString,
+        QK: AsRef<str>,
+        QV: ToString,
+    {
+        let pattern = self
+            .routes
+            .get(name)
+            .ok_or_else(|| LinkError::UnknownRoute(name.to_string()))?;
+
+        let values: HashMap<&str, String> = params
+            .iter()
+            .map(|(key, value)| (key.as_ref(), value.to_string()))
+            .collect();
+        let mut used = HashSet::new();
+
+        let mut path = String::new();
+        for segment in pattern.trim_matches('/').split('/') {
+            if segment.is_empty() {
+                continue;
+            }
+            path.push('/');
+            match param_name(segment) {
+                Some(param) => {
+                    let value = values
+                        .get(param)
+                        .ok_or_else(|| LinkError::MissingParameter {
+                            route: name.to_string(),
+                            parameter: param.to_string(),
+                        })?;
+                    used.insert(param);
+                    path.push_str(&encode(value, false));
+                }
+                None => path.push_str(segment),
+            }
+        }
+
+        if let Some((key, _)) = params.iter().find(|(key, _)| !used.contains(key.as_ref())) {
+            return Err(LinkError::UnexpectedParameter {
+                route: name.to_string(),
+                parameter: key.as_ref().to_string(),
+            });
+        }
+
+        if path.is_empty() {
+            path.push('/');
+        }
+
+        if !query.is_empty() {
+            let pairs: Vec<String> = query
+                .iter()
+                .map(|(key, value)| {
+                    format!(
+                        "{}={}",
+                        encode(key.as_ref(), true),
+                        encode(&value.to_string(), true)
+                    )
+                })
+                .collect();
+            path.push('?');
+            path.push_str(&pairs.join("&"));
+        }
+
+        Ok(path)
+    }
+
+    pub fn url<K, V>(
+        &self,
+        request: &HttpRequest,
+        name: &str,
+        params: &[(K, V)],
+    ) -> Result<String, LinkError>
+    where
+        K: AsRef<str>,
+        V: ToString,
+    {
+        self.url_with_query::<K, V, &str, &str>(request, name, params, &[])
+    }
+
+    pub fn url_with_query<K, V, QK, QV>(
+        &self,
+        request: &HttpRequest,
+        name: &str,
+        params: &[(K, V)],
+        query: &[(QK, QV)],
+    ) -> Result<String, LinkError>
+    where
+        K: AsRef<str>,
+        V: ToString,
+        QK: AsRef<str>,
+        QV: ToString,
+    {
+        let path = self.path_with_query(name, params, query)?;
+        let headers = request.headers();
+        let host = headers
+            .get("x-forwarded-host")
+            .or_else(|| headers.get("host"))
+            .map(|value| value.split(',').next().unwrap_or(value).trim())
+            .filter(|value| !value.is_empty())
+            .ok_or(LinkError::MissingHost)?;
+        let scheme = headers
+            .get("x-forwarded-proto")
+            .map(|value| value.split(',').next().unwrap_or(value).trim())
+            .filter(|value| !value.is_empty())
+            .unwrap_or("http");
+
+        Ok(format!("{}://{}{}", scheme, host, path))
+    }
+}
+
+fn param_name(segment: &str) -> Option<&str> {
+    if segment.len() >= 3 && segment.starts_with('{') && segment.ends_with('}') {
+        Some(&segment[1..segment.len() - 1])
+    } else {
+        None
+    }
+}
+
+fn encode(value: &str, query: bool) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                out.push(byte as char)
+            }
+            b'!' | b'$' | b'\'' | b'(' | b')' | b'*' | b',' | b';' | b':' | b'@' if !query => {
+                out.push(byte as char)
+            }
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
@@ -1,4 +1,7 @@
 pub mod default_router;
+pub mod link_generator;
 pub mod route;
 pub mod route_data;
+pub mod route_table;
 pub mod router;
+pub mod versioning;
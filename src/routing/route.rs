@@ -1,12 +1,14 @@
 use std::collections::HashMap;
 use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
 
+use crate::http::request::HttpRequest;
 use crate::routing::route_data::RouteData;
 
 #[derive(Clone, PartialEq, Eq)]
 pub struct Route {
     method: String,
     path: String,
+    host: Option<String>,
 }
 
 impl Route {
@@ -14,9 +16,15 @@ impl Route {
         Self {
             method: method.to_string(),
             path: path.to_string(),
+            host: None,
         }
     }
 
+    pub fn with_host(mut self, host: &str) -> Self {
+        self.host = Some(normalize_host(host).to_string());
+        self
+    }
+
     pub fn method(&self) -> &str {
         &self.method
     }
@@ -25,6 +33,52 @@ impl Route {
         &self.path
     }
 
+    pub fn host(&self) -> Option<&str> {
+        self.host.as_deref()
+    }
+
+    pub fn match_request(&self, request: &HttpRequest) -> Option<RouteData> {
+        let host_params = self.match_host(request.host())?;
+        let route_data = self.match_path(request.method(), request.path())?;
+        if host_params.is_empty() {
+            return Some(route_data);
+        }
+
+        let mut params = host_params;
+        params.extend(route_data.params().clone());
+        Some(RouteData::new(self.clone(), params))
+    }
+
+    pub fn match_host(&self, host: Option<&str>) -> Option<HashMap<String, String>> {
+        let Some(pattern) = self.host.as_deref() else {
+            return Some(HashMap::new());
+        };
+        let host = normalize_host(strip_port(host?));
+
+        let pattern_labels: Vec<&str> = pattern.split('.').collect();
+        let host_labels: Vec<&str> = host.split('.').collect();
+        if pattern_labels.len() != host_labels.len() {
+            return None;
+        }
+
+        let mut params = HashMap::new();
+        for (pattern_label, host_label) in pattern_labels.into_iter().zip(host_labels) {
+            if let Some(param_name) = self.param_name(pattern_label) {
+                if host_label.is_empty() {
+                    return None;
+                }
+                params.insert(param_name.to_string(), host_label.to_string());
+                continue;
+            }
+
+            if !pattern_label.eq_ignore_ascii_case(host_label) {
+                return None;
+            }
+        }
+
+        Some(params)
+    }
+
     pub fn match_path(&self, method: &str, path: &str) -> Option<RouteData> {
         if self.method != method {
             return None;
@@ -80,12 +134,27 @@ impl Debug for Route {
         f.debug_struct("Route")
             .field("method", &self.method)
             .field("path", &self.path)
+            .field("host", &self.host)
             .finish()
     }
 }
 
 impl Display for Route {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
-        write!(f, "{} {}", self.method, self.path)
+        match self.host.as_deref() {
+            Some(host) => write!(f, "{} {}{}", self.method, host, self.path),
+            None => write!(f, "{} {}", self.method, self.path),
+        }
+    }
+}
+
+fn normalize_host(host: &str) -> &str {
+    host.trim().trim_end_matches('.')
+}
+
+fn strip_port(host: &str) -> &str {
+    if let Some(rest) = host.strip_prefix('[') {
+        return rest.split(']').next().unwrap_or(rest);
     }
+    host.split(':').next().unwrap_or(host)
 }
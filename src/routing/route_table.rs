@@ -0,0 +1,94 @@
+use async_trait::async_trait;
+use serde::Serialize;
+
+use crate::endpoint::http_handler::HttpHandler;
+use crate::endpoint::registry::EndpointRegistry;
+use crate::http::context::HttpContext;
+use crate::pipeline::pipeline::PipelineError;
+use crate::result::into_response::ResponseValue;
+
+pub const DEFAULT_ROUTE_DIAGNOSTICS_PATH: &str = "/_nimble/routes";
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct RouteDescriptor {
+    pub method: String,
+    pub pattern: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    pub name: Option<String>,
+    pub tags: Vec<String>,
+    pub policy: Option<String>,
+    pub validators: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub api_versions: Vec<String>,
+    pub origin: String,
+    pub handler: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct RouteTable {
+    routes: Vec<RouteDescriptor>,
+}
+
+impl RouteTable {
+    pub fn from_registry(registry: &EndpointRegistry) -> Self {
+        let routes = registry
+            .routes()
+            .iter()
+            .zip(registry.endpoints().iter())
+            .zip(registry.origins().iter())
+            .map(|((route, endpoint), origin)| {
+                let metadata = endpoint.metadata();
+                RouteDescriptor {
+                    method: route.method().to_string(),
+                    pattern: route.path().to_string(),
+                    host: route.host().map(str::to_string),
+                    name: metadata.name().map(str::to_string),
+                    tags: metadata.tags().to_vec(),
+                    policy: metadata.policy().map(ToString::to_string),
+                    validators: metadata.validators().len(),
+                    api_versions: metadata
+                        .api_versions()
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect(),
+                    origin: origin.to_string(),
+                    handler: metadata.handler_type().map(str::to_string),
+                }
+            })
+            .collect();
+        Self { routes }
+    }
+
+    pub fn routes(&self) -> &[RouteDescriptor] {
+        &self.routes
+    }
+
+    pub fn find(&self, method: &str, pattern: &str) -> Option<&RouteDescriptor> {
+        self.routes
+            .iter()
+            .find(|route| route.method.eq_ignore_ascii_case(method) && route.pattern == pattern)
+    }
+
+    pub fn len(&self) -> usize {
+        self.routes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_else(|_| "{}".to_string())
+    }
+}
+
+pub struct RouteTableHandler;
+
+#[async_trait]
+impl HttpHandler for RouteTableHandler {
+    async fn invoke(&self, context: &mut HttpContext) -> Result<ResponseValue, PipelineError> {
+        let table = context.service::<RouteTable>()?;
+        Ok(ResponseValue::json((*table).clone()))
+    }
+}
@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+use crate::http::request::HttpRequest;
+use crate::routing::route_data::RouteData;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ApiVersion {
+    major: u32,
+    minor: u32,
+}
+
+impl ApiVersion {
+    pub const fn new(major: u32, minor: u32) -> Self {
+        Self { major, minor }
+    }
+
+    pub fn major(&self) -> u32 {
+        self.major
+    }
+
+    pub fn minor(&self) -> u32 {
+        self.minor
+    }
+}
+
+impl FromStr for ApiVersion {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let trimmed = value.trim();
+        let raw = trimmed
+            .strip_prefix('v')
+            .or_else(|| trimmed.strip_prefix('V'))
+            .unwrap_or(trimmed);
+        let mut parts = raw.splitn(2, '.');
+        let major = parts
+            .next()
+            .and_then(|part| part.parse::<u32>().ok())
+            .ok_or_else(|| format!("invalid API version `{}`", value))?;
+        let minor = match parts.next() {
+            Some(part) => part
+                .parse::<u32>()
+                .map_err(|_| format!("invalid API version `{}`", value))?,
+            None => 0,
+        };
+        Ok(Self::new(major, minor))
+    }
+}
+
+impl Display for ApiVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiVersionReader {
+    RouteParam(String),
+    Query(String),
+    Header(String),
+    MediaType(String),
+}
+
+impl ApiVersionReader {
+    pub fn route_param(name: &str) -> Self {
+        Self::RouteParam(name.to_string())
+    }
+
+    pub fn query(name: &str) -> Self {
+        Self::Query(name.to_string())
+    }
+
+    pub fn header(name: &str) -> Self {
+        Self::Header(name.to_ascii_lowercase())
+    }
+
+    pub fn media_type(parameter: &str) -> Self {
+        Self::MediaType(parameter.to_string())
+    }
+
+    pub fn read(&self, request: &HttpRequest, route: &RouteData) -> Option<String> {
+        match self {
+            ApiVersionReader::RouteParam(name) => route.params().get(name).cloned(),
+            ApiVersionReader::Query(name) => request.query_param(name),
+            ApiVersionReader::Header(name) => request.headers().get(name).map(str::to_string),
+            ApiVersionReader::MediaType(parameter) => {
+                let accept = request.headers().get("accept")?;
+                accept
+                    .split(',')
+                    .flat_map(|media| media.split(';').skip(1))
+                    .filter_map(|param| param.split_once('='))
+                    .find(|(key, _)| key.trim().eq_ignore_ascii_case(parameter))
+                    .map(|(_, value)| value.trim().trim_matches('"').to_string())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ApiVersioning {
+    readers: Vec<ApiVersionReader>,
+    default_version: Option<ApiVersion>,
+    deprecated: HashMap<ApiVersion, Option<String>>,
+}
+
+impl ApiVersioning {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn read_from(mut self, reader: ApiVersionReader) -> Self {
+        self.readers.push(reader);
+        self
+    }
+
+    pub fn default_version(mut self, version: ApiVersion) -> Self {
+        self.default_version = Some(version);
+        self
+    }
+
+    pub fn deprecate(mut self, version: ApiVersion) -> Self {
+        self.deprecated.entry(version).or_insert(None);
+        self
+    }
+
+    pub fn sunset(mut self, version: ApiVersion, date: &str) -> Self {
+        self.deprecated.insert(version, Some(date.to_string()));
+        self
+    }
+
+    pub fn readers(&self) -> &[ApiVersionReader] {
+        &self.readers
+    }
+
+    pub fn is_deprecated(&self, version: &ApiVersion) -> bool {
+        self.deprecated.contains_key(version)
+    }
+
+    pub fn sunset_date(&self, version: &ApiVersion) -> Option<&str> {
+        self.deprecated
+            .get(version)
+            .and_then(|date| date.as_deref())
+    }
+
+    pub fn resolve(
+        &self,
+        request: &HttpRequest,
+        route: &RouteData,
+    ) -> Result<Option<ApiVersion>, String> {
+        for reader in &self.readers {
+            if let Some(raw) = reader.read(request, route) {
+                return raw.parse::<ApiVersion>().map(Some);
+            }
+        }
+        Ok(self.default_version)
+    }
+}
@@ -4,6 +4,7 @@ use crate::pipeline::middleware::Middleware;
 use crate::pipeline::next::Next;
 use crate::pipeline::pipeline::PipelineError;
 use async_trait::async_trait;
+use std::fmt::{Display, Formatter, Result as FmtResult};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Policy {
@@ -26,6 +27,16 @@ impl Policy {
     }
 }
 
+impl Display for Policy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Policy::Authenticated => write!(f, "authenticated"),
+            Policy::InRole(role) => write!(f, "role:{}", role),
+            Policy::Custom(name) => write!(f, "custom:{}", name),
+        }
+    }
+}
+
 pub struct AuthorizationMiddleware;
 
 impl AuthorizationMiddleware {
@@ -15,6 +15,7 @@ impl BackgroundTestkit for TestApp {
 
     fn run_background_jobs(&mut self) -> Vec<JobResult> {
         let queue = self.ensure_background_queue();
-        queue.run_all()
+        let rt = tokio::runtime::Runtime::new().expect("failed to create runtime");
+        rt.block_on(queue.run_all())
     }
 }
@@ -1,31 +1,85 @@
+use serde::Serialize;
+
 use crate::http::context::HttpContext;
 use crate::http::response_body::ResponseBody;
 use crate::result::into_response::IntoResponse;
 
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: &str, message: &str) -> Self {
+        Self {
+            field: field.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ValidationError {
     message: String,
+    errors: Vec<FieldError>,
 }
 
 impl ValidationError {
     pub fn new(message: &str) -> Self {
         Self {
             message: message.to_string(),
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn from_fields(errors: Vec<FieldError>) -> Self {
+        let details = errors
+            .iter()
+            .map(|error| format!("{}: {}", error.field, error.message))
+            .collect::<Vec<_>>()
+            .join("; ");
+        Self {
+            message: format!("validation failed: {}", details),
+            errors,
         }
     }
 
     pub fn message(&self) -> &str {
         &self.message
     }
+
+    pub fn errors(&self) -> &[FieldError] {
+        &self.errors
+    }
+}
+
+#[derive(Serialize)]
+struct ValidationProblem<'a> {
+    message: &'a str,
+    errors: &'a [FieldError],
 }
 
 impl IntoResponse for ValidationError {
     fn into_response(self, context: &mut HttpContext) {
         let response = context.response_mut();
         response.set_status(400);
-        response.set_body(ResponseBody::Text(self.message));
+        if self.errors.is_empty() {
+            response.set_body(ResponseBody::Text(self.message));
+            response
+                .headers_mut()
+                .insert("content-type", "text/plain; charset=utf-8");
+            return;
+        }
+
+        let problem = ValidationProblem {
+            message: &self.message,
+            errors: &self.errors,
+        };
+        let body = serde_json::to_string(&problem).unwrap_or_else(|_| self.message.clone());
+        response.set_body(ResponseBody::Text(body));
         response
             .headers_mut()
-            .insert("content-type", "text/plain; charset=utf-8");
+            .insert("content-type", "application/json");
     }
 }
@@ -5,10 +5,14 @@ use crate::http::context::HttpContext;
 
 mod error;
 mod middleware;
+pub mod rules;
+mod validate;
 mod validator;
 
-pub use error::ValidationError;
+pub use error::{FieldError, ValidationError};
 pub use middleware::ValidationMiddleware;
+pub use nimble_web_macros::Validate;
+pub use validate::{BodyValidator, ModelValidator, Validate};
 pub use validator::Validator;
 
 pub trait AnyValidator: Send + Sync {
@@ -0,0 +1,146 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::OnceLock;
+
+pub use regex::Regex;
+
+use crate::validation::{FieldError, Validate};
+
+pub trait HasLength {
+    fn length(&self) -> usize;
+}
+
+impl HasLength for str {
+    fn length(&self) -> usize {
+        self.chars().count()
+    }
+}
+
+impl HasLength for String {
+    fn length(&self) -> usize {
+        self.as_str().length()
+    }
+}
+
+impl<T> HasLength for [T] {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> HasLength for Vec<T> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<K, V> HasLength for HashMap<K, V> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<K, V> HasLength for BTreeMap<K, V> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T: HasLength + ?Sized> HasLength for &T {
+    fn length(&self) -> usize {
+        (**self).length()
+    }
+}
+
+pub trait Required {
+    fn is_present(&self) -> bool;
+}
+
+impl<T> Required for Option<T> {
+    fn is_present(&self) -> bool {
+        self.is_some()
+    }
+}
+
+impl Required for String {
+    fn is_present(&self) -> bool {
+        !self.trim().is_empty()
+    }
+}
+
+impl<T> Required for Vec<T> {
+    fn is_present(&self) -> bool {
+        !self.is_empty()
+    }
+}
+
+pub fn length_message(min: Option<usize>, max: Option<usize>) -> String {
+    match (min, max) {
+        (Some(min), Some(max)) if min == max => format!("length must be {}", min),
+        (Some(min), Some(max)) => format!("length must be between {} and {}", min, max),
+        (Some(min), None) => format!("length must be at least {}", min),
+        (None, Some(max)) => format!("length must be at most {}", max),
+        (None, None) => "has an invalid length".to_string(),
+    }
+}
+
+pub fn check_length<T: HasLength + ?Sized>(
+    value: &T,
+    min: Option<usize>,
+    max: Option<usize>,
+) -> bool {
+    let length = value.length();
+    min.is_none_or(|min| length >= min) && max.is_none_or(|max| length <= max)
+}
+
+pub fn range_message(min: Option<String>, max: Option<String>) -> String {
+    match (min, max) {
+        (Some(min), Some(max)) => format!("must be between {} and {}", min, max),
+        (Some(min), None) => format!("must be at least {}", min),
+        (None, Some(max)) => format!("must be at most {}", max),
+        (None, None) => "is out of range".to_string(),
+    }
+}
+
+pub fn is_email(value: &str) -> bool {
+    let Some((local, domain)) = value.split_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && !domain.contains('@')
+        && !value.chars().any(char::is_whitespace)
+        && domain
+            .split('.')
+            .all(|label| !label.is_empty() && !label.starts_with('-') && !label.ends_with('-'))
+        && domain.contains('.')
+}
+
+pub fn is_url(value: &str) -> bool {
+    let Some((scheme, rest)) = value.split_once("://") else {
+        return false;
+    };
+    let mut chars = scheme.chars();
+    let valid_scheme = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    valid_scheme && !host.is_empty() && !value.chars().any(char::is_whitespace)
+}
+
+pub fn matches_pattern(value: &str, cache: &'static OnceLock<Regex>, pattern: &str) -> bool {
+    cache
+        .get_or_init(|| Regex::new(pattern).expect("validation pattern is checked at compile time"))
+        .is_match(value)
+}
+
+pub fn nested<T: Validate + ?Sized>(value: &T, field: &str, errors: &mut Vec<FieldError>) {
+    if let Err(error) = value.validate() {
+        if error.errors().is_empty() {
+            errors.push(FieldError::new(field, error.message()));
+        }
+        for inner in error.errors() {
+            errors.push(FieldError::new(
+                &format!("{}.{}", field, inner.field),
+                &inner.message,
+            ));
+        }
+    }
+}
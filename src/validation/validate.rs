@@ -0,0 +1,58 @@
+use std::marker::PhantomData;
+
+use serde::de::DeserializeOwned;
+
+use crate::http::context::HttpContext;
+use crate::validation::{AnyValidator, ValidationError, Validator};
+
+pub trait Validate {
+    fn validate(&self) -> Result<(), ValidationError>;
+}
+
+pub struct ModelValidator<T> {
+    _marker: PhantomData<fn(&T)>,
+}
+
+impl<T> ModelValidator<T> {
+    pub fn new() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for ModelValidator<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Validate> Validator<T> for ModelValidator<T> {
+    fn validate(&self, value: &T) -> Result<(), ValidationError> {
+        value.validate()
+    }
+}
+
+pub struct BodyValidator<T> {
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> BodyValidator<T> {
+    pub fn new() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for BodyValidator<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Validate + DeserializeOwned> AnyValidator for BodyValidator<T> {
+    fn validate(&self, context: &HttpContext) -> Result<(), ValidationError> {
+        context.read_json::<T>()?.validate()
+    }
+}
@@ -181,8 +181,8 @@ fn app_builder_loads_env_config_with_prefix() {
     assert_eq!(app.config().get_bool("feature.enabled"), Some(true));
 }
 
-#[test]
-fn app_builder_registers_in_memory_job_queue() {
+#[tokio::test]
+async fn app_builder_registers_in_memory_job_queue() {
     let mut builder = AppBuilder::new();
     builder.use_in_memory_job_queue();
     let app = builder.build();
@@ -198,7 +198,7 @@ fn app_builder_registers_in_memory_job_queue() {
 
     let calls = Arc::new(Mutex::new(0));
     in_memory.enqueue(Box::new(CountJob::new(calls.clone())));
-    let results = in_memory.run_all();
+    let results = in_memory.run_all().await;
 
     assert_eq!(results.len(), 1);
     assert_eq!(*calls.lock().expect("calls lock"), 1);
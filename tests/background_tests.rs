@@ -114,8 +114,8 @@ fn job_context_exposes_services_and_failure_variant() {
     }
 }
 
-#[test]
-fn in_memory_job_queue_runs_jobs_and_returns_results() {
+#[tokio::test]
+async fn in_memory_job_queue_runs_jobs_and_returns_results() {
     let calls = Arc::new(Mutex::new(Vec::new()));
     let services = Arc::new(ServiceContainer::new().build());
     let queue = InMemoryJobQueue::new(services);
@@ -123,18 +123,18 @@ fn in_memory_job_queue_runs_jobs_and_returns_results() {
     queue.enqueue(Box::new(TestJob::new("first", calls.clone())));
     queue.enqueue(Box::new(TestJob::new("second", calls.clone())));
 
-    let result = queue.run_next();
+    let result = queue.run_next().await;
     assert!(matches!(result, Some(JobResult::Success)));
 
-    let results = queue.run_all();
+    let results = queue.run_all().await;
     assert_eq!(results.len(), 1);
 
     let snapshot = calls.lock().expect("calls lock").clone();
     assert_eq!(snapshot, vec!["first", "second"]);
 }
 
-#[test]
-fn job_queue_runner_respects_accepting_state() {
+#[tokio::test]
+async fn job_queue_runner_respects_accepting_state() {
     let calls = Arc::new(Mutex::new(Vec::new()));
     let services = Arc::new(ServiceContainer::new().build());
     let queue = Arc::new(InMemoryJobQueue::new(services));
@@ -142,12 +142,12 @@ fn job_queue_runner_respects_accepting_state() {
 
     runner.stop();
     runner.enqueue(Box::new(TestJob::new("ignored", calls.clone())));
-    let results = runner.run_pending_jobs();
+    let results = runner.run_pending_jobs().await;
     assert!(results.is_empty());
 
     runner.start(HostedServiceContext::new(ServiceContainer::new().build()));
     runner.enqueue(Box::new(TestJob::new("accepted", calls.clone())));
-    let results = runner.run_pending_jobs();
+    let results = runner.run_pending_jobs().await;
     assert_eq!(results.len(), 1);
 
     let snapshot = calls.lock().expect("calls lock").clone();
@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use nimble_web::app::builder::AppBuilder;
+use nimble_web::config::{BindError, ConfigBuilder, Configuration};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct StorageOptions {
+    bucket_name: String,
+    max_upload_mb: u32,
+    public: bool,
+    regions: Vec<String>,
+    retry: RetryOptions,
+    #[serde(default)]
+    tags: HashMap<String, String>,
+    cdn: Option<String>,
+    mode: Mode,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct RetryOptions {
+    attempts: u8,
+    backoff_ms: Vec<u64>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+enum Mode {
+    ReadOnly,
+    ReadWrite,
+}
+
+fn config(entries: &[(&str, &str)]) -> Configuration {
+    Configuration::from_values(
+        entries
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect(),
+    )
+}
+
+fn data_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("data")
+        .join(name)
+}
+
+fn storage() -> Configuration {
+    config(&[
+        ("Storage.BucketName", "photos"),
+        ("storage.max_upload_mb", "25"),
+        ("STORAGE.PUBLIC", "true"),
+        ("storage.regions.1", "us-east"),
+        ("storage.regions.0", "eu-west"),
+        ("storage.retry.attempts", "3"),
+        ("storage.retry.backoffMs", "100, 250, 500"),
+        ("storage.tags.team", "media"),
+        ("storage.mode", "read_write"),
+    ])
+}
+
+#[test]
+fn binds_sections_with_any_key_style() {
+    let options: StorageOptions = storage().bind("Storage").expect("bind");
+
+    assert_eq!(
+        options,
+        StorageOptions {
+            bucket_name: "photos".to_string(),
+            max_upload_mb: 25,
+            public: true,
+            regions: vec!["eu-west".to_string(), "us-east".to_string()],
+            retry: RetryOptions {
+                attempts: 3,
+                backoff_ms: vec![100, 250, 500],
+            },
+            tags: HashMap::from([("team".to_string(), "media".to_string())]),
+            cdn: None,
+            mode: Mode::ReadWrite,
+        }
+    );
+}
+
+#[test]
+fn binds_nested_files() {
+    let config = ConfigBuilder::new()
+        .with_json(data_path("config.json"))
+        .build();
+
+    let server: HashMap<String, String> = config.bind("server").expect("bind");
+
+    assert_eq!(server.get("port").map(String::as_str), Some("3000"));
+}
+
+#[test]
+fn reports_invalid_values_with_their_key() {
+    let config = config(&[("retry.attempts", "many"), ("retry.backoff_ms", "1")]);
+
+    let error = config.bind::<RetryOptions>("Retry").unwrap_err();
+
+    assert_eq!(
+        error,
+        BindError {
+            section: "Retry".to_string(),
+            message: "invalid value `many` for `retry.attempts`: expected u8".to_string(),
+        }
+    );
+    assert!(config
+        .bind::<StorageOptions>("storage")
+        .unwrap_err()
+        .to_string()
+        .starts_with("invalid configuration section `storage`: missing field"));
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct JwtOptions {
+    issuer: String,
+    access_token_minutes: u32,
+}
+
+#[test]
+fn configure_registers_options_in_services() {
+    let mut builder = AppBuilder::new();
+    builder
+        .use_config(data_path("config.json"))
+        .configure::<JwtOptions>("jwt");
+    let app = builder.build();
+
+    let jwt = app.services().get::<JwtOptions>();
+    assert_eq!(jwt.issuer, "mrp-issuer");
+    assert_eq!(jwt.access_token_minutes, 60);
+}
+
+#[test]
+#[should_panic(expected = "invalid configuration section `Jwt`: missing field `Issuer`")]
+fn configure_fails_build_on_invalid_section() {
+    let mut builder = AppBuilder::new();
+    builder.configure::<JwtOptions>("Jwt");
+    builder.build();
+}
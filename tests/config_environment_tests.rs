@@ -0,0 +1,156 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+use nimble_web::app::builder::AppBuilder;
+use nimble_web::config::{ConfigBuilder, Configuration};
+
+fn env_lock() -> MutexGuard<'static, ()> {
+    static LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+    LOCK.get_or_init(|| Mutex::new(()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+struct EnvGuard(&'static str);
+
+impl EnvGuard {
+    fn set(key: &'static str, value: &str) -> Self {
+        env::set_var(key, value);
+        Self(key)
+    }
+}
+
+impl Drop for EnvGuard {
+    fn drop(&mut self) {
+        env::remove_var(self.0);
+    }
+}
+
+fn config_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("nimble-env-{}-{}", name, std::process::id()));
+    fs::create_dir_all(&dir).expect("create dir");
+    fs::write(
+        dir.join("web.config.json"),
+        r#"{"Server":{"Port":8080,"Host":"0.0.0.0"},"Db":{"Url":"postgres://base"}}"#,
+    )
+    .expect("write base");
+    fs::write(
+        dir.join("web.config.Staging.json"),
+        r#"{"Db":{"Url":"postgres://staging"}}"#,
+    )
+    .expect("write json");
+    fs::write(
+        dir.join("web.config.Staging.toml"),
+        "[server]\nport = 9090\n",
+    )
+    .expect("write toml");
+    dir
+}
+
+#[test]
+fn layers_environment_files_over_base_file() {
+    let _lock = env_lock();
+    let dir = config_dir("layers");
+
+    let config = ConfigBuilder::new()
+        .with_environment("Staging")
+        .with_environment_files(dir.join("web.config.json"))
+        .with_optional_file(dir.join("web.config.local.json"))
+        .build();
+
+    assert_eq!(config.environment(), "Staging");
+    assert!(!config.is_development());
+    assert_eq!(config.get("server.port"), Some("9090"));
+    assert_eq!(config.get("server.host"), Some("0.0.0.0"));
+    assert_eq!(config.get("db.url"), Some("postgres://staging"));
+
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn missing_environment_files_are_skipped() {
+    let _lock = env_lock();
+    let dir = config_dir("missing");
+
+    let config = ConfigBuilder::new()
+        .with_environment("Development")
+        .with_environment_files(dir.join("web.config.json"))
+        .build();
+
+    assert!(config.is_development());
+    assert_eq!(config.get("server.port"), Some("8080"));
+
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn environment_comes_from_variable_unless_set_on_builder() {
+    let _lock = env_lock();
+    let _guard = EnvGuard::set("NIMBLE_ENVIRONMENT", "development");
+
+    assert!(ConfigBuilder::new().build().is_development());
+    assert_eq!(
+        ConfigBuilder::new()
+            .with_environment("Test")
+            .build()
+            .environment(),
+        "Test"
+    );
+    drop(_guard);
+    assert_eq!(ConfigBuilder::new().build().environment(), "Production");
+    assert!(Configuration::default().is_production());
+}
+
+#[test]
+fn debug_view_reports_source_of_each_key() {
+    let _lock = env_lock();
+    let dir = config_dir("sources");
+    let _guard = EnvGuard::set("NIMBLE_TEST_DB_URL", "postgres://env");
+
+    let config = ConfigBuilder::new()
+        .with_environment("Staging")
+        .with_environment_files(dir.join("web.config.json"))
+        .with_env("NIMBLE_TEST_")
+        .build();
+
+    let base = dir.join("web.config.json");
+    let toml = dir.join("web.config.Staging.toml");
+    assert_eq!(
+        config.source("Server.Host"),
+        Some(format!("json file `{}`", base.display()).as_str())
+    );
+    assert_eq!(
+        config.source("server.port"),
+        Some(format!("toml file `{}`", toml.display()).as_str())
+    );
+    assert_eq!(config.get("db.url"), Some("postgres://env"));
+    assert_eq!(
+        config.debug_view(),
+        format!(
+            "environment: Staging\n  db.url <- environment variables `NIMBLE_TEST_*`\n  server.host <- json file `{}`\n  server.port <- toml file `{}`",
+            base.display(),
+            toml.display()
+        )
+    );
+
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn app_builder_uses_environment_files() {
+    let _lock = env_lock();
+    let dir = config_dir("app");
+
+    let mut builder = AppBuilder::new();
+    builder
+        .use_environment("Staging")
+        .use_config(dir.join("web.config.json"));
+    let app = builder.build();
+
+    assert_eq!(app.config().environment(), "Staging");
+    assert_eq!(app.config().get("db.url"), Some("postgres://staging"));
+
+    let _ = fs::remove_dir_all(dir);
+}
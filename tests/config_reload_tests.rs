@@ -0,0 +1,129 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use nimble_web::app::builder::AppBuilder;
+use nimble_web::config::{ConfigBuilder, OptionsMonitor, ReloadableConfiguration};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct FeatureOptions {
+    enabled: bool,
+    limit: u32,
+}
+
+fn config_file(name: &str, contents: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("nimble-reload-{}-{}", name, std::process::id()));
+    fs::create_dir_all(&dir).expect("create dir");
+    let path = dir.join("web.config.json");
+    fs::write(&path, contents).expect("write config");
+    path
+}
+
+fn features(enabled: bool, limit: u32) -> String {
+    format!(
+        r#"{{"Features":{{"Enabled":{},"Limit":{}}}}}"#,
+        enabled, limit
+    )
+}
+
+fn cleanup(path: &Path) {
+    if let Some(dir) = path.parent() {
+        let _ = fs::remove_dir_all(dir);
+    }
+}
+
+#[test]
+fn reload_applies_file_changes_and_notifies() {
+    let path = config_file("manual", &features(false, 10));
+    let config = ConfigBuilder::new()
+        .with_environment("Test")
+        .with_json(&path)
+        .build_reloadable();
+    let notified = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&notified);
+    config.on_change(move |_| {
+        counter.fetch_add(1, Ordering::SeqCst);
+    });
+
+    assert!(!config.reload());
+    fs::write(&path, features(true, 25)).expect("rewrite config");
+    assert!(config.reload());
+
+    assert_eq!(config.current().get("features.limit"), Some("25"));
+    assert_eq!(config.current().environment(), "Test");
+    assert_eq!(notified.load(Ordering::SeqCst), 1);
+
+    cleanup(&path);
+}
+
+#[test]
+fn options_monitor_keeps_last_valid_options() {
+    let path = config_file("monitor", &features(false, 10));
+    let config = ConfigBuilder::new().with_json(&path).build_reloadable();
+    let monitor = OptionsMonitor::<FeatureOptions>::new(&config, "Features").expect("bind");
+    let seen = Arc::new(AtomicUsize::new(0));
+    let last = Arc::clone(&seen);
+    monitor.on_change(move |options: &FeatureOptions| {
+        last.store(options.limit as usize, Ordering::SeqCst);
+    });
+
+    fs::write(&path, features(true, 40)).expect("rewrite config");
+    config.reload();
+    assert!(monitor.current().enabled);
+    assert_eq!(monitor.current().limit, 40);
+    assert_eq!(seen.load(Ordering::SeqCst), 40);
+
+    fs::write(&path, r#"{"Features":{"Enabled":true,"Limit":"lots"}}"#).expect("rewrite");
+    assert!(config.reload());
+    assert_eq!(monitor.current().limit, 40);
+
+    cleanup(&path);
+}
+
+#[test]
+fn broken_file_keeps_previous_configuration() {
+    let path = config_file("broken", &features(true, 5));
+    let config = ConfigBuilder::new().with_json(&path).build_reloadable();
+
+    fs::write(&path, "{ not json").expect("rewrite config");
+
+    assert!(!config.reload());
+    assert_eq!(config.current().get("features.limit"), Some("5"));
+
+    cleanup(&path);
+}
+
+#[test]
+fn app_watches_config_files_when_enabled() {
+    let path = config_file("watch", &features(false, 1));
+    let mut builder = AppBuilder::new();
+    builder
+        .use_config(&path)
+        .use_config_reload(Duration::from_millis(20))
+        .configure::<FeatureOptions>("Features");
+    let app = builder.build();
+    let monitor = app.services().get::<OptionsMonitor<FeatureOptions>>();
+    assert!(app
+        .services()
+        .resolve::<ReloadableConfiguration>()
+        .is_some());
+
+    thread::sleep(Duration::from_millis(50));
+    fs::write(&path, features(true, 2)).expect("rewrite config");
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while monitor.current().limit != 2 && Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(20));
+    }
+
+    assert_eq!(monitor.current().limit, 2);
+    assert!(monitor.current().enabled);
+    assert_eq!(app.services().get::<FeatureOptions>().limit, 1);
+
+    cleanup(&path);
+}
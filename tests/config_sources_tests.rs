@@ -0,0 +1,335 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use nimble_web::app::builder::AppBuilder;
+use nimble_web::config::file::FileSource;
+use nimble_web::config::source::ConfigSource;
+use nimble_web::config::{CommandLineSource, ConfigBuilder, SecretsDirectorySource};
+use nimble_web::Configuration;
+use serde::Deserialize;
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("nimble-sources-{}-{}", name, std::process::id()));
+    fs::create_dir_all(&dir).expect("create dir");
+    dir
+}
+
+#[test]
+fn command_line_overrides_file_values() {
+    let dir = temp_dir("args");
+    let path = dir.join("web.config.json");
+    fs::write(&path, r#"{"Postgres":{"Url":"postgres://file","Pool":5}}"#).expect("write");
+
+    let config = ConfigBuilder::new()
+        .with_json(&path)
+        .with_args([
+            "serve",
+            "--Postgres:Url=postgres://cli",
+            "--server__port",
+            "9000",
+            "--verbose",
+            "--",
+            "--ignored=1",
+        ])
+        .build();
+
+    assert_eq!(config.get("postgres.url"), Some("postgres://cli"));
+    assert_eq!(config.get("postgres.pool"), Some("5"));
+    assert_eq!(config.get("server.port"), Some("9000"));
+    assert_eq!(config.get_bool("verbose"), Some(true));
+    assert_eq!(config.get("ignored"), None);
+    assert_eq!(
+        config.source("postgres.url"),
+        Some("command line arguments")
+    );
+
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn dotenv_file_maps_prefixed_keys() {
+    let dir = temp_dir("dotenv");
+    let path = dir.join(".env");
+    fs::write(
+        &path,
+        "# local overrides\nAPP_DB__URL=postgres://dotenv # inline comment\nexport APP_DB__NAME='orders # main'\nAPP_GREETING=\"hello\\nworld\"\nOTHER__KEY=skipped\n",
+    )
+    .expect("write");
+
+    let config = ConfigBuilder::new()
+        .with_dotenv(&path, "APP_")
+        .with_dotenv(dir.join("missing.env"), "APP_")
+        .build();
+
+    assert_eq!(config.get("db.url"), Some("postgres://dotenv"));
+    assert_eq!(config.get("db.name"), Some("orders # main"));
+    assert_eq!(config.get("greeting"), Some("hello\nworld"));
+    assert_eq!(config.get("other.key"), None);
+
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+struct PoolOptions {
+    pool_size: u32,
+    max_idle_seconds: u64,
+}
+
+#[test]
+fn dotenv_keeps_single_underscores_in_names() {
+    let dir = temp_dir("dotenv-nested");
+    let path = dir.join(".env");
+    fs::write(
+        &path,
+        "NIMBLE_POSTGRES__POOL_SIZE=12\nNIMBLE_POSTGRES__MAX_IDLE_SECONDS=30\n",
+    )
+    .expect("write");
+
+    let config = ConfigBuilder::new().with_dotenv(&path, "NIMBLE_").build();
+
+    assert_eq!(config.get("postgres.pool_size"), Some("12"));
+    assert_eq!(
+        config.bind::<PoolOptions>("Postgres").expect("bind"),
+        PoolOptions {
+            pool_size: 12,
+            max_idle_seconds: 30,
+        }
+    );
+
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct ServiceOptions {
+    name: String,
+    hosts: Vec<String>,
+    limits: Vec<Limit>,
+    motd: String,
+    banner: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Limit {
+    route: String,
+    rate: u32,
+}
+
+#[test]
+fn app_builder_dotenv_uses_the_given_prefix() {
+    let dir = temp_dir("dotenv-app");
+    let path = dir.join(".env");
+    fs::write(
+        &path,
+        "SHOP_DB__URL=postgres://shop\nNIMBLE_DB__URL=postgres://nimble\n",
+    )
+    .expect("write");
+
+    let mut builder = AppBuilder::new();
+    builder.use_dotenv(&path, "SHOP_");
+    let app = builder.build();
+    let config = app.services().get::<Configuration>();
+
+    assert_eq!(config.get("db.url"), Some("postgres://shop"));
+    assert_eq!(config.get("nimble.db.url"), None);
+
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn yaml_file_is_flattened_like_json() {
+    let dir = temp_dir("yaml");
+    let path = dir.join("web.config.yaml");
+    fs::write(
+        &path,
+        r#"---
+# service settings
+Service:
+  Name: "orders: api"   # quoted colon
+  Hosts: [a.example.com, 'b.example.com']
+  Limits:
+    - Route: /orders
+      Rate: 10
+    - Route: /users
+      Rate: 20
+  Motd: |
+    line one
+
+    line two
+  Banner: >-
+    folded
+    text
+  Empty: ~
+Server:
+  Port: 8081
+"#,
+    )
+    .expect("write");
+
+    let config = ConfigBuilder::new().with_file(&path).build();
+
+    assert_eq!(config.get("server.port"), Some("8081"));
+    assert_eq!(config.get("service.empty"), None);
+    assert_eq!(
+        config.bind::<ServiceOptions>("Service").expect("bind"),
+        ServiceOptions {
+            name: "orders: api".to_string(),
+            hosts: vec!["a.example.com".to_string(), "b.example.com".to_string()],
+            limits: vec![
+                Limit {
+                    route: "/orders".to_string(),
+                    rate: 10,
+                },
+                Limit {
+                    route: "/users".to_string(),
+                    rate: 20,
+                },
+            ],
+            motd: "line one\n\nline two\n".to_string(),
+            banner: "folded text".to_string(),
+        }
+    );
+    assert_eq!(
+        config.source("server.port"),
+        Some(format!("yaml file `{}`", path.display()).as_str())
+    );
+
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn yaml_flow_collections_quoting_and_merge_keys() {
+    let dir = temp_dir("yaml-flow");
+    let path = dir.join("web.config.yaml");
+    fs::write(
+        &path,
+        r##"defaults: &defaults
+  pool: 5
+  host: localhost
+hosts: ["a,b", c, 'd: e']
+db: {host: x, ports: [5432, 5433]}
+primary:
+  <<: *defaults
+  host: primary.internal
+replica: *defaults
+hash: "#not-a-comment"
+escaped: "tab\tend"
+single: 'it''s'
+"##,
+    )
+    .expect("write");
+
+    let config = ConfigBuilder::new().with_yaml(&path).build();
+
+    assert_eq!(config.get("hosts.0"), Some("a,b"));
+    assert_eq!(config.get("hosts.1"), Some("c"));
+    assert_eq!(config.get("hosts.2"), Some("d: e"));
+    assert_eq!(config.get("db.host"), Some("x"));
+    assert_eq!(config.get("db.ports.1"), Some("5433"));
+    assert_eq!(config.get("primary.host"), Some("primary.internal"));
+    assert_eq!(config.get("primary.pool"), Some("5"));
+    assert_eq!(config.get("replica.host"), Some("localhost"));
+    assert_eq!(config.get("hash"), Some("#not-a-comment"));
+    assert_eq!(config.get("escaped"), Some("tab\tend"));
+    assert_eq!(config.get("single"), Some("it's"));
+
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+#[should_panic(
+    expected = "yaml parse failed: mapping values are not allowed in this context at line 3"
+)]
+fn yaml_reports_bad_indentation() {
+    let dir = temp_dir("yaml-bad");
+    let path = dir.join("bad.yaml");
+    fs::write(&path, "server:\n  port: 1\n    host: x\n").expect("write");
+
+    ConfigBuilder::new().with_yaml(&path).build();
+}
+
+#[test]
+fn file_sources_return_load_errors() {
+    let dir = temp_dir("try-load");
+    let yaml = dir.join("bad.yaml");
+    fs::write(&yaml, "server:\n  port: 1\n    host: x\n").expect("write");
+    let json = dir.join("bad.json");
+    fs::write(&json, "{ not json").expect("write");
+
+    let error = FileSource::yaml(yaml.clone())
+        .try_load()
+        .expect_err("bad yaml");
+    assert_eq!(error.source, format!("yaml file `{}`", yaml.display()));
+    assert!(error
+        .message
+        .starts_with("yaml parse failed: mapping values are not allowed"));
+    assert!(FileSource::json(json).try_load().is_err());
+    assert!(FileSource::toml(dir.join("missing.toml"))
+        .try_load()
+        .is_err());
+    assert!(SecretsDirectorySource::new(dir.join("absent"))
+        .try_load()
+        .is_err());
+    assert_eq!(
+        FileSource::optional_json(dir.join("missing.json")).try_load(),
+        Ok(Default::default())
+    );
+
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn secrets_directory_maps_file_names_to_keys() {
+    let dir = temp_dir("secrets");
+    fs::write(dir.join("postgres.url"), "postgres://secret\n").expect("write");
+    fs::write(dir.join("Jwt__SigningKey"), "s3cr3t").expect("write");
+    fs::write(dir.join(".hidden"), "nope").expect("write");
+
+    let config = ConfigBuilder::new()
+        .with_secrets(&dir)
+        .with_secrets(dir.join("missing"))
+        .build();
+
+    assert_eq!(config.get("postgres.url"), Some("postgres://secret"));
+    assert_eq!(config.get("jwt.signingkey"), Some("s3cr3t"));
+    assert_eq!(config.get(".hidden"), None);
+    assert_eq!(
+        config.source("postgres.url"),
+        Some(format!("secrets directory `{}`", dir.display()).as_str())
+    );
+
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+#[should_panic(expected = "secrets directory read failed")]
+fn required_secrets_directory_must_exist() {
+    ConfigBuilder::new()
+        .with_source(SecretsDirectorySource::new(temp_dir("none").join("absent")))
+        .build();
+}
+
+#[test]
+fn placeholders_expand_across_sources() {
+    env::set_var("NIMBLE_SOURCES_TEST_HOST", "db.internal");
+    let config = ConfigBuilder::new()
+        .with_source(CommandLineSource::new([
+            "--Postgres:User=app",
+            "--Postgres:Url=postgres://${Postgres:User}@${NIMBLE_SOURCES_TEST_HOST}/${Postgres:Db:-orders}",
+            "--Literal=$${NOT_EXPANDED}",
+            "--Missing=${NIMBLE_SOURCES_TEST_UNSET}",
+            "--Loop=${Loop}",
+        ]))
+        .build();
+    env::remove_var("NIMBLE_SOURCES_TEST_HOST");
+
+    assert_eq!(
+        config.get("postgres.url"),
+        Some("postgres://app@db.internal/orders")
+    );
+    assert_eq!(config.get("literal"), Some("${NOT_EXPANDED}"));
+    assert_eq!(config.get("missing"), Some("${NIMBLE_SOURCES_TEST_UNSET}"));
+    assert_eq!(config.get("loop"), Some("${Loop}"));
+}
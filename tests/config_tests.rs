@@ -100,6 +100,19 @@ fn env_overrides_file_values_with_prefix() {
     assert_eq!(config.get("upload.max.mb"), Some("100"));
 }
 
+#[test]
+fn env_double_underscore_separates_sections() {
+    let _lock = env_lock();
+    let _nested = EnvGuard::set("NIMBLE_POSTGRES__POOL_SIZE", "12");
+    let _legacy = EnvGuard::set("NIMBLE_UPLOAD_MAX_MB", "100");
+
+    let config = ConfigBuilder::new().with_env("NIMBLE_").build();
+
+    assert_eq!(config.get("postgres.pool_size"), Some("12"));
+    assert_eq!(config.get("postgres.pool.size"), None);
+    assert_eq!(config.get("upload.max.mb"), Some("100"));
+}
+
 #[test]
 fn env_prefix_ignores_unrelated_keys() {
     let _lock = env_lock();
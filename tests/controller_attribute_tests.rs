@@ -0,0 +1,190 @@
+use nimble_web::app::application::Application;
+use nimble_web::app::builder::AppBuilder;
+use nimble_web::controller;
+use nimble_web::controller::controller::Controller;
+use nimble_web::http::context::HttpContext;
+use nimble_web::http::request::HttpRequest;
+use nimble_web::http::response::HttpResponse;
+use nimble_web::http::response_body::ResponseBody;
+use nimble_web::result::Json;
+use nimble_web::security::policy::Policy;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+#[derive(Default)]
+struct Counter {
+    created: AtomicUsize,
+}
+
+struct PhotosController {
+    instance: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct NewPhoto {
+    title: String,
+}
+
+#[controller("/api/photos")]
+impl PhotosController {
+    #[get]
+    async fn list(&self) -> String {
+        format!("list from {}", self.instance)
+    }
+
+    #[get("/{id}", name = "photo-detail")]
+    async fn detail(&self, id: i64, verbose: Option<bool>) -> String {
+        format!("photo {} verbose={}", id, verbose.unwrap_or(false))
+    }
+
+    #[post("/", policy = Policy::Authenticated)]
+    async fn create(&self, #[body] photo: NewPhoto) -> Json<String> {
+        Json(photo.title)
+    }
+
+    #[route("/{id}", method = "patch")]
+    fn touch(context: &HttpContext, id: i64) -> String {
+        format!("touched {} via {}", id, context.request().method())
+    }
+
+    fn helper(&self) -> usize {
+        self.instance
+    }
+}
+
+struct AdminController;
+
+#[controller(prefix = "/admin/{tenant}", policy = Policy::InRole("admin".to_string()))]
+impl AdminController {
+    #[get("/users")]
+    fn users(tenant: String) -> String {
+        format!("users of {}", tenant)
+    }
+
+    #[delete("/cache", policy = Policy::Authenticated)]
+    fn clear_cache() -> &'static str {
+        "cleared"
+    }
+}
+
+fn app() -> Application {
+    let mut builder = AppBuilder::new();
+    builder.register_instance(Arc::new(Counter::default()));
+    builder.register_transient::<PhotosController, _>(|provider| {
+        let counter = provider
+            .resolve::<Arc<Counter>>()
+            .expect("counter registered");
+        PhotosController {
+            instance: counter.created.fetch_add(1, Ordering::SeqCst) + 1,
+        }
+    });
+    builder.use_controller::<PhotosController>();
+    builder.use_controller::<AdminController>();
+    builder.build()
+}
+
+fn send(app: &Application, request: HttpRequest) -> HttpResponse {
+    Runtime::new()
+        .expect("runtime")
+        .block_on(app.handle_http_request(request))
+}
+
+fn body(response: &HttpResponse) -> &str {
+    match response.body() {
+        ResponseBody::Text(text) => text,
+        other => panic!("unexpected body {:?}", other),
+    }
+}
+
+#[test]
+fn controller_routes_are_prefixed() {
+    let routes = PhotosController::routes();
+    let patterns: Vec<String> = routes
+        .iter()
+        .map(|route| format!("{} {}", route.route.method(), route.route.path()))
+        .collect();
+
+    assert_eq!(
+        patterns,
+        vec![
+            "GET /api/photos",
+            "GET /api/photos/{id}",
+            "POST /api/photos",
+            "PATCH /api/photos/{id}",
+        ]
+    );
+    assert_eq!(routes[1].endpoint.metadata().name(), Some("photo-detail"));
+    assert_eq!(
+        routes[2].endpoint.metadata().policy(),
+        Some(&Policy::Authenticated)
+    );
+    assert_eq!(routes[0].endpoint.metadata().policy(), None);
+}
+
+#[test]
+fn controller_is_resolved_from_services_per_request() {
+    let app = app();
+
+    assert_eq!(
+        body(&send(&app, HttpRequest::new("GET", "/api/photos"))),
+        "list from 1"
+    );
+    assert_eq!(
+        body(&send(&app, HttpRequest::new("GET", "/api/photos"))),
+        "list from 2"
+    );
+}
+
+#[test]
+fn controller_actions_bind_parameters() {
+    let app = app();
+
+    let mut request = HttpRequest::new("GET", "/api/photos/7");
+    request.set_query(Some("verbose=true".to_string()));
+    assert_eq!(body(&send(&app, request)), "photo 7 verbose=true");
+
+    let response = send(&app, HttpRequest::new("PATCH", "/api/photos/7"));
+    assert_eq!(body(&response), "touched 7 via PATCH");
+
+    let response = send(&app, HttpRequest::new("GET", "/admin/acme/users"));
+    assert_eq!(body(&response), "users of acme");
+}
+
+#[test]
+fn missing_controller_registration_is_reported() {
+    let mut builder = AppBuilder::new();
+    builder.use_controller::<PhotosController>();
+    let app = builder.build();
+
+    let response = send(&app, HttpRequest::new("GET", "/api/photos"));
+
+    assert_eq!(response.status(), 500);
+}
+
+#[test]
+fn controller_policy_applies_to_all_actions_unless_overridden() {
+    let routes = AdminController::routes();
+
+    assert_eq!(routes[0].route.path(), "/admin/{tenant}/users");
+    assert_eq!(
+        routes[0].endpoint.metadata().policy(),
+        Some(&Policy::InRole("admin".to_string()))
+    );
+    assert_eq!(routes[1].route.method(), "DELETE");
+    assert_eq!(
+        routes[1].endpoint.metadata().policy(),
+        Some(&Policy::Authenticated)
+    );
+}
+
+#[test]
+fn controller_methods_remain_callable() {
+    let controller = PhotosController { instance: 3 };
+    let value = Runtime::new().expect("runtime").block_on(controller.list());
+
+    assert_eq!(value, "list from 3");
+    assert_eq!(controller.helper(), 3);
+    assert_eq!(AdminController::users("acme".to_string()), "users of acme");
+}
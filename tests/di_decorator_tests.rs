@@ -0,0 +1,125 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use nimble_web::app::builder::AppBuilder;
+use nimble_web::di::ServiceContainer;
+
+trait PriceSource: Send + Sync {
+    fn price(&self, sku: &str) -> String;
+}
+
+struct Warehouse;
+
+impl PriceSource for Warehouse {
+    fn price(&self, sku: &str) -> String {
+        format!("{}=10", sku)
+    }
+}
+
+struct Cached {
+    inner: Arc<dyn PriceSource>,
+}
+
+impl PriceSource for Cached {
+    fn price(&self, sku: &str) -> String {
+        format!("cached({})", self.inner.price(sku))
+    }
+}
+
+struct Metered {
+    inner: Arc<dyn PriceSource>,
+    prefix: String,
+}
+
+impl PriceSource for Metered {
+    fn price(&self, sku: &str) -> String {
+        format!("{}({})", self.prefix, self.inner.price(sku))
+    }
+}
+
+#[derive(Clone)]
+struct MetricsPrefix(String);
+
+type Prices = Arc<dyn PriceSource>;
+
+#[test]
+fn decorators_compose_regardless_of_registration_order() {
+    let mut container = ServiceContainer::new();
+    container.decorate::<Prices, _>(|inner, _| {
+        Arc::new(Cached {
+            inner: (*inner).clone(),
+        })
+    });
+    container.register_singleton::<Prices, _>(|_| Arc::new(Warehouse));
+    container.register_instance(MetricsPrefix("metered".to_string()));
+    container.decorate::<Prices, _>(|inner, provider| {
+        Arc::new(Metered {
+            inner: (*inner).clone(),
+            prefix: provider.get::<MetricsPrefix>().0.clone(),
+        })
+    });
+
+    let provider = container.build();
+
+    assert_eq!(
+        provider.get::<Prices>().price("sku-1"),
+        "metered(cached(sku-1=10))"
+    );
+}
+
+#[test]
+fn decorated_services_keep_their_lifetime() {
+    let created = Arc::new(AtomicUsize::new(0));
+    let counter = created.clone();
+
+    let mut builder = AppBuilder::new();
+    builder
+        .register_scoped::<Prices, _>(|_| Arc::new(Warehouse))
+        .decorate::<Prices, _>(move |inner, _| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Arc::new(Cached {
+                inner: (*inner).clone(),
+            })
+        });
+    let app = builder.build();
+    let first = app.services().create_scope();
+    let second = app.services().create_scope();
+
+    let a = first.resolve::<Prices>().expect("prices");
+    let b = first.resolve::<Prices>().expect("prices");
+    let c = second.resolve::<Prices>().expect("prices");
+
+    assert!(Arc::ptr_eq(&a, &b));
+    assert!(!Arc::ptr_eq(&a, &c));
+    assert_eq!(created.load(Ordering::SeqCst), 2);
+    assert_eq!(c.price("sku-2"), "cached(sku-2=10)");
+}
+
+#[test]
+fn decorators_wrap_every_registration() {
+    let mut container = ServiceContainer::new();
+    container.register_transient::<Prices, _>(|_| Arc::new(Warehouse));
+    container.register_transient::<Prices, _>(|_| {
+        Arc::new(Cached {
+            inner: Arc::new(Warehouse),
+        })
+    });
+    container.decorate::<Prices, _>(|inner, _| {
+        Arc::new(Metered {
+            inner: (*inner).clone(),
+            prefix: "metered".to_string(),
+        })
+    });
+
+    let prices: Vec<String> = container
+        .build()
+        .resolve_all::<Prices>()
+        .iter()
+        .map(|source| source.price("sku-3"))
+        .collect();
+
+    assert_eq!(
+        prices,
+        vec!["metered(sku-3=10)", "metered(cached(sku-3=10))"]
+    );
+}
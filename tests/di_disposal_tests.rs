@@ -0,0 +1,229 @@
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use nimble_web::app::builder::AppBuilder;
+use nimble_web::background::in_memory_queue::InMemoryJobQueue;
+use nimble_web::background::job::{BackgroundJob, JobContext, JobResult};
+use nimble_web::background::job_queue::JobQueue;
+use nimble_web::di::{Disposable, ServiceContainer, ServiceLifetime, ServiceProvider};
+use nimble_web::get;
+use nimble_web::http::request::HttpRequest;
+use nimble_web::http::response_body::ResponseBody;
+use tokio::runtime::Runtime;
+
+type Log = Arc<Mutex<Vec<String>>>;
+
+struct Connection {
+    log: Log,
+}
+
+#[async_trait]
+impl Disposable for Connection {
+    async fn dispose(&self) {
+        self.log
+            .lock()
+            .unwrap()
+            .push("connection returned".to_string());
+    }
+}
+
+struct Transaction {
+    log: Log,
+    _connection: Arc<Connection>,
+}
+
+#[async_trait]
+impl Disposable for Transaction {
+    async fn dispose(&self) {
+        self.log
+            .lock()
+            .unwrap()
+            .push("transaction closed".to_string());
+    }
+}
+
+struct Telemetry {
+    log: Log,
+}
+
+#[async_trait]
+impl Disposable for Telemetry {
+    async fn dispose(&self) {
+        self.log
+            .lock()
+            .unwrap()
+            .push("telemetry flushed".to_string());
+    }
+}
+
+fn connection(log: &Log) -> impl Fn(Arc<ServiceProvider>) -> Connection + Send + Sync {
+    let log = log.clone();
+    move |_| Connection { log: log.clone() }
+}
+
+fn transaction(log: &Log) -> impl Fn(Arc<ServiceProvider>) -> Transaction + Send + Sync {
+    let log = log.clone();
+    move |provider| Transaction {
+        log: log.clone(),
+        _connection: provider.get::<Connection>(),
+    }
+}
+
+fn telemetry(log: &Log) -> impl Fn(Arc<ServiceProvider>) -> Telemetry + Send + Sync {
+    let log = log.clone();
+    move |_| Telemetry { log: log.clone() }
+}
+
+fn container(log: &Log) -> ServiceContainer {
+    let mut container = ServiceContainer::new();
+    container.register_disposable(ServiceLifetime::Scoped, connection(log));
+    container.register_disposable(ServiceLifetime::Transient, transaction(log));
+    container.register_disposable(ServiceLifetime::Singleton, telemetry(log));
+    container
+}
+
+fn entries(log: &Log) -> Vec<String> {
+    log.lock().unwrap().clone()
+}
+
+#[get("/disposal/transaction")]
+async fn open_transaction(
+    #[service] first: Arc<Connection>,
+    #[service] second: Arc<Connection>,
+    #[service] _transaction: Arc<Transaction>,
+) -> String {
+    format!("same connection: {}", Arc::ptr_eq(&first, &second))
+}
+
+struct Ledger {
+    _connection: Arc<Connection>,
+}
+
+struct ImportJob;
+
+impl BackgroundJob for ImportJob {
+    fn execute(&self, ctx: JobContext) -> JobResult {
+        ctx.services().get::<Transaction>();
+        JobResult::Success
+    }
+}
+
+#[test]
+fn scope_disposes_in_reverse_creation_order() {
+    let log = Log::default();
+    let provider = container(&log).build();
+    let scope = provider.create_scope();
+
+    scope.resolve::<Telemetry>().expect("telemetry");
+    scope.resolve::<Transaction>().expect("transaction");
+    let runtime = Runtime::new().expect("runtime");
+    runtime.block_on(scope.dispose());
+    runtime.block_on(scope.dispose());
+
+    assert_eq!(
+        entries(&log),
+        vec!["transaction closed", "connection returned"]
+    );
+
+    runtime.block_on(provider.dispose());
+    assert_eq!(entries(&log).last().unwrap(), "telemetry flushed");
+}
+
+#[test]
+fn clones_share_their_scope() {
+    let log = Log::default();
+    let scope = container(&log).build().create_scope();
+    let clone = scope.provider().clone();
+
+    let first = scope.resolve::<Connection>().expect("connection");
+    let second = clone.resolve::<Connection>().expect("connection");
+
+    assert!(Arc::ptr_eq(&first, &second));
+}
+
+#[test]
+fn each_request_owns_a_disposed_scope() {
+    let log = Log::default();
+    let mut builder = AppBuilder::new();
+    builder
+        .register_disposable(ServiceLifetime::Scoped, connection(&log))
+        .register_disposable(ServiceLifetime::Transient, transaction(&log));
+    let app = builder.build();
+    let runtime = Runtime::new().expect("runtime");
+
+    for _ in 0..2 {
+        let response = runtime
+            .block_on(app.handle_http_request(HttpRequest::new("GET", "/disposal/transaction")));
+        assert_eq!(
+            response.body(),
+            &ResponseBody::Text("same connection: true".to_string())
+        );
+    }
+
+    assert_eq!(
+        entries(&log),
+        vec![
+            "transaction closed",
+            "connection returned",
+            "transaction closed",
+            "connection returned",
+        ]
+    );
+}
+
+#[test]
+fn singletons_resolved_from_a_scope_outlive_it() {
+    let log = Log::default();
+    let mut container = ServiceContainer::new();
+    container.register_disposable(ServiceLifetime::Transient, connection(&log));
+    container.register_singleton(|provider| Ledger {
+        _connection: provider.get::<Connection>(),
+    });
+    let provider = container.build();
+    let runtime = Runtime::new().expect("runtime");
+
+    let scope = provider.create_scope();
+    let ledger = scope.resolve::<Ledger>().expect("ledger");
+    runtime.block_on(scope.dispose());
+    assert!(entries(&log).is_empty());
+
+    let again = provider.create_scope().resolve::<Ledger>().expect("ledger");
+    assert!(Arc::ptr_eq(&ledger, &again));
+
+    runtime.block_on(provider.dispose());
+    assert_eq!(entries(&log), vec!["connection returned"]);
+}
+
+#[tokio::test]
+async fn each_job_execution_owns_a_disposed_scope() {
+    let log = Log::default();
+    let queue = InMemoryJobQueue::new(Arc::new(container(&log).build()));
+
+    queue.enqueue(Box::new(ImportJob));
+    queue.enqueue(Box::new(ImportJob));
+    queue.run_all().await;
+
+    assert_eq!(
+        entries(&log),
+        vec![
+            "transaction closed",
+            "connection returned",
+            "transaction closed",
+            "connection returned",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn job_scope_is_disposed_before_run_next_returns() {
+    let log = Log::default();
+    let queue = InMemoryJobQueue::new(Arc::new(container(&log).build()));
+
+    queue.enqueue(Box::new(ImportJob));
+    queue.run_next().await.expect("job ran");
+
+    assert_eq!(
+        entries(&log),
+        vec!["transaction closed", "connection returned"]
+    );
+}
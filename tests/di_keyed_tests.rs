@@ -0,0 +1,111 @@
+use std::sync::Arc;
+
+use nimble_web::app::builder::AppBuilder;
+use nimble_web::di::ServiceContainer;
+use nimble_web::http::request::HttpRequest;
+use nimble_web::http::response_body::ResponseBody;
+use nimble_web::{get, injectable};
+use tokio::runtime::Runtime;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Pool {
+    url: String,
+}
+
+fn pool(url: &str) -> Pool {
+    Pool {
+        url: url.to_string(),
+    }
+}
+
+#[injectable]
+struct ReportStore {
+    #[inject(key = "reporting")]
+    pool: Arc<Pool>,
+}
+
+#[get("/keyed/pools")]
+async fn pools(
+    #[service] primary: Arc<Pool>,
+    #[service("reporting")] reporting: Arc<Pool>,
+    #[service("archive")] archive: Option<Arc<Pool>>,
+) -> String {
+    format!(
+        "{} {} {}",
+        primary.url,
+        reporting.url,
+        archive.map_or("none".to_string(), |pool| pool.url.clone())
+    )
+}
+
+#[test]
+fn keyed_registrations_are_independent_of_default() {
+    let mut container = ServiceContainer::new();
+    container.register_instance(pool("primary"));
+    container.register_keyed_instance("reporting", pool("reporting"));
+
+    assert!(container.has_keyed_registration::<Pool>("reporting"));
+    assert!(!container.has_keyed_registration::<Pool>("archive"));
+
+    let provider = container.build();
+    assert_eq!(provider.get::<Pool>().url, "primary");
+    assert_eq!(provider.get_keyed::<Pool>("reporting").url, "reporting");
+    assert!(provider.resolve_keyed::<Pool>("archive").is_none());
+    assert_eq!(provider.resolve_all::<Pool>().len(), 1);
+}
+
+#[test]
+fn keyed_registrations_honour_lifetimes() {
+    let mut container = ServiceContainer::new();
+    container.register_keyed_singleton("single", |_| pool("single"));
+    container.register_keyed_scoped("scoped", |_| pool("scoped"));
+    container.register_keyed_transient("transient", |_| pool("transient"));
+
+    let provider = container.build();
+    let scope_a = provider.create_scope();
+    let scope_b = provider.create_scope();
+
+    let same = |key: &str| {
+        let first = scope_a.resolve_keyed::<Pool>(key).expect("first");
+        let second = scope_a.resolve_keyed::<Pool>(key).expect("second");
+        let other = scope_b.resolve_keyed::<Pool>(key).expect("other");
+        (Arc::ptr_eq(&first, &second), Arc::ptr_eq(&first, &other))
+    };
+
+    assert_eq!(same("single"), (true, true));
+    assert_eq!(same("scoped"), (true, false));
+    assert_eq!(same("transient"), (false, false));
+}
+
+#[test]
+fn keyed_factories_can_resolve_default_service() {
+    let mut container = ServiceContainer::new();
+    container.register_instance(pool("primary"));
+    container.register_keyed_singleton("replica", |provider| {
+        let primary = provider.get::<Pool>();
+        pool(&format!("{}-replica", primary.url))
+    });
+
+    let provider = container.build();
+
+    assert_eq!(provider.get_keyed::<Pool>("replica").url, "primary-replica");
+}
+
+#[test]
+fn handlers_and_injectables_receive_keyed_services() {
+    let mut builder = AppBuilder::new();
+    builder.use_injectables().register_instance(pool("primary"));
+    builder.register_keyed_singleton("reporting", |_| pool("reporting"));
+    let app = builder.build();
+
+    let store = app.services().resolve::<ReportStore>().expect("store");
+    assert_eq!(store.pool.url, "reporting");
+
+    let response = Runtime::new()
+        .expect("runtime")
+        .block_on(app.handle_http_request(HttpRequest::new("GET", "/keyed/pools")));
+    assert_eq!(
+        response.body(),
+        &ResponseBody::Text("primary reporting none".to_string())
+    );
+}
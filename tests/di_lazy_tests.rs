@@ -0,0 +1,170 @@
+use std::any::type_name;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use nimble_web::app::builder::AppBuilder;
+use nimble_web::di::injectable::Dependency;
+use nimble_web::di::{DependencyIssue, Factory, Lazy, ServiceContainer, ServiceLifetime};
+use nimble_web::injectable;
+
+static SESSIONS: AtomicUsize = AtomicUsize::new(0);
+
+struct Index {
+    built: usize,
+}
+
+struct Session {
+    id: usize,
+}
+
+struct Token {
+    id: usize,
+}
+
+#[injectable(lifetime = "singleton")]
+struct SearchService {
+    index: Arc<Lazy<Index>>,
+    sessions: Arc<Factory<Session>>,
+}
+
+#[test]
+fn lazy_resolves_on_first_use_only() {
+    let built = Arc::new(AtomicUsize::new(0));
+    let counter = built.clone();
+    let mut container = ServiceContainer::new();
+    container.register_singleton(move |_| Index {
+        built: counter.fetch_add(1, Ordering::SeqCst) + 1,
+    });
+    let provider = container.build();
+
+    let lazy = provider.get::<Lazy<Index>>();
+    assert!(!lazy.is_created());
+    assert_eq!(built.load(Ordering::SeqCst), 0);
+
+    assert_eq!(lazy.get().built, 1);
+    assert!(Arc::ptr_eq(&lazy.get(), &provider.get::<Index>()));
+    assert_eq!(built.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn factory_creates_per_call_whatever_the_lifetime() {
+    let tokens = Arc::new(AtomicUsize::new(0));
+    let built = Arc::new(AtomicUsize::new(0));
+    let counter = built.clone();
+    let mut container = ServiceContainer::new();
+    container.register_transient(move |_| Token {
+        id: tokens.fetch_add(1, Ordering::SeqCst),
+    });
+    container.register_scoped(|_| Session {
+        id: SESSIONS.fetch_add(1, Ordering::SeqCst),
+    });
+    container.register_singleton(move |_| Index {
+        built: counter.fetch_add(1, Ordering::SeqCst) + 1,
+    });
+    let provider = container.build();
+    let scope = provider.create_scope();
+
+    let factory = scope.resolve::<Factory<Token>>().expect("token factory");
+    assert_ne!(factory.create().id, factory.create().id);
+
+    let sessions = provider.get::<Factory<Session>>();
+    let cached = scope.resolve::<Session>().expect("session");
+    let first = sessions.create_in(&scope);
+    assert_ne!(first.id, cached.id);
+    assert_ne!(first.id, sessions.create_in(&scope).id);
+    assert_eq!(scope.resolve::<Session>().expect("session").id, cached.id);
+
+    let indexes = provider.get::<Factory<Index>>();
+    assert_eq!(provider.get::<Index>().built, 1);
+    assert_eq!(indexes.create().built, 2);
+    assert_eq!(indexes.create().built, 3);
+    assert_eq!(provider.get::<Index>().built, 1);
+}
+
+struct Reporter {
+    #[allow(dead_code)]
+    session: Arc<Lazy<Session>>,
+}
+
+#[test]
+fn singletons_cannot_capture_scoped_services_through_lazy() {
+    let mut container = ServiceContainer::new();
+    container.register_scoped(|_| Session {
+        id: SESSIONS.fetch_add(1, Ordering::SeqCst),
+    });
+    container.register_with_dependencies(
+        ServiceLifetime::Singleton,
+        vec![Dependency::required::<Lazy<Session>>("session")],
+        |provider| Reporter {
+            session: provider.get::<Lazy<Session>>(),
+        },
+    );
+
+    let error = match container.build_validated() {
+        Ok(_) => panic!("singleton captures a scoped session"),
+        Err(error) => error,
+    };
+
+    assert_eq!(
+        error.issues,
+        vec![DependencyIssue::Captive {
+            service: type_name::<Reporter>().to_string(),
+            path: vec![
+                type_name::<Lazy<Session>>().to_string(),
+                type_name::<Session>().to_string(),
+            ],
+        }]
+    );
+}
+
+#[test]
+fn lazy_breaks_dependency_cycles() {
+    let mut container = ServiceContainer::new();
+    container.register_with_dependencies(
+        ServiceLifetime::Singleton,
+        vec![Dependency::required::<Lazy<Token>>("token")],
+        |_| Index { built: 1 },
+    );
+    container.register_with_dependencies(
+        ServiceLifetime::Transient,
+        vec![Dependency::required::<Index>("index")],
+        |provider| Token {
+            id: provider.get::<Index>().built,
+        },
+    );
+
+    let provider = container.build_validated().expect("lazy breaks the cycle");
+
+    assert_eq!(provider.get::<Token>().id, 1);
+}
+
+#[test]
+fn injectables_receive_lazy_and_factory_wrappers() {
+    let mut builder = AppBuilder::new();
+    builder
+        .use_injectables()
+        .register_singleton(|_| Index { built: 1 })
+        .register_scoped(|_| Session {
+            id: SESSIONS.fetch_add(1, Ordering::SeqCst),
+        });
+    let app = builder.build();
+    let search = app.services().get::<SearchService>();
+
+    assert!(!search.index.is_created());
+    assert_eq!(search.index.get().built, 1);
+
+    let request_a = app.services().create_scope();
+    let request_b = app.services().create_scope();
+    let a = search.sessions.create_in(&request_a);
+    let b = search.sessions.create_in(&request_b);
+    assert_ne!(a.id, b.id);
+    assert_ne!(search.sessions.create_in(&request_a).id, a.id);
+}
+
+#[test]
+#[should_panic(expected = "Service `Index` is not registered")]
+fn lazy_reports_missing_service_on_use() {
+    let lazy = Lazy::<Index>::new(ServiceContainer::new().build());
+
+    lazy.get();
+}
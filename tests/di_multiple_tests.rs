@@ -0,0 +1,100 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use nimble_web::di::{ServiceContainer, ServiceLifetime};
+
+trait HealthCheck: Send + Sync {
+    fn name(&self) -> String;
+}
+
+struct Database;
+
+impl HealthCheck for Database {
+    fn name(&self) -> String {
+        "database".to_string()
+    }
+}
+
+struct Cache {
+    id: usize,
+}
+
+impl HealthCheck for Cache {
+    fn name(&self) -> String {
+        format!("cache-{}", self.id)
+    }
+}
+
+fn names(checks: &[Arc<Arc<dyn HealthCheck>>]) -> Vec<String> {
+    checks.iter().map(|check| check.name()).collect()
+}
+
+#[test]
+fn resolve_all_returns_registrations_in_order() {
+    let mut container = ServiceContainer::new();
+    container.register_singleton::<Arc<dyn HealthCheck>, _>(|_| Arc::new(Database));
+    container.register_transient::<Arc<dyn HealthCheck>, _>(|_| Arc::new(Cache { id: 1 }));
+    container.register_scoped::<Arc<dyn HealthCheck>, _>(|_| Arc::new(Cache { id: 2 }));
+
+    let provider = container.build();
+    let checks = provider.resolve_all::<Arc<dyn HealthCheck>>();
+
+    assert_eq!(names(&checks), vec!["database", "cache-1", "cache-2"]);
+    let last = provider
+        .resolve::<Arc<dyn HealthCheck>>()
+        .expect("last registration");
+    assert_eq!(last.name(), "cache-2");
+}
+
+#[test]
+fn resolve_all_is_empty_when_nothing_registered() {
+    let provider = ServiceContainer::new().build();
+
+    assert!(provider.resolve_all::<Arc<dyn HealthCheck>>().is_empty());
+}
+
+#[test]
+fn each_registration_keeps_its_own_lifetime() {
+    let created = Arc::new(AtomicUsize::new(0));
+    let mut container = ServiceContainer::new();
+    let counter = created.clone();
+    container.register_singleton(move |_| Cache {
+        id: counter.fetch_add(1, Ordering::SeqCst),
+    });
+    let counter = created.clone();
+    container.register_scoped(move |_| Cache {
+        id: 100 + counter.fetch_add(1, Ordering::SeqCst),
+    });
+
+    let provider = container.build();
+    let scope_a = provider.create_scope();
+    let scope_b = provider.create_scope();
+    let a1 = scope_a.resolve_all::<Cache>();
+    let a2 = scope_a.resolve_all::<Cache>();
+    let b = scope_b.resolve_all::<Cache>();
+
+    assert!(Arc::ptr_eq(&a1[0], &a2[0]));
+    assert!(Arc::ptr_eq(&a1[0], &b[0]));
+    assert!(Arc::ptr_eq(&a1[1], &a2[1]));
+    assert!(!Arc::ptr_eq(&a1[1], &b[1]));
+    assert!(Arc::ptr_eq(
+        &a1[1],
+        &scope_a.resolve::<Cache>().expect("scoped cache")
+    ));
+    assert_eq!(created.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn try_add_keeps_first_registration() {
+    let mut container = ServiceContainer::new();
+
+    assert!(container.try_add_singleton(|_| Cache { id: 1 }));
+    assert!(!container.try_add_singleton(|_| Cache { id: 2 }));
+    assert!(!container.try_add(ServiceLifetime::Transient, |_| Cache { id: 3 }));
+    assert!(container.try_add_scoped(|_| Database));
+    assert_eq!(container.registration_count::<Cache>(), 1);
+
+    let provider = container.build();
+    assert_eq!(provider.resolve::<Cache>().expect("cache").id, 1);
+    assert_eq!(provider.resolve_all::<Cache>().len(), 1);
+}
@@ -0,0 +1,133 @@
+use nimble_web::app::builder::AppBuilder;
+use nimble_web::di::injectable::{Dependency, MissingDependency};
+use nimble_web::di::{DependencyIssue, ServiceContainer, ServiceLifetime};
+
+struct Pool;
+struct Session;
+struct Orders;
+struct Invoices;
+struct Mailer;
+
+fn name<T>() -> String {
+    std::any::type_name::<T>().to_string()
+}
+
+#[test]
+fn valid_graph_builds() {
+    let mut container = ServiceContainer::new();
+    container.register_singleton(|_| Pool);
+    container.register_with_dependencies(
+        ServiceLifetime::Scoped,
+        vec![
+            Dependency::required::<Pool>("pool"),
+            Dependency::optional::<Mailer>("mailer"),
+        ],
+        |_| Session,
+    );
+    container.register_with_dependencies(
+        ServiceLifetime::Transient,
+        vec![Dependency::required::<Session>("session")],
+        |_| Orders,
+    );
+
+    let provider = container.build_validated().expect("valid graph");
+
+    assert!(provider.create_scope().resolve::<Orders>().is_some());
+}
+
+#[test]
+fn reports_missing_dependencies() {
+    let mut container = ServiceContainer::new();
+    container.register_with_dependencies(
+        ServiceLifetime::Scoped,
+        vec![
+            Dependency::required::<Pool>("pool"),
+            Dependency::required::<Pool>("reporting").with_key("reporting"),
+        ],
+        |_| Session,
+    );
+
+    let error = container.validate().expect_err("missing pool");
+
+    assert_eq!(
+        error.issues,
+        vec![
+            DependencyIssue::Missing(MissingDependency::new::<Session, Pool>("pool")),
+            DependencyIssue::Missing(MissingDependency::new::<Session, Pool>("reporting")),
+        ]
+    );
+}
+
+#[test]
+fn reports_cycles_once() {
+    let mut container = ServiceContainer::new();
+    container.register_with_dependencies(
+        ServiceLifetime::Transient,
+        vec![Dependency::required::<Invoices>("invoices")],
+        |_| Orders,
+    );
+    container.register_with_dependencies(
+        ServiceLifetime::Transient,
+        vec![Dependency::required::<Orders>("orders")],
+        |_| Invoices,
+    );
+
+    let error = container.validate().expect_err("cycle");
+
+    assert_eq!(
+        error.issues,
+        vec![DependencyIssue::Cycle(vec![
+            name::<Invoices>(),
+            name::<Orders>(),
+            name::<Invoices>(),
+        ])]
+    );
+}
+
+#[test]
+fn reports_captive_dependencies_through_transients() {
+    let mut container = ServiceContainer::new();
+    container.register_scoped(|_| Session);
+    container.register_with_dependencies(
+        ServiceLifetime::Transient,
+        vec![Dependency::required::<Session>("session")],
+        |_| Orders,
+    );
+    container.register_with_dependencies(
+        ServiceLifetime::Singleton,
+        vec![Dependency::required::<Session>("session")],
+        |_| Mailer,
+    );
+    container.register_with_dependencies(
+        ServiceLifetime::Singleton,
+        vec![Dependency::required::<Orders>("orders")],
+        |_| Invoices,
+    );
+
+    let error = container.validate().expect_err("captive");
+
+    assert_eq!(
+        error.to_string(),
+        format!(
+            "Service container validation failed:\n  \
+             - captive dependency: singleton `{invoices}` depends on scoped `{session}` through `{orders}`\n  \
+             - captive dependency: singleton `{mailer}` depends on scoped `{session}`",
+            invoices = name::<Invoices>(),
+            mailer = name::<Mailer>(),
+            orders = name::<Orders>(),
+            session = name::<Session>(),
+        )
+    );
+}
+
+#[test]
+#[should_panic(expected = "Service container validation failed:\n  - dependency cycle:")]
+fn app_builder_rejects_invalid_graphs() {
+    let mut builder = AppBuilder::new();
+    builder.register_with_dependencies(
+        ServiceLifetime::Singleton,
+        vec![Dependency::required::<Pool>("pool")],
+        |_| Pool,
+    );
+    builder.build();
+}
@@ -0,0 +1,121 @@
+use nimble_web::app::builder::AppBuilder;
+use nimble_web::data::memory_repository::MemoryRepository;
+use nimble_web::data::repository::Repository;
+use nimble_web::entity::entity::Entity;
+use nimble_web::entity::operation::EntityOperation;
+use nimble_web::http::request::HttpRequest;
+use serde::{Deserialize, Serialize};
+use tokio::runtime::Runtime;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Entity)]
+struct Photo {
+    #[entity(id)]
+    id: i64,
+    title: String,
+}
+
+#[derive(Debug, Clone, Entity)]
+#[entity(name = "person", plural = "people")]
+struct Person {
+    #[entity(id)]
+    key: String,
+}
+
+#[derive(Debug, Clone, Entity)]
+struct PhotoAlbum {
+    #[entity(id)]
+    album_id: u32,
+}
+
+#[derive(Debug, Clone, Entity)]
+struct HTTPLog {
+    #[entity(id)]
+    id: u64,
+}
+
+#[derive(Debug, Clone, Entity)]
+struct UserID {
+    #[entity(id)]
+    id: u64,
+}
+
+#[derive(Debug, Clone, Entity)]
+struct XMLHttpRequest2FA {
+    #[entity(id)]
+    id: u64,
+}
+
+#[derive(Debug, Clone, Entity)]
+struct Tag(#[entity(id)] String, #[allow(dead_code)] u32);
+
+#[derive(Debug, Clone, Entity)]
+struct Labeled<T: Send + Sync + Clone + 'static> {
+    #[entity(id)]
+    id: T,
+}
+
+#[test]
+fn derive_uses_marked_id_field() {
+    let photo = Photo {
+        id: 7,
+        title: "Sunset".to_string(),
+    };
+    let tag = Tag("sky".to_string(), 1);
+    let labeled = Labeled { id: 'x' };
+
+    assert_eq!(photo.id(), &7);
+    assert_eq!(tag.id(), "sky");
+    assert_eq!(labeled.id(), &'x');
+}
+
+#[test]
+fn derive_defaults_name_to_snake_case_type_name() {
+    assert_eq!(Photo::name(), "photo");
+    assert_eq!(Photo::plural_name(), "photos");
+    assert_eq!(PhotoAlbum::name(), "photo_album");
+    assert_eq!(PhotoAlbum::plural_name(), "photo_albums");
+    assert_eq!(PhotoAlbum { album_id: 3 }.id(), &3);
+}
+
+#[test]
+fn derive_treats_capital_runs_as_one_word() {
+    assert_eq!(HTTPLog::name(), "http_log");
+    assert_eq!(HTTPLog { id: 1 }.id(), &1);
+    assert_eq!(UserID::name(), "user_id");
+    assert_eq!(UserID { id: 2 }.id(), &2);
+    assert_eq!(XMLHttpRequest2FA::name(), "xml_http_request2_fa");
+    assert_eq!(XMLHttpRequest2FA { id: 3 }.id(), &3);
+}
+
+#[test]
+fn derive_honours_name_and_plural_overrides() {
+    let person = Person {
+        key: "ann".to_string(),
+    };
+
+    assert_eq!(Person::name(), "person");
+    assert_eq!(Person::plural_name(), "people");
+    assert_eq!(person.id(), "ann");
+}
+
+#[test]
+fn derived_entity_exposes_crud_routes() {
+    let repository = MemoryRepository::<Photo>::new();
+    repository.seed(vec![Photo {
+        id: 1,
+        title: "Dawn".to_string(),
+    }]);
+
+    let mut builder = AppBuilder::new();
+    builder.register_singleton::<Repository<Photo>, _>(move |_| {
+        Repository::new(Box::new(repository.clone()))
+    });
+    builder.use_entity_with_operations::<Photo>(&[EntityOperation::Get]);
+    let app = builder.build();
+
+    let response = Runtime::new()
+        .expect("runtime")
+        .block_on(app.handle_http_request(HttpRequest::new("GET", "/api/photos/1")));
+
+    assert_eq!(response.status(), 200);
+}
@@ -0,0 +1,174 @@
+use nimble_web::app::application::Application;
+use nimble_web::app::builder::AppBuilder;
+use nimble_web::controller::route::HttpRoute;
+use nimble_web::http::context::HttpContext;
+use nimble_web::http::request::HttpRequest;
+use nimble_web::http::request_body::RequestBody;
+use nimble_web::http::response::HttpResponse;
+use nimble_web::http::response_body::ResponseBody;
+use nimble_web::pipeline::pipeline::PipelineError;
+use nimble_web::result::Json;
+use nimble_web::security::policy::Policy;
+use nimble_web::{get, post};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+#[derive(Clone)]
+struct Greeter {
+    greeting: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct NewPhoto {
+    title: String,
+}
+
+#[get("/fn/photos/{id}")]
+async fn get_photo(id: i64, verbose: Option<bool>) -> String {
+    format!("photo {} verbose={}", id, verbose.unwrap_or(false))
+}
+
+#[get("/fn/greet/{name}", name = "fn-greet", policy = Policy::Authenticated)]
+async fn greet(name: String, #[service] greeter: Arc<Greeter>) -> String {
+    format!("{}, {}", greeter.greeting, name)
+}
+
+#[post("/fn/albums/{album}/photos")]
+async fn create_photo(
+    #[param("album")] album_id: u32,
+    #[query("notify")] send_notification: bool,
+    #[body] photo: NewPhoto,
+) -> Json<String> {
+    Json(format!(
+        "{} in {} notify={}",
+        photo.title, album_id, send_notification
+    ))
+}
+
+#[get("/fn/context")]
+fn read_context(context: &HttpContext) -> Result<String, PipelineError> {
+    Ok(format!("path={}", context.request().path()))
+}
+
+#[get("/fn/optional-service")]
+async fn optional_service(#[service] greeter: Option<Arc<Greeter>>) -> &'static str {
+    if greeter.is_some() {
+        "present"
+    } else {
+        "absent"
+    }
+}
+
+fn app() -> Application {
+    let mut builder = AppBuilder::new();
+    builder.register_instance(Greeter {
+        greeting: "Hello".to_string(),
+    });
+    builder.build()
+}
+
+fn send(app: &Application, request: HttpRequest) -> HttpResponse {
+    Runtime::new()
+        .expect("runtime")
+        .block_on(app.handle_http_request(request))
+}
+
+fn body(response: &HttpResponse) -> &str {
+    match response.body() {
+        ResponseBody::Text(text) => text,
+        other => panic!("unexpected body {:?}", other),
+    }
+}
+
+#[test]
+fn function_attribute_generates_route() {
+    let route = GreetHandler::endpoint();
+
+    assert_eq!(route.route.method(), "GET");
+    assert_eq!(route.route.path(), "/fn/greet/{name}");
+    assert_eq!(route.endpoint.metadata().name(), Some("fn-greet"));
+    assert_eq!(GreetHandler::PARAMS, &["name"]);
+    assert_eq!(CreatePhotoHandler::PARAMS, &["album"]);
+    assert_eq!(
+        route.endpoint.metadata().policy(),
+        Some(&Policy::Authenticated)
+    );
+}
+
+#[test]
+fn binds_route_and_optional_query_parameters() {
+    let app = app();
+
+    let response = send(&app, HttpRequest::new("GET", "/fn/photos/42"));
+    assert_eq!(body(&response), "photo 42 verbose=false");
+
+    let mut request = HttpRequest::new("GET", "/fn/photos/42");
+    request.set_query(Some("verbose=true".to_string()));
+    assert_eq!(body(&send(&app, request)), "photo 42 verbose=true");
+}
+
+#[test]
+fn invalid_parameters_return_bad_request() {
+    let app = app();
+
+    let response = send(&app, HttpRequest::new("GET", "/fn/photos/abc"));
+    assert_eq!(response.status(), 400);
+    assert!(body(&response).contains("route parameter `id`"));
+
+    let mut request = HttpRequest::new("POST", "/fn/albums/3/photos");
+    request.set_body(RequestBody::Text(r#"{"title":"Dawn"}"#.to_string()));
+    let response = send(&app, request);
+    assert_eq!(response.status(), 400);
+    assert!(body(&response).contains("query parameter `notify`"));
+}
+
+#[test]
+fn binds_renamed_parameters_and_json_body() {
+    let app = app();
+    let mut request = HttpRequest::new("POST", "/fn/albums/3/photos");
+    request.set_query(Some("notify=true".to_string()));
+    request.set_body(RequestBody::Text(r#"{"title":"Dawn"}"#.to_string()));
+
+    let response = send(&app, request);
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(body(&response), r#""Dawn in 3 notify=true""#);
+}
+
+#[test]
+fn binds_services_and_context() {
+    let mut builder = AppBuilder::new();
+    builder.register_instance(Greeter {
+        greeting: "Hi".to_string(),
+    });
+    let app = builder.build();
+
+    let endpoint = GreetHandler::route().with_policy(Policy::Custom("none".to_string()));
+    assert_eq!(
+        endpoint.build().endpoint.metadata().policy(),
+        Some(&Policy::Custom("none".to_string()))
+    );
+
+    let response = send(&app, HttpRequest::new("GET", "/fn/greet/Ann"));
+    assert_eq!(body(&response), "Hi, Ann");
+
+    let response = send(&app, HttpRequest::new("GET", "/fn/context"));
+    assert_eq!(body(&response), "path=/fn/context");
+
+    let response = send(&app, HttpRequest::new("GET", "/fn/optional-service"));
+    assert_eq!(body(&response), "present");
+    let response = send(
+        &AppBuilder::new().build(),
+        HttpRequest::new("GET", "/fn/optional-service"),
+    );
+    assert_eq!(body(&response), "absent");
+}
+
+#[test]
+fn generated_function_remains_callable() {
+    let value = Runtime::new()
+        .expect("runtime")
+        .block_on(get_photo(1, Some(true)));
+    assert_eq!(value, "photo 1 verbose=true");
+}
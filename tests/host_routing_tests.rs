@@ -0,0 +1,161 @@
+use async_trait::async_trait;
+use nimble_web::app::application::Application;
+use nimble_web::app::builder::AppBuilder;
+use nimble_web::endpoint::http_handler::HttpHandler;
+use nimble_web::endpoint::registry::EndpointRegistry;
+use nimble_web::endpoint::route::EndpointRoute;
+use nimble_web::http::context::HttpContext;
+use nimble_web::http::request::HttpRequest;
+use nimble_web::http::response::HttpResponse;
+use nimble_web::http::response_body::ResponseBody;
+use nimble_web::pipeline::pipeline::PipelineError;
+use nimble_web::result::into_response::ResponseValue;
+use nimble_web::routing::default_router::DefaultRouter;
+use nimble_web::routing::route::Route;
+use nimble_web::routing::router::Router;
+use tokio::runtime::Runtime;
+
+struct NameHandler(&'static str);
+
+#[async_trait]
+impl HttpHandler for NameHandler {
+    async fn invoke(&self, context: &mut HttpContext) -> Result<ResponseValue, PipelineError> {
+        let tenant = context
+            .route()
+            .and_then(|route| route.params().get("tenant").cloned())
+            .unwrap_or_default();
+        Ok(ResponseValue::new(format!("{}{}", self.0, tenant)))
+    }
+}
+
+fn request(host: Option<&str>, path: &str) -> HttpRequest {
+    let mut request = HttpRequest::new("GET", path);
+    if let Some(host) = host {
+        request.headers_mut().insert("host", host);
+    }
+    request
+}
+
+fn send(app: &Application, request: HttpRequest) -> HttpResponse {
+    Runtime::new()
+        .expect("runtime")
+        .block_on(app.handle_http_request(request))
+}
+
+fn body(response: &HttpResponse) -> &str {
+    match response.body() {
+        ResponseBody::Text(text) => text,
+        other => panic!("unexpected body {:?}", other),
+    }
+}
+
+#[test]
+fn host_constrained_route_matches_only_its_host() {
+    let mut router = DefaultRouter::new();
+    router.add_route(Route::new("GET", "/dashboard").with_host("admin.example.com"));
+
+    assert!(router
+        .match_request(&request(Some("Admin.Example.com:8080"), "/dashboard"))
+        .is_some());
+    assert!(router
+        .match_request(&request(Some("api.example.com"), "/dashboard"))
+        .is_none());
+    assert!(router.match_request(&request(None, "/dashboard")).is_none());
+}
+
+#[test]
+fn host_parameter_is_exposed_as_route_param() {
+    let mut router = DefaultRouter::new();
+    router.add_route(Route::new("GET", "/orders/{id}").with_host("{tenant}.example.com"));
+
+    let matched = router
+        .match_request(&request(Some("acme.example.com"), "/orders/7"))
+        .expect("route match");
+
+    assert_eq!(
+        matched.params().get("tenant").map(String::as_str),
+        Some("acme")
+    );
+    assert_eq!(matched.params().get("id").map(String::as_str), Some("7"));
+    assert!(router
+        .match_request(&request(Some("example.com"), "/orders/7"))
+        .is_none());
+}
+
+#[test]
+fn host_specific_route_wins_over_host_agnostic_route() {
+    let mut router = DefaultRouter::new();
+    router.add_route(Route::new("GET", "/"));
+    router.add_route(Route::new("GET", "/").with_host("{tenant}.example.com"));
+    router.add_route(Route::new("GET", "/").with_host("admin.example.com"));
+
+    let matched = router
+        .match_request(&request(Some("admin.example.com"), "/"))
+        .expect("route match");
+    assert_eq!(matched.route().host(), Some("admin.example.com"));
+
+    let matched = router
+        .match_request(&request(Some("acme.example.com"), "/"))
+        .expect("route match");
+    assert_eq!(matched.route().host(), Some("{tenant}.example.com"));
+
+    let matched = router
+        .match_request(&request(Some("localhost"), "/"))
+        .expect("route match");
+    assert_eq!(matched.route().host(), None);
+}
+
+#[test]
+fn route_groups_apply_host_and_prefix() {
+    let mut builder = AppBuilder::new();
+    builder
+        .route_host("admin.example.com")
+        .get("/", NameHandler("admin-home"))
+        .get("/users", NameHandler("admin-users"));
+    builder
+        .route_group("/api")
+        .with_host("{tenant}.example.com")
+        .get("/orders", NameHandler("orders:"))
+        .route(EndpointRoute::get("/invoices", NameHandler("invoices:")).with_name("invoices"));
+    builder.route_get("/", NameHandler("public-home"));
+    let app = builder.build();
+
+    let response = send(&app, request(Some("admin.example.com"), "/users"));
+    assert_eq!(body(&response), "admin-users");
+
+    let response = send(&app, request(Some("admin.example.com"), "/"));
+    assert_eq!(body(&response), "admin-home");
+
+    let response = send(&app, request(Some("www.example.org"), "/"));
+    assert_eq!(body(&response), "public-home");
+
+    let response = send(&app, request(Some("acme.example.com"), "/api/orders"));
+    assert_eq!(body(&response), "orders:acme");
+
+    let response = send(&app, request(Some("acme.example.com"), "/api/invoices"));
+    assert_eq!(body(&response), "invoices:acme");
+
+    let response = send(&app, request(Some("www.example.org"), "/users"));
+    assert_eq!(response.status(), 404);
+}
+
+#[test]
+fn same_pattern_on_different_hosts_does_not_conflict() {
+    let mut registry = EndpointRegistry::new();
+    registry
+        .host("admin.example.com")
+        .get("/", NameHandler("admin"));
+    registry
+        .host("api.example.com")
+        .get("/", NameHandler("api"));
+    registry.get("/", NameHandler("public"));
+    assert!(registry.validate().is_ok());
+
+    registry
+        .host("{tenant}.example.com")
+        .get("/", NameHandler("a"));
+    registry
+        .host("{org}.example.com")
+        .get("/", NameHandler("b"));
+    assert!(registry.validate().is_err());
+}
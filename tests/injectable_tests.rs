@@ -0,0 +1,154 @@
+use nimble_web::app::builder::AppBuilder;
+use nimble_web::di::injectable::{Dependency, Injectable, MissingDependency};
+use nimble_web::di::{DependencyIssue, ServiceContainer, ServiceLifetime};
+use nimble_web::injectable;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[derive(Clone)]
+struct Settings {
+    title: String,
+}
+
+struct AuditLog;
+
+#[injectable(lifetime = "singleton")]
+struct Clock {
+    #[inject(default)]
+    ticks: AtomicUsize,
+}
+
+#[injectable(lifetime = "scoped")]
+struct PhotoRepository {
+    clock: Arc<Clock>,
+    audit: Option<Arc<AuditLog>>,
+}
+
+#[injectable]
+struct ReportService {
+    photos: Arc<PhotoRepository>,
+    settings: Arc<Settings>,
+}
+
+impl ReportService {
+    fn describe(&self) -> String {
+        let tick = self.photos.clock.ticks.fetch_add(1, Ordering::SeqCst);
+        format!("{} #{}", self.settings.title, tick)
+    }
+}
+
+fn builder() -> AppBuilder {
+    let mut builder = AppBuilder::new();
+    builder.use_injectables().register_instance(Settings {
+        title: "Photos".to_string(),
+    });
+    builder
+}
+
+#[test]
+fn build_auto_registers_injectables() {
+    let app = builder().build();
+    let services = app.services();
+
+    let report = services.resolve::<ReportService>().expect("report service");
+
+    assert_eq!(report.describe(), "Photos #0");
+    assert_eq!(report.describe(), "Photos #1");
+    assert!(report.photos.audit.is_none());
+}
+
+#[test]
+fn injectables_respect_declared_lifetimes() {
+    let app = builder().build();
+    let first = app.services().create_scope();
+    let second = app.services().create_scope();
+
+    let a = first.resolve::<PhotoRepository>().expect("repository");
+    let b = first.resolve::<PhotoRepository>().expect("repository");
+    let c = second.resolve::<PhotoRepository>().expect("repository");
+
+    assert!(Arc::ptr_eq(&a, &b));
+    assert!(!Arc::ptr_eq(&a, &c));
+    assert!(Arc::ptr_eq(&a.clock, &c.clock));
+
+    let first_report = first.resolve::<ReportService>().expect("report");
+    let second_report = first.resolve::<ReportService>().expect("report");
+    assert!(!Arc::ptr_eq(&first_report, &second_report));
+    assert!(Arc::ptr_eq(&first_report.photos, &second_report.photos));
+}
+
+#[test]
+fn explicit_registrations_take_precedence() {
+    let mut builder = builder();
+    builder.register_singleton::<Clock, _>(|_| Clock {
+        ticks: AtomicUsize::new(40),
+    });
+    builder.register_singleton::<AuditLog, _>(|_| AuditLog);
+    let app = builder.build();
+
+    let report = app.services().resolve::<ReportService>().expect("report");
+
+    assert_eq!(report.describe(), "Photos #40");
+    assert!(report.photos.audit.is_some());
+}
+
+#[test]
+#[should_panic(
+    expected = "dependency `injectable_tests::Settings` for field `settings` is not registered"
+)]
+fn build_reports_missing_dependencies() {
+    let mut builder = AppBuilder::new();
+    builder.use_injectables();
+    builder.build();
+}
+
+#[test]
+fn failed_constructors_resolve_to_none() {
+    let mut container = ServiceContainer::new();
+    container.register_injectable::<ReportService>();
+
+    let report = container.validate().expect_err("dependencies are missing");
+    assert!(report
+        .issues
+        .contains(&DependencyIssue::Missing(MissingDependency::new::<
+            ReportService,
+            PhotoRepository,
+        >("photos"))));
+
+    let provider = container.build();
+    assert!(provider.resolve::<ReportService>().is_none());
+    assert!(provider.create_scope().resolve::<ReportService>().is_none());
+}
+
+#[test]
+fn injectables_are_not_registered_unless_enabled() {
+    let app = AppBuilder::new().build();
+
+    assert!(app.services().resolve::<ReportService>().is_none());
+    assert!(app.services().resolve::<Clock>().is_none());
+}
+
+#[test]
+fn injectable_exposes_dependencies_and_errors() {
+    assert_eq!(
+        <PhotoRepository as Injectable>::LIFETIME,
+        ServiceLifetime::Scoped
+    );
+    assert_eq!(
+        PhotoRepository::dependencies(),
+        vec![
+            Dependency::required::<Clock>("clock"),
+            Dependency::optional::<AuditLog>("audit"),
+        ]
+    );
+
+    let provider = ServiceContainer::new().build();
+    let error = match ReportService::create(&provider) {
+        Ok(_) => panic!("photos are not registered"),
+        Err(error) => error,
+    };
+    assert_eq!(
+        error,
+        MissingDependency::new::<ReportService, PhotoRepository>("photos")
+    );
+}
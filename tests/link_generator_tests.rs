@@ -0,0 +1,163 @@
+use async_trait::async_trait;
+use nimble_web::app::builder::AppBuilder;
+use nimble_web::endpoint::http_handler::HttpHandler;
+use nimble_web::endpoint::registry::EndpointRegistry;
+use nimble_web::endpoint::route::EndpointRoute;
+use nimble_web::http::context::HttpContext;
+use nimble_web::http::request::HttpRequest;
+use nimble_web::http::response_body::ResponseBody;
+use nimble_web::pipeline::pipeline::PipelineError;
+use nimble_web::result::into_response::ResponseValue;
+use nimble_web::routing::link_generator::{LinkError, LinkGenerator};
+use tokio::runtime::Runtime;
+
+struct NoopHandler;
+
+#[async_trait]
+impl HttpHandler for NoopHandler {
+    async fn invoke(&self, _context: &mut HttpContext) -> Result<ResponseValue, PipelineError> {
+        Ok(ResponseValue::empty())
+    }
+}
+
+struct LocationHandler;
+
+#[async_trait]
+impl HttpHandler for LocationHandler {
+    async fn invoke(&self, context: &mut HttpContext) -> Result<ResponseValue, PipelineError> {
+        let links = context.service::<LinkGenerator>()?;
+        let url = links
+            .url(context.request(), "photo", &[("id", 42)])
+            .map_err(|err| PipelineError::message(&err.to_string()))?;
+        Ok(ResponseValue::new(url))
+    }
+}
+
+fn generator() -> LinkGenerator {
+    let mut generator = LinkGenerator::new();
+    generator.add("photo", "/photos/{id}");
+    generator.add("album-photo", "/albums/{albumId}/photos/{id}");
+    generator.add("home", "/");
+    generator
+}
+
+#[test]
+fn builds_path_from_route_parameters() {
+    let links = generator();
+
+    let path = links
+        .path("album-photo", &[("albumId", "7"), ("id", "42")])
+        .expect("path");
+
+    assert_eq!(path, "/albums/7/photos/42");
+    assert_eq!(links.path::<&str, &str>("home", &[]).expect("root"), "/");
+}
+
+#[test]
+fn escapes_parameters_and_appends_query() {
+    let links = generator();
+
+    let path = links
+        .path_with_query(
+            "photo",
+            &[("id", "a b/c")],
+            &[("size", "large & wide"), ("page", "2")],
+        )
+        .expect("path");
+
+    assert_eq!(path, "/photos/a%20b%2Fc?size=large%20%26%20wide&page=2");
+}
+
+#[test]
+fn reports_missing_and_unexpected_parameters() {
+    let links = generator();
+
+    assert_eq!(
+        links.path::<&str, &str>("photo", &[]),
+        Err(LinkError::MissingParameter {
+            route: "photo".to_string(),
+            parameter: "id".to_string(),
+        })
+    );
+    assert_eq!(
+        links.path("photo", &[("id", "1"), ("slug", "x")]),
+        Err(LinkError::UnexpectedParameter {
+            route: "photo".to_string(),
+            parameter: "slug".to_string(),
+        })
+    );
+    assert_eq!(
+        links.path::<&str, &str>("missing", &[]),
+        Err(LinkError::UnknownRoute("missing".to_string()))
+    );
+}
+
+#[test]
+fn builds_absolute_url_from_request_host() {
+    let links = generator();
+    let mut request = HttpRequest::new("GET", "/");
+    request.headers_mut().insert("host", "api.example.com");
+
+    assert_eq!(
+        links.url(&request, "photo", &[("id", 5)]).expect("url"),
+        "http://api.example.com/photos/5"
+    );
+
+    request.headers_mut().insert("x-forwarded-proto", "https");
+    request
+        .headers_mut()
+        .insert("x-forwarded-host", "public.example.com");
+    assert_eq!(
+        links.url(&request, "photo", &[("id", 5)]).expect("url"),
+        "https://public.example.com/photos/5"
+    );
+
+    let bare = HttpRequest::new("GET", "/");
+    assert_eq!(
+        links.url(&bare, "photo", &[("id", 5)]),
+        Err(LinkError::MissingHost)
+    );
+}
+
+#[test]
+fn collects_named_routes_from_registry() {
+    let mut registry = EndpointRegistry::new();
+    registry.add_endpoint_route(
+        EndpointRoute::get("/photos/{id}", NoopHandler)
+            .with_name("photo")
+            .build(),
+    );
+    registry.add_endpoint_route(EndpointRoute::get("/photos", NoopHandler).build());
+
+    let links = LinkGenerator::from_registry(&registry);
+
+    assert_eq!(links.route_pattern("photo"), Some("/photos/{id}"));
+    assert_eq!(
+        links.path("photo", &[("id", 3)]).expect("path"),
+        "/photos/3"
+    );
+}
+
+#[test]
+fn link_generator_is_resolvable_from_context() {
+    let mut builder = AppBuilder::new();
+    builder.routes().add_endpoint_route(
+        EndpointRoute::get("/photos/{id}", NoopHandler)
+            .with_name("photo")
+            .build(),
+    );
+    builder.route_post("/photos", LocationHandler);
+    let app = builder.build();
+
+    let mut request = HttpRequest::new("POST", "/photos");
+    request.headers_mut().insert("host", "localhost:8080");
+    let response = Runtime::new()
+        .expect("runtime")
+        .block_on(app.handle_http_request(request));
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.body(),
+        &ResponseBody::Text("http://localhost:8080/photos/42".to_string())
+    );
+}
@@ -0,0 +1,131 @@
+#![cfg(feature = "mongodb")]
+
+use mongodb::bson::oid::ObjectId;
+use mongodb::bson::{doc, Bson};
+use nimble_web::data::mongo::index::MongoIndex;
+use nimble_web::data::mongo::MongoEntity;
+use nimble_web::data::provider::DataError;
+use nimble_web::entity::entity::Entity;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Entity, MongoEntity)]
+#[index(keys("owner", "-created_at"), name = "owner_recent")]
+struct Photo {
+    #[entity(id)]
+    #[serde(rename = "_id")]
+    id: ObjectId,
+    #[index(unique)]
+    slug: String,
+    owner: String,
+    created_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Entity, MongoEntity)]
+#[serde(rename_all = "camelCase")]
+struct Account {
+    #[entity(id)]
+    account_id: String,
+    #[index(descending)]
+    last_login: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Entity, MongoEntity)]
+struct Counter {
+    #[entity(id)]
+    id: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Entity, MongoEntity)]
+struct Session {
+    #[entity(id)]
+    id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ShardKey {
+    shard: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Entity, MongoEntity)]
+struct Shard {
+    #[entity(id)]
+    id: ShardKey,
+}
+
+#[test]
+fn derive_uses_serialized_id_field_name() {
+    assert_eq!(Photo::id_field(), "_id");
+    assert_eq!(Account::id_field(), "accountId");
+    assert_eq!(Counter::id_field(), "id");
+}
+
+#[test]
+fn derive_converts_ids_to_bson() {
+    let object_id = ObjectId::new();
+    let photo = Photo {
+        id: object_id,
+        slug: "sunset".to_string(),
+        owner: "ann".to_string(),
+        created_at: 1,
+    };
+    let session_id = Uuid::new_v4();
+
+    assert_eq!(
+        Photo::id_bson(photo.id()).expect("object id bson"),
+        Bson::ObjectId(object_id)
+    );
+    assert_eq!(
+        Account::id_bson(&"acc-1".to_string()).expect("string bson"),
+        Bson::String("acc-1".to_string())
+    );
+    assert_eq!(Counter::id_bson(&7).expect("i32 bson"), Bson::Int32(7));
+    assert_eq!(
+        Session::id_bson(&session_id).expect("uuid bson"),
+        Bson::String(session_id.to_string())
+    );
+
+    let document = mongodb::bson::to_document(&photo).expect("document");
+    assert_eq!(
+        document.get(Photo::id_field()),
+        Some(&Bson::ObjectId(object_id))
+    );
+}
+
+#[test]
+fn derive_reports_ids_that_do_not_fit_in_bson() {
+    let result = Shard::id_bson(&ShardKey { shard: u64::MAX });
+
+    assert!(matches!(result, Err(DataError::InvalidQuery(_))));
+}
+
+#[test]
+fn derive_declares_indexes() {
+    assert_eq!(
+        Photo::indexes(),
+        vec![
+            MongoIndex::new().ascending("slug").unique(),
+            MongoIndex::new()
+                .ascending("owner")
+                .descending("created_at")
+                .with_name("owner_recent"),
+        ]
+    );
+    assert_eq!(
+        Account::indexes(),
+        vec![MongoIndex::new().descending("lastLogin")]
+    );
+    assert!(Counter::indexes().is_empty());
+}
+
+#[test]
+fn index_builds_keys_document() {
+    let index = MongoIndex::new()
+        .ascending("owner")
+        .descending("created_at")
+        .unique();
+
+    assert_eq!(index.keys_document(), doc! { "owner": 1, "created_at": -1 });
+    assert!(index.is_unique());
+    assert_eq!(index.to_model().keys, doc! { "owner": 1, "created_at": -1 });
+}
@@ -6,7 +6,7 @@ use mongodb::Client;
 
 use nimble_web::data::mongo::{MongoEntity, MongoProvider};
 use nimble_web::data::paging::PageRequest;
-use nimble_web::data::provider::{DataError, DataProvider};
+use nimble_web::data::provider::{DataError, DataProvider, DataResult};
 use nimble_web::data::query::{Filter, FilterOperator, GroupBy, Join, JoinOn, Query, Value};
 use nimble_web::entity::entity::Entity;
 use std::time::Duration;
@@ -37,8 +37,8 @@ impl MongoEntity for Photo {
         "id"
     }
 
-    fn id_bson(id: &Self::Id) -> Bson {
-        Bson::Int64(*id)
+    fn id_bson(id: &Self::Id) -> DataResult<Bson> {
+        Ok(Bson::Int64(*id))
     }
 }
 
@@ -68,8 +68,8 @@ impl MongoEntity for Album {
         "id"
     }
 
-    fn id_bson(id: &Self::Id) -> Bson {
-        Bson::Int64(*id)
+    fn id_bson(id: &Self::Id) -> DataResult<Bson> {
+        Ok(Bson::Int64(*id))
     }
 }
 
@@ -0,0 +1,332 @@
+use async_trait::async_trait;
+use nimble_web::app::application::Application;
+use nimble_web::app::builder::AppBuilder;
+use nimble_web::data::memory_repository::MemoryRepository;
+use nimble_web::data::provider::DataProvider;
+use nimble_web::data::repository::Repository;
+use nimble_web::entity::entity::Entity;
+use nimble_web::entity::hooks::{EntityHooks, RequestContext};
+use nimble_web::entity::operation::EntityOperation;
+use nimble_web::entity::patch::{
+    apply_json_patch, apply_merge_patch, PatchDocument, PatchError, PatchOperation,
+};
+use nimble_web::http::request::HttpRequest;
+use nimble_web::http::request_body::RequestBody;
+use nimble_web::http::response::HttpResponse;
+use nimble_web::http::response_body::ResponseBody;
+use nimble_web::result::{HttpError, Result as HttpResult};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Photo {
+    id: i64,
+    title: String,
+    tags: Vec<String>,
+    description: Option<String>,
+}
+
+impl Entity for Photo {
+    type Id = i64;
+
+    fn id(&self) -> &Self::Id {
+        &self.id
+    }
+
+    fn name() -> &'static str {
+        "photo"
+    }
+
+    fn plural_name() -> String {
+        "photos".to_string()
+    }
+}
+
+#[derive(Clone, Default)]
+struct CountingHooks {
+    before: Arc<AtomicUsize>,
+    after: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl EntityHooks<Photo> for CountingHooks {
+    async fn before_update(&self, _context: &RequestContext, entity: &mut Photo) -> HttpResult<()> {
+        if entity.title.is_empty() {
+            return Err(HttpError::new(400, "title is required"));
+        }
+        self.before.fetch_add(1, Ordering::SeqCst);
+        entity.title = entity.title.trim().to_string();
+        Ok(())
+    }
+
+    async fn after_update(&self, _context: &RequestContext, _entity: &Photo) -> HttpResult<()> {
+        self.after.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+fn photo() -> Photo {
+    Photo {
+        id: 1,
+        title: "Sunset".to_string(),
+        tags: vec!["sky".to_string()],
+        description: Some("Evening".to_string()),
+    }
+}
+
+fn build_app(hooks: CountingHooks) -> (Application, MemoryRepository<Photo>) {
+    let repository = MemoryRepository::<Photo>::new();
+    repository.seed(vec![photo()]);
+
+    let mut builder = AppBuilder::new();
+    let shared = repository.clone();
+    builder.register_singleton::<Repository<Photo>, _>(move |_| {
+        Repository::new(Box::new(shared.clone()))
+    });
+    builder.use_entity_with_hooks::<Photo, _>(hooks, &[EntityOperation::Patch]);
+    (builder.build(), repository)
+}
+
+fn send_patch(app: &Application, content_type: &str, body: Value) -> HttpResponse {
+    let mut request = HttpRequest::new("PATCH", "/api/photos/1");
+    request.headers_mut().insert("content-type", content_type);
+    request.set_body(RequestBody::Text(body.to_string()));
+    Runtime::new()
+        .expect("runtime")
+        .block_on(app.handle_http_request(request))
+}
+
+fn stored(repository: &MemoryRepository<Photo>) -> Photo {
+    Runtime::new()
+        .expect("runtime")
+        .block_on(repository.get(&1))
+        .expect("get")
+        .expect("photo")
+}
+
+#[test]
+fn merge_patch_follows_rfc_7396() {
+    let mut target = json!({"a": "b", "c": {"d": "e", "f": "g"}, "list": [1, 2]});
+    apply_merge_patch(
+        &mut target,
+        &json!({"a": "z", "c": {"f": null}, "list": [3], "n": {"x": 1}}),
+    );
+
+    assert_eq!(
+        target,
+        json!({"a": "z", "c": {"d": "e"}, "list": [3], "n": {"x": 1}})
+    );
+}
+
+#[test]
+fn json_patch_applies_all_operations() {
+    let mut target = json!({"title": "a", "tags": ["x", "y"], "meta": {"a~b": 1, "c/d": 2}});
+    let operations: Vec<PatchOperation> = serde_json::from_value(json!([
+        {"op": "test", "path": "/title", "value": "a"},
+        {"op": "replace", "path": "/title", "value": "b"},
+        {"op": "add", "path": "/tags/-", "value": "z"},
+        {"op": "add", "path": "/tags/0", "value": "first"},
+        {"op": "remove", "path": "/tags/1"},
+        {"op": "copy", "from": "/meta/a~0b", "path": "/copied"},
+        {"op": "move", "from": "/meta/c~1d", "path": "/moved"}
+    ]))
+    .expect("operations");
+
+    apply_json_patch(&mut target, &operations).expect("patch");
+
+    assert_eq!(
+        target,
+        json!({
+            "title": "b",
+            "tags": ["first", "y", "z"],
+            "meta": {"a~b": 1},
+            "copied": 1,
+            "moved": 2
+        })
+    );
+}
+
+#[test]
+fn failed_json_patch_leaves_document_untouched() {
+    let original = json!({"title": "a"});
+    let mut target = original.clone();
+    let operations: Vec<PatchOperation> = serde_json::from_value(json!([
+        {"op": "replace", "path": "/title", "value": "b"},
+        {"op": "test", "path": "/title", "value": "c"}
+    ]))
+    .expect("operations");
+
+    assert_eq!(
+        apply_json_patch(&mut target, &operations),
+        Err(PatchError::TestFailed("/title".to_string()))
+    );
+    assert_eq!(target, original);
+
+    let operations: Vec<PatchOperation> =
+        serde_json::from_value(json!([{"op": "remove", "path": "/missing"}])).expect("operations");
+    assert_eq!(
+        apply_json_patch(&mut target, &operations),
+        Err(PatchError::PathNotFound("/missing".to_string()))
+    );
+}
+
+#[test]
+fn patch_document_is_selected_by_content_type() {
+    let merge = PatchDocument::from_body(
+        Some("application/merge-patch+json; charset=utf-8"),
+        json!({"a": 1}),
+    )
+    .expect("merge");
+    assert!(matches!(merge, PatchDocument::Merge(_)));
+
+    let json_patch = PatchDocument::from_body(
+        Some("application/json-patch+json"),
+        json!([{"op": "remove", "path": "/a"}]),
+    )
+    .expect("json patch");
+    assert!(matches!(json_patch, PatchDocument::Json(_)));
+
+    assert!(PatchDocument::from_body(Some("application/json-patch+json"), json!({})).is_err());
+}
+
+#[test]
+fn entity_patch_applies_merge_patch_and_runs_update_hooks() {
+    let hooks = CountingHooks::default();
+    let (app, repository) = build_app(hooks.clone());
+
+    let response = send_patch(
+        &app,
+        "application/merge-patch+json",
+        json!({"title": "  Dawn ", "description": null}),
+    );
+
+    assert_eq!(response.status(), 200);
+    let expected = Photo {
+        title: "Dawn".to_string(),
+        description: None,
+        ..photo()
+    };
+    assert_eq!(stored(&repository), expected);
+    let ResponseBody::Text(body) = response.body() else {
+        panic!("unexpected body {:?}", response.body());
+    };
+    assert_eq!(serde_json::from_str::<Photo>(body).expect("json"), expected);
+    assert_eq!(hooks.before.load(Ordering::SeqCst), 1);
+    assert_eq!(hooks.after.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn entity_patch_applies_json_patch() {
+    let hooks = CountingHooks::default();
+    let (app, repository) = build_app(hooks);
+
+    let response = send_patch(
+        &app,
+        "application/json-patch+json",
+        json!([
+            {"op": "add", "path": "/tags/-", "value": "clouds"},
+            {"op": "replace", "path": "/title", "value": "Dusk"}
+        ]),
+    );
+
+    assert_eq!(response.status(), 200);
+    let updated = stored(&repository);
+    assert_eq!(updated.title, "Dusk");
+    assert_eq!(updated.tags, vec!["sky", "clouds"]);
+}
+
+#[test]
+fn entity_patch_does_not_update_when_hook_rejects() {
+    let hooks = CountingHooks::default();
+    let (app, repository) = build_app(hooks.clone());
+
+    let response = send_patch(&app, "application/merge-patch+json", json!({"title": ""}));
+
+    assert_ne!(response.status(), 200);
+    assert_eq!(stored(&repository), photo());
+    assert_eq!(hooks.after.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn entity_patch_cannot_change_the_id() {
+    let hooks = CountingHooks::default();
+    let (app, repository) = build_app(hooks.clone());
+
+    let response = send_patch(
+        &app,
+        "application/merge-patch+json",
+        json!({"id": 2, "title": "Moved"}),
+    );
+
+    assert_eq!(response.status(), 422);
+    assert_eq!(stored(&repository), photo());
+    assert_eq!(hooks.before.load(Ordering::SeqCst), 0);
+
+    let response = send_patch(
+        &app,
+        "application/json-patch+json",
+        json!([{"op": "replace", "path": "/id", "value": 2}]),
+    );
+    assert_eq!(response.status(), 422);
+    assert_eq!(stored(&repository), photo());
+}
+
+#[test]
+fn entity_patch_reports_client_errors() {
+    let hooks = CountingHooks::default();
+    let (app, repository) = build_app(hooks.clone());
+
+    let mut request = HttpRequest::new("PATCH", "/api/photos/1");
+    request
+        .headers_mut()
+        .insert("content-type", "application/merge-patch+json");
+    request.set_body(RequestBody::Text("{not json".to_string()));
+    let response = Runtime::new()
+        .expect("runtime")
+        .block_on(app.handle_http_request(request));
+    assert_eq!(response.status(), 400);
+
+    let response = send_patch(&app, "application/json-patch+json", json!({"op": "add"}));
+    assert_eq!(response.status(), 400);
+
+    let response = send_patch(
+        &app,
+        "application/json-patch+json",
+        json!([{"op": "remove", "path": "/missing"}]),
+    );
+    assert_eq!(response.status(), 400);
+
+    let response = send_patch(
+        &app,
+        "application/json-patch+json",
+        json!([
+            {"op": "test", "path": "/title", "value": "Sunrise"},
+            {"op": "replace", "path": "/title", "value": "Dawn"}
+        ]),
+    );
+    assert_eq!(response.status(), 409);
+
+    let response = send_patch(&app, "application/merge-patch+json", json!({"title": 42}));
+    assert_eq!(response.status(), 422);
+
+    assert_eq!(stored(&repository), photo());
+    assert_eq!(hooks.before.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn use_entity_does_not_expose_patch_by_default() {
+    assert!(!EntityOperation::all().contains(&EntityOperation::Patch));
+
+    let mut builder = AppBuilder::new();
+    builder.use_entity::<Photo>();
+    let app = builder.build();
+
+    let request = HttpRequest::new("PATCH", "/api/photos/1");
+    let response = Runtime::new()
+        .expect("runtime")
+        .block_on(app.handle_http_request(request));
+    assert_eq!(response.status(), 404);
+}
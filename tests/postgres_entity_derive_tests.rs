@@ -0,0 +1,164 @@
+#![cfg(feature = "postgres")]
+
+use chrono::{DateTime, NaiveDate, Utc};
+use nimble_web::data::postgres::migration::MigrationBuilder;
+use nimble_web::data::postgres::PostgresEntity;
+use nimble_web::data::query::Value;
+use nimble_web::data::schema::ColumnType;
+use nimble_web::entity::entity::Entity;
+use sqlx::postgres::PgRow;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Entity, PostgresEntity, FromRow)]
+struct Account {
+    #[entity(id)]
+    id: Uuid,
+    #[column(type = "varchar(120)", unique)]
+    email: String,
+    #[sqlx(rename = "display_name")]
+    name: Option<String>,
+    #[column(default = "0")]
+    logins: i32,
+    active: bool,
+    score: Option<f64>,
+    #[column(skip_update, default = "now()")]
+    created_at: DateTime<Utc>,
+    birthday: Option<NaiveDate>,
+    tags: Vec<String>,
+    #[sqlx(skip)]
+    #[allow(dead_code)]
+    session: Option<std::sync::Arc<String>>,
+}
+
+#[derive(Debug, Clone, Entity, PostgresEntity)]
+#[entity(name = "counter")]
+struct Counter {
+    #[entity(id)]
+    #[column(name = "counter_id")]
+    id: i64,
+    value: u16,
+}
+
+// sqlx cannot decode `u16`, so this row mapping stays hand-written.
+impl<'r> FromRow<'r, PgRow> for Counter {
+    fn from_row(_row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        Err(sqlx::Error::RowNotFound)
+    }
+}
+
+#[derive(Debug, Clone, Entity, PostgresEntity, FromRow)]
+#[entity(name = "audit_entry")]
+#[sqlx(rename_all = "camelCase")]
+struct AuditEntry {
+    #[entity(id)]
+    entry_id: i64,
+    #[sqlx(rename = "actor")]
+    #[column(name = "actor")]
+    changed_by: String,
+    #[sqlx(default)]
+    changed_fields: Vec<String>,
+}
+
+fn account() -> Account {
+    Account {
+        id: Uuid::nil(),
+        email: "ann@example.com".to_string(),
+        name: None,
+        logins: 3,
+        active: true,
+        score: Some(1.5),
+        created_at: DateTime::<Utc>::UNIX_EPOCH,
+        birthday: NaiveDate::from_ymd_opt(1990, 1, 2),
+        tags: vec!["admin".to_string()],
+        session: None,
+    }
+}
+
+#[test]
+fn derive_generates_insert_and_update_columns() {
+    assert_eq!(Account::id_column(), "id");
+    assert_eq!(
+        Account::insert_columns(),
+        &[
+            "id",
+            "email",
+            "display_name",
+            "logins",
+            "active",
+            "score",
+            "created_at",
+            "birthday",
+            "tags"
+        ]
+    );
+    assert_eq!(
+        Account::update_columns(),
+        &[
+            "email",
+            "display_name",
+            "logins",
+            "active",
+            "score",
+            "birthday",
+            "tags"
+        ]
+    );
+    assert_eq!(Counter::id_column(), "counter_id");
+    assert_eq!(Counter::update_columns(), &["value"]);
+    assert_eq!(AuditEntry::id_column(), "entryId");
+    assert_eq!(
+        AuditEntry::insert_columns(),
+        &["entryId", "actor", "changedFields"]
+    );
+}
+
+#[test]
+fn derive_maps_field_values() {
+    let account = account();
+
+    assert_eq!(
+        account.insert_values(),
+        vec![
+            Value::Uuid(Uuid::nil()),
+            Value::String("ann@example.com".to_string()),
+            Value::Null,
+            Value::Int(3),
+            Value::Bool(true),
+            Value::Float(1.5),
+            Value::DateTime(DateTime::<Utc>::UNIX_EPOCH),
+            Value::Date(NaiveDate::from_ymd_opt(1990, 1, 2).unwrap()),
+            Value::StringArray(vec!["admin".to_string()]),
+        ]
+    );
+    assert_eq!(
+        account.update_values().len(),
+        Account::update_columns().len()
+    );
+    assert_eq!(Account::id_value(account.id()), Value::Uuid(Uuid::nil()));
+    assert_eq!(
+        Counter { id: 4, value: 9 }.insert_values(),
+        vec![Value::Int(4), Value::U16(9)]
+    );
+}
+
+#[test]
+fn derive_generates_table_columns() {
+    let columns = Account::table_columns();
+
+    assert_eq!(columns.len(), 9);
+    assert_eq!(columns[0].data_type, ColumnType::Uuid);
+    assert!(columns[0].is_primary_key);
+    assert_eq!(columns[1].data_type, ColumnType::Varchar(120));
+    assert!(columns[1].unique);
+    assert!(!columns[1].is_nullable);
+    assert!(columns[2].is_nullable);
+    assert_eq!(columns[3].data_type, ColumnType::Integer);
+    assert_eq!(columns[3].default, Some("0"));
+    assert_eq!(columns[7].data_type, ColumnType::Custom("DATE"));
+
+    assert_eq!(
+        MigrationBuilder::build_create_table("counters", &Counter::table_columns()),
+        "CREATE TABLE counters (counter_id BIGINT PRIMARY KEY, value INTEGER NOT NULL)"
+    );
+}
@@ -15,13 +15,55 @@ fn redis_module_registers_client_cache_and_lock() {
     let config = Configuration::default();
     RedisModule::register(&mut container, &config);
 
+    assert!(container.has_registration::<RedisClient>());
+    assert!(container.has_registration::<RedisCache>());
+    assert!(container.has_registration::<RedisLock>());
     let services = container.build();
-    assert!(services.resolve::<RedisClient>().is_some());
-    assert!(services.resolve::<RedisCache>().is_some());
-    assert!(services.resolve::<RedisLock>().is_some());
     assert!(services.resolve::<RedisConfig>().is_some());
 }
 
+#[tokio::test]
+async fn unconfigured_redis_does_not_connect_during_initialization() {
+    let mut container = ServiceContainer::new();
+    RedisModule::register(&mut container, &Configuration::default());
+
+    let services = container.build();
+    services
+        .initialize()
+        .await
+        .expect("redis is not configured");
+    assert!(services.resolve::<RedisClient>().is_some());
+}
+
+#[tokio::test]
+async fn redis_module_reports_unreachable_server_during_initialization() {
+    let mut values = HashMap::new();
+    values.insert("redis.url".to_string(), "redis://127.0.0.1:1".to_string());
+    let config = Configuration::from_values(values);
+    let mut container = ServiceContainer::new();
+    RedisModule::register(&mut container, &config);
+
+    let services = container.build();
+    let error = services
+        .initialize()
+        .await
+        .expect_err("redis is unreachable");
+    assert!(error.name.ends_with("RedisClient"), "{}", error.name);
+}
+
+#[tokio::test]
+async fn redis_module_reports_invalid_url_during_initialization() {
+    let mut values = HashMap::new();
+    values.insert("redis.url".to_string(), "not a redis url".to_string());
+    let config = Configuration::from_values(values);
+    let mut container = ServiceContainer::new();
+    RedisModule::register(&mut container, &config);
+
+    let services = container.build();
+    let error = services.initialize().await.expect_err("url is invalid");
+    assert!(error.name.ends_with("RedisClient"), "{}", error.name);
+}
+
 #[test]
 fn redis_module_exposes_configuration_from_provider() {
     let mut values = HashMap::new();
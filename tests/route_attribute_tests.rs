@@ -7,7 +7,7 @@ use nimble_web::http::context::HttpContext;
 use nimble_web::pipeline::pipeline::PipelineError;
 use nimble_web::result::into_response::ResponseValue;
 use nimble_web::security::policy::Policy;
-use nimble_web::{get, post};
+use nimble_web::{get, patch, post, route};
 
 struct TaggedGet;
 
@@ -39,6 +39,36 @@ impl HttpHandler for TaggedGetWithPolicy {
     }
 }
 
+struct NamedGet;
+
+#[async_trait]
+#[get("/attr/named/{id}", name = "attr-named")]
+impl HttpHandler for NamedGet {
+    async fn invoke(&self, _context: &mut HttpContext) -> Result<ResponseValue, PipelineError> {
+        Err(PipelineError::message("not used"))
+    }
+}
+
+struct TaggedPatch;
+
+#[async_trait]
+#[patch("/attr/patch/{id}")]
+impl HttpHandler for TaggedPatch {
+    async fn invoke(&self, _context: &mut HttpContext) -> Result<ResponseValue, PipelineError> {
+        Err(PipelineError::message("not used"))
+    }
+}
+
+struct GenericRoute;
+
+#[async_trait]
+#[route(path = "/attr/generic", method = "options", name = "attr-generic")]
+impl HttpHandler for GenericRoute {
+    async fn invoke(&self, _context: &mut HttpContext) -> Result<ResponseValue, PipelineError> {
+        Err(PipelineError::message("not used"))
+    }
+}
+
 #[test]
 fn get_attribute_generates_route_metadata() {
     let route = TaggedGet::endpoint();
@@ -55,6 +85,27 @@ fn get_attribute_accepts_policy_expression() {
     );
 }
 
+#[test]
+fn get_attribute_accepts_route_name() {
+    let route = NamedGet::endpoint();
+    assert_eq!(route.endpoint.metadata().name(), Some("attr-named"));
+}
+
+#[test]
+fn patch_attribute_generates_patch_route() {
+    let route = TaggedPatch::endpoint();
+    assert_eq!(route.route.method(), "PATCH");
+    assert_eq!(route.route.path(), "/attr/patch/{id}");
+}
+
+#[test]
+fn generic_route_attribute_uses_given_method() {
+    let route = GenericRoute::endpoint();
+    assert_eq!(route.route.method(), "OPTIONS");
+    assert_eq!(route.route.path(), "/attr/generic");
+    assert_eq!(route.endpoint.metadata().name(), Some("attr-generic"));
+}
+
 #[test]
 fn attribute_route_builder_supports_customization() {
     let route = TaggedPost::route()
@@ -94,3 +145,10 @@ fn attribute_routes_do_not_duplicate_existing_routes() {
 
     assert_eq!(get_count, 1);
 }
+
+#[test]
+fn attribute_routes_expose_template_params() {
+    assert_eq!(NamedGet::PARAMS, &["id"]);
+    assert_eq!(TaggedPatch::PARAMS, &["id"]);
+    assert!(TaggedGet::PARAMS.is_empty());
+}
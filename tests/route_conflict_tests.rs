@@ -0,0 +1,164 @@
+use async_trait::async_trait;
+use nimble_web::app::builder::AppBuilder;
+use nimble_web::controller::controller::Controller;
+use nimble_web::endpoint::conflict::{RouteConflictKind, RouteOrigin};
+use nimble_web::endpoint::http_handler::HttpHandler;
+use nimble_web::endpoint::registry::EndpointRegistry;
+use nimble_web::endpoint::route::EndpointRoute;
+use nimble_web::get;
+use nimble_web::http::context::HttpContext;
+use nimble_web::pipeline::pipeline::PipelineError;
+use nimble_web::result::into_response::ResponseValue;
+use nimble_web::routing::versioning::ApiVersion;
+
+struct FirstHandler;
+
+#[async_trait]
+impl HttpHandler for FirstHandler {
+    async fn invoke(&self, _context: &mut HttpContext) -> Result<ResponseValue, PipelineError> {
+        Ok(ResponseValue::empty())
+    }
+}
+
+struct SecondHandler;
+
+#[async_trait]
+impl HttpHandler for SecondHandler {
+    async fn invoke(&self, _context: &mut HttpContext) -> Result<ResponseValue, PipelineError> {
+        Ok(ResponseValue::empty())
+    }
+}
+
+struct AttributeHandler;
+
+#[async_trait]
+#[get("/conflict/attribute")]
+impl HttpHandler for AttributeHandler {
+    async fn invoke(&self, _context: &mut HttpContext) -> Result<ResponseValue, PipelineError> {
+        Ok(ResponseValue::empty())
+    }
+}
+
+struct UsersController;
+
+impl Controller for UsersController {
+    fn routes() -> Vec<EndpointRoute> {
+        vec![EndpointRoute::get("/users/{id}", FirstHandler).build()]
+    }
+}
+
+#[test]
+fn distinct_routes_validate() {
+    let mut registry = EndpointRegistry::new();
+    registry.get("/users", FirstHandler);
+    registry.get("/users/{id}", FirstHandler);
+    registry.post("/users", SecondHandler);
+    registry.get("/users/me", SecondHandler);
+
+    assert!(registry.validate().is_ok());
+}
+
+#[test]
+fn duplicate_method_and_pattern_is_reported_with_both_origins() {
+    let mut registry = EndpointRegistry::new();
+    registry.register::<UsersController>();
+    registry.get("/users/{id}/", SecondHandler);
+
+    let report = registry.validate().expect_err("conflict");
+    let conflict = &report.conflicts()[0];
+
+    assert_eq!(report.conflicts().len(), 1);
+    assert_eq!(conflict.kind, RouteConflictKind::Duplicate);
+    assert_eq!(
+        conflict.first.origin,
+        RouteOrigin::Controller("UsersController")
+    );
+    assert_eq!(conflict.second.origin, RouteOrigin::Manual);
+
+    let message = report.to_string();
+    assert!(message.contains("controller `UsersController`"));
+    assert!(message.contains("manual registration"));
+    assert!(message.contains("SecondHandler"));
+}
+
+#[test]
+fn parameter_patterns_that_shadow_each_other_are_ambiguous() {
+    let mut registry = EndpointRegistry::new();
+    registry.get("/users/{id}", FirstHandler);
+    registry.get("/users/{name}", SecondHandler);
+    registry.get("/teams/{team}/members", FirstHandler);
+    registry.get("/teams/all/{member}", SecondHandler);
+
+    let report = registry.validate().expect_err("conflict");
+    let kinds: Vec<RouteConflictKind> = report.conflicts().iter().map(|c| c.kind).collect();
+
+    assert_eq!(
+        kinds,
+        vec![RouteConflictKind::Ambiguous, RouteConflictKind::Ambiguous]
+    );
+}
+
+#[test]
+fn literal_segments_take_precedence_over_parameters() {
+    let mut registry = EndpointRegistry::new();
+    registry.get("/users/{id}", FirstHandler);
+    registry.get("/users/me", SecondHandler);
+    registry.get("/api/albums/{page}/{pageSize}", FirstHandler);
+    registry.get("/api/albums/{id}/comments", SecondHandler);
+
+    assert!(registry.validate().is_ok());
+}
+
+#[test]
+fn disjoint_api_versions_do_not_conflict() {
+    let mut registry = EndpointRegistry::new();
+    registry.add_endpoint_route(
+        EndpointRoute::get("/orders", FirstHandler)
+            .with_api_versions([ApiVersion::new(1, 0)])
+            .build(),
+    );
+    registry.add_endpoint_route(
+        EndpointRoute::get("/orders", SecondHandler)
+            .with_api_versions([ApiVersion::new(2, 0)])
+            .build(),
+    );
+    assert!(registry.validate().is_ok());
+
+    registry.add_endpoint_route(
+        EndpointRoute::get("/orders", SecondHandler)
+            .with_api_versions([ApiVersion::new(2, 0)])
+            .build(),
+    );
+    assert!(registry.validate().is_err());
+}
+
+#[test]
+fn attribute_route_conflicting_with_manual_registration_is_reported() {
+    let mut builder = AppBuilder::new();
+    builder.route_get("/conflict/attribute", FirstHandler);
+
+    let report = builder.validate().expect_err("conflict");
+    let conflict = &report.conflicts()[0];
+
+    assert_eq!(conflict.first.origin, RouteOrigin::Manual);
+    assert_eq!(conflict.second.origin, RouteOrigin::Attribute);
+}
+
+#[test]
+fn attribute_route_registered_manually_is_not_a_conflict() {
+    let mut builder = AppBuilder::new();
+    builder
+        .routes()
+        .add_endpoint_route(EndpointRoute::get("/conflict/attribute", AttributeHandler).build());
+
+    assert!(builder.validate().is_ok());
+}
+
+#[test]
+#[should_panic(expected = "route conflict(s) detected")]
+fn build_fails_fast_on_conflicts() {
+    let mut builder = AppBuilder::new();
+    builder.route_get("/dup", FirstHandler);
+    builder.route_get("/dup", SecondHandler);
+    builder.build();
+}
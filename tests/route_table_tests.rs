@@ -0,0 +1,109 @@
+use async_trait::async_trait;
+use nimble_web::app::builder::AppBuilder;
+use nimble_web::endpoint::http_handler::HttpHandler;
+use nimble_web::endpoint::route::EndpointRoute;
+use nimble_web::http::context::HttpContext;
+use nimble_web::http::request::HttpRequest;
+use nimble_web::http::response_body::ResponseBody;
+use nimble_web::pipeline::pipeline::PipelineError;
+use nimble_web::result::into_response::ResponseValue;
+use nimble_web::routing::versioning::ApiVersion;
+use nimble_web::security::policy::Policy;
+use nimble_web::validation::ContextValidator;
+use serde_json::Value;
+use tokio::runtime::Runtime;
+
+struct NoopHandler;
+
+#[async_trait]
+impl HttpHandler for NoopHandler {
+    async fn invoke(&self, _context: &mut HttpContext) -> Result<ResponseValue, PipelineError> {
+        Ok(ResponseValue::empty())
+    }
+}
+
+fn builder() -> AppBuilder {
+    let mut builder = AppBuilder::new();
+    builder.routes().add_endpoint_route(
+        EndpointRoute::post("/photos/{id}", NoopHandler)
+            .with_name("update-photo")
+            .with_tags(["photos", "write"])
+            .with_policy(Policy::InRole("admin".to_string()))
+            .validate(ContextValidator::new(|_| Ok(())))
+            .with_api_versions([ApiVersion::new(2, 0)])
+            .build(),
+    );
+    builder.route_get("/photos", NoopHandler);
+    builder
+}
+
+#[test]
+fn application_exposes_route_table() {
+    let app = builder().build();
+    let table = app.route_table();
+
+    assert_eq!(table.len(), 2);
+
+    let route = table.find("POST", "/photos/{id}").expect("route");
+    assert_eq!(route.name.as_deref(), Some("update-photo"));
+    assert_eq!(route.tags, vec!["photos", "write"]);
+    assert_eq!(route.policy.as_deref(), Some("role:admin"));
+    assert_eq!(route.validators, 1);
+    assert_eq!(route.api_versions, vec!["2.0"]);
+    assert_eq!(route.origin, "manual registration");
+
+    let route = table.find("GET", "/photos").expect("route");
+    assert_eq!(route.name, None);
+    assert_eq!(route.policy, None);
+    assert_eq!(route.validators, 0);
+}
+
+#[test]
+fn route_table_serializes_to_json() {
+    let app = builder().build();
+    let json: Value = serde_json::from_str(&app.route_table().to_json()).expect("json");
+
+    let routes = json["routes"].as_array().expect("routes");
+    assert_eq!(routes.len(), 2);
+    assert_eq!(routes[0]["method"], "POST");
+    assert_eq!(routes[0]["pattern"], "/photos/{id}");
+    assert_eq!(routes[0]["validators"], 1);
+    assert!(routes[1].get("api_versions").is_none());
+}
+
+#[test]
+fn diagnostics_endpoint_is_opt_in() {
+    let app = builder().build();
+    let response = Runtime::new()
+        .expect("runtime")
+        .block_on(app.handle_http_request(HttpRequest::new("GET", "/_nimble/routes")));
+    assert_eq!(response.status(), 404);
+}
+
+#[test]
+fn diagnostics_endpoint_returns_route_table() {
+    let mut builder = builder();
+    builder.use_route_diagnostics();
+    let app = builder.build();
+
+    let response = Runtime::new()
+        .expect("runtime")
+        .block_on(app.handle_http_request(HttpRequest::new("GET", "/_nimble/routes")));
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("content-type"),
+        Some("application/json")
+    );
+    let ResponseBody::Text(body) = response.body() else {
+        panic!("unexpected body {:?}", response.body());
+    };
+    let json: Value = serde_json::from_str(body).expect("json");
+    let patterns: Vec<&str> = json["routes"]
+        .as_array()
+        .expect("routes")
+        .iter()
+        .filter_map(|route| route["pattern"].as_str())
+        .collect();
+    assert_eq!(patterns, vec!["/photos/{id}", "/photos", "/_nimble/routes"]);
+}
@@ -0,0 +1,5 @@
+#[test]
+fn invalid_route_templates_fail_to_compile() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/route_*.rs");
+}
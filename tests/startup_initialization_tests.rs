@@ -0,0 +1,123 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use nimble_web::app::application::AppError;
+use nimble_web::app::builder::AppBuilder;
+use nimble_web::di::initializer::InitializeError;
+use nimble_web::di::{ServiceInitializer, ServiceProvider};
+
+struct Pool {
+    url: String,
+}
+
+struct Cache {
+    pool_url: String,
+}
+
+async fn connect(url: &str) -> Result<Pool, String> {
+    tokio::task::yield_now().await;
+    if url.starts_with("postgres://") {
+        Ok(Pool {
+            url: url.to_string(),
+        })
+    } else {
+        Err(format!("unsupported url `{}`", url))
+    }
+}
+
+struct Migrations {
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl ServiceInitializer for Migrations {
+    fn name(&self) -> String {
+        "migrations".to_string()
+    }
+
+    async fn initialize(&self, services: Arc<ServiceProvider>) -> Result<(), InitializeError> {
+        let pool = services.resolve::<Pool>().ok_or("pool missing")?;
+        self.log
+            .lock()
+            .unwrap()
+            .push(format!("migrated {}", pool.url));
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn initialize_builds_async_singletons_and_runs_initializers() {
+    let created = Arc::new(AtomicUsize::new(0));
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let counter = created.clone();
+
+    let mut builder = AppBuilder::new();
+    builder
+        .register_singleton_async(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async { connect("postgres://db").await }
+        })
+        .register_singleton(|provider| Cache {
+            pool_url: provider.get::<Pool>().url.clone(),
+        })
+        .initialize_on_startup::<Cache>()
+        .add_initializer(Migrations { log: log.clone() });
+    let app = builder.build();
+
+    app.initialize().await.expect("startup");
+    app.initialize().await.expect("second startup is a no-op");
+
+    assert_eq!(created.load(Ordering::SeqCst), 1);
+    assert_eq!(app.services().get::<Pool>().url, "postgres://db");
+    assert_eq!(app.services().get::<Cache>().pool_url, "postgres://db");
+    assert_eq!(*log.lock().unwrap(), vec!["migrated postgres://db"]);
+}
+
+#[tokio::test]
+async fn async_factory_errors_fail_startup() {
+    let mut builder = AppBuilder::new();
+    builder
+        .use_address("127.0.0.1:0")
+        .register_singleton_async(|_| async { connect("mysql://db").await });
+    let app = builder.build();
+
+    let result = app
+        .start_with_shutdown(Box::pin(std::future::pending()))
+        .await;
+
+    let Err(AppError::Initialization(error)) = result else {
+        panic!("expected initialization error");
+    };
+    assert!(error.name.ends_with("Pool"));
+    assert_eq!(error.message, "unsupported url `mysql://db`");
+}
+
+#[tokio::test]
+async fn eager_singletons_report_factory_panics() {
+    let mut builder = AppBuilder::new();
+    builder
+        .register_singleton::<Cache, _>(|_| panic!("redis url is invalid"))
+        .initialize_on_startup::<Cache>();
+    let app = builder.build();
+
+    let error = app.initialize().await.expect_err("startup should fail");
+
+    assert!(error
+        .to_string()
+        .starts_with("startup failed: failed to initialize `"));
+    assert!(error.to_string().ends_with("Cache`: redis url is invalid"));
+}
+
+#[tokio::test]
+async fn async_singletons_are_unavailable_before_initialize() {
+    let mut builder = AppBuilder::new();
+    builder.register_singleton_async(|_| async { connect("postgres://db").await });
+    let app = builder.build();
+
+    assert!(app.services().resolve::<Pool>().is_none());
+
+    app.initialize().await.expect("startup");
+
+    assert_eq!(app.services().get::<Pool>().url, "postgres://db");
+}
@@ -0,0 +1,6 @@
+struct Handler;
+
+#[nimble_web::get("/notes/{id}/comments/{id}")]
+impl nimble_web::endpoint::http_handler::HttpHandler for Handler {}
+
+fn main() {}
@@ -0,0 +1,263 @@
+use nimble_web::app::builder::AppBuilder;
+use nimble_web::data::memory_repository::MemoryRepository;
+use nimble_web::data::provider::DataProvider;
+use nimble_web::data::repository::Repository;
+use nimble_web::entity::entity::Entity;
+use nimble_web::entity::operation::EntityOperation;
+use nimble_web::http::request::HttpRequest;
+use nimble_web::http::request_body::RequestBody;
+use nimble_web::http::response::HttpResponse;
+use nimble_web::http::response_body::ResponseBody;
+use nimble_web::validation::{FieldError, ModelValidator, Validate, ValidationError, Validator};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tokio::runtime::Runtime;
+
+fn no_spaces(value: &str) -> Result<(), ValidationError> {
+    if value.contains(' ') {
+        Err(ValidationError::new("must not contain spaces"))
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+struct Address {
+    #[validate(length(min = 2))]
+    city: String,
+    #[validate(regex = r"^\d{5}$")]
+    postal_code: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+struct SignUp {
+    #[validate(length(min = 3, max = 12), custom = "no_spaces")]
+    user_name: String,
+    #[validate(email)]
+    email: String,
+    #[validate(url(message = "homepage must be an absolute URL"))]
+    homepage: Option<String>,
+    #[validate(range(min = 18, max = 130))]
+    age: u32,
+    #[validate(required)]
+    referrer: Option<String>,
+    #[validate(nested)]
+    address: Address,
+    #[validate(nested, length(max = 2))]
+    previous: Vec<Address>,
+}
+
+fn valid() -> SignUp {
+    SignUp {
+        user_name: "ann".to_string(),
+        email: "ann@example.com".to_string(),
+        homepage: Some("https://example.com/ann".to_string()),
+        age: 30,
+        referrer: Some("friend".to_string()),
+        address: Address {
+            city: "Oslo".to_string(),
+            postal_code: "01234".to_string(),
+        },
+        previous: Vec::new(),
+    }
+}
+
+#[test]
+fn valid_model_passes() {
+    assert_eq!(valid().validate(), Ok(()));
+
+    let mut without_homepage = valid();
+    without_homepage.homepage = None;
+    assert_eq!(without_homepage.validate(), Ok(()));
+}
+
+#[test]
+fn all_failures_are_reported() {
+    let model = SignUp {
+        user_name: "a b".to_string(),
+        email: "not-an-email".to_string(),
+        homepage: Some("example.com".to_string()),
+        age: 12,
+        referrer: None,
+        address: Address {
+            city: "X".to_string(),
+            postal_code: "12".to_string(),
+        },
+        previous: vec![
+            valid().address,
+            Address {
+                city: "Bergen".to_string(),
+                postal_code: "abcde".to_string(),
+            },
+            valid().address,
+        ],
+    };
+
+    let error = model.validate().expect_err("invalid model");
+
+    assert_eq!(
+        error.errors(),
+        &[
+            FieldError::new("userName", "must not contain spaces"),
+            FieldError::new("email", "must be a valid email address"),
+            FieldError::new("homepage", "homepage must be an absolute URL"),
+            FieldError::new("age", "must be between 18 and 130"),
+            FieldError::new("referrer", "is required"),
+            FieldError::new("address.city", "length must be at least 2"),
+            FieldError::new("address.postalCode", "has an invalid format"),
+            FieldError::new("previous[1].postalCode", "has an invalid format"),
+            FieldError::new("previous", "length must be at most 2"),
+        ]
+    );
+    assert!(error.message().starts_with("validation failed: userName:"));
+}
+
+#[test]
+fn model_validator_delegates_to_derive() {
+    let validator = ModelValidator::<SignUp>::new();
+    let mut model = valid();
+    model.user_name = "ab".to_string();
+
+    let error = validator.validate(&model).expect_err("too short");
+
+    assert_eq!(
+        error.errors(),
+        &[FieldError::new(
+            "userName",
+            "length must be between 3 and 12"
+        )]
+    );
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Entity, Validate)]
+struct Note {
+    #[entity(id)]
+    id: i64,
+    #[validate(length(min = 1, max = 40))]
+    title: String,
+}
+
+fn post_note(body: serde_json::Value) -> HttpResponse {
+    let mut builder = AppBuilder::new();
+    builder.use_validation();
+    builder.register_singleton::<Repository<Note>, _>(|_| {
+        Repository::new(Box::new(MemoryRepository::<Note>::new()))
+    });
+    builder.use_entity_with_validation::<Note>(&[EntityOperation::Create]);
+    let app = builder.build();
+
+    let mut request = HttpRequest::new("POST", "/api/notes");
+    request.set_body(RequestBody::Text(body.to_string()));
+    Runtime::new()
+        .expect("runtime")
+        .block_on(app.handle_http_request(request))
+}
+
+#[test]
+fn entity_create_body_is_validated() {
+    let response = post_note(json!({ "id": 1, "title": "" }));
+
+    assert_eq!(response.status(), 400);
+    let ResponseBody::Text(text) = response.body() else {
+        panic!("unexpected body {:?}", response.body());
+    };
+    let body: serde_json::Value = serde_json::from_str(text).expect("json body");
+    assert_eq!(
+        body["errors"],
+        json!([{ "field": "title", "message": "length must be between 1 and 40" }])
+    );
+
+    let response = post_note(json!({ "id": 2, "title": "Groceries" }));
+    assert!(response.status() < 300);
+}
+
+fn send_note_without_middleware(method: &str, body: serde_json::Value) -> HttpResponse {
+    let mut builder = AppBuilder::new();
+    builder.register_singleton::<Repository<Note>, _>(|_| {
+        Repository::new(Box::new(MemoryRepository::<Note>::new()))
+    });
+    builder.use_entity_with_validation::<Note>(&[EntityOperation::Create, EntityOperation::Update]);
+    let app = builder.build();
+
+    let mut request = HttpRequest::new(method, "/api/notes");
+    request.set_body(RequestBody::Text(body.to_string()));
+    Runtime::new()
+        .expect("runtime")
+        .block_on(app.handle_http_request(request))
+}
+
+#[test]
+fn entity_create_and_update_validate_without_validation_middleware() {
+    for method in ["POST", "PUT"] {
+        let response = send_note_without_middleware(method, json!({ "id": 1, "title": "" }));
+
+        assert_eq!(response.status(), 400, "{method}");
+        let ResponseBody::Text(text) = response.body() else {
+            panic!("unexpected body {:?}", response.body());
+        };
+        let body: serde_json::Value = serde_json::from_str(text).expect("json body");
+        assert_eq!(
+            body["errors"],
+            json!([{ "field": "title", "message": "length must be between 1 and 40" }])
+        );
+    }
+
+    let response = send_note_without_middleware("POST", json!({ "id": 2, "title": "Groceries" }));
+    assert!(response.status() < 300);
+}
+
+fn patch_note(repository: &MemoryRepository<Note>, body: serde_json::Value) -> HttpResponse {
+    let mut builder = AppBuilder::new();
+    builder.use_validation();
+    let shared = repository.clone();
+    builder.register_singleton::<Repository<Note>, _>(move |_| {
+        Repository::new(Box::new(shared.clone()))
+    });
+    builder.use_entity_with_validation::<Note>(&[EntityOperation::Patch]);
+    let app = builder.build();
+
+    let mut request = HttpRequest::new("PATCH", "/api/notes/1");
+    request
+        .headers_mut()
+        .insert("content-type", "application/merge-patch+json");
+    request.set_body(RequestBody::Text(body.to_string()));
+    Runtime::new()
+        .expect("runtime")
+        .block_on(app.handle_http_request(request))
+}
+
+#[test]
+fn entity_patch_validates_merged_entity() {
+    let repository = MemoryRepository::<Note>::new();
+    repository.seed(vec![Note {
+        id: 1,
+        title: "Groceries".to_string(),
+    }]);
+    let stored = |repository: &MemoryRepository<Note>| {
+        Runtime::new()
+            .expect("runtime")
+            .block_on(repository.get(&1))
+            .expect("get")
+            .expect("note")
+            .title
+    };
+
+    let response = patch_note(&repository, json!({ "title": "" }));
+
+    assert_eq!(response.status(), 400);
+    let ResponseBody::Text(text) = response.body() else {
+        panic!("unexpected body {:?}", response.body());
+    };
+    let body: serde_json::Value = serde_json::from_str(text).expect("json body");
+    assert_eq!(
+        body["errors"],
+        json!([{ "field": "title", "message": "length must be between 1 and 40" }])
+    );
+    assert_eq!(stored(&repository), "Groceries");
+
+    let response = patch_note(&repository, json!({ "title": "Errands" }));
+    assert_eq!(response.status(), 200);
+    assert_eq!(stored(&repository), "Errands");
+}
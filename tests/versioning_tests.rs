@@ -0,0 +1,191 @@
+use async_trait::async_trait;
+use nimble_web::app::application::Application;
+use nimble_web::app::builder::AppBuilder;
+use nimble_web::endpoint::http_handler::HttpHandler;
+use nimble_web::endpoint::route::EndpointRoute;
+use nimble_web::http::context::HttpContext;
+use nimble_web::http::request::HttpRequest;
+use nimble_web::http::response::HttpResponse;
+use nimble_web::http::response_body::ResponseBody;
+use nimble_web::pipeline::pipeline::PipelineError;
+use nimble_web::result::into_response::ResponseValue;
+use nimble_web::routing::versioning::{ApiVersion, ApiVersionReader, ApiVersioning};
+use tokio::runtime::Runtime;
+
+const V1: ApiVersion = ApiVersion::new(1, 0);
+const V2: ApiVersion = ApiVersion::new(2, 0);
+
+struct VersionHandler(&'static str);
+
+#[async_trait]
+impl HttpHandler for VersionHandler {
+    async fn invoke(&self, context: &mut HttpContext) -> Result<ResponseValue, PipelineError> {
+        let version = context
+            .get::<ApiVersion>()
+            .map(ToString::to_string)
+            .unwrap_or_else(|| "none".to_string());
+        Ok(ResponseValue::new(format!("{}:{}", self.0, version)))
+    }
+}
+
+fn build_app(versioning: ApiVersioning, path: &str) -> Application {
+    let mut builder = AppBuilder::new();
+    builder.routes().add_endpoint_route(
+        EndpointRoute::get(path, VersionHandler("users-v1"))
+            .with_api_versions([V1])
+            .build(),
+    );
+    builder.routes().add_endpoint_route(
+        EndpointRoute::get(path, VersionHandler("users-v2"))
+            .with_api_versions([V2])
+            .build(),
+    );
+    builder
+        .routes()
+        .add_endpoint_route(EndpointRoute::get("/health", VersionHandler("health")).build());
+    builder.use_api_versioning(versioning);
+    builder.build()
+}
+
+fn send(app: &Application, request: HttpRequest) -> HttpResponse {
+    Runtime::new()
+        .expect("runtime")
+        .block_on(app.handle_http_request(request))
+}
+
+fn body(response: &HttpResponse) -> &str {
+    match response.body() {
+        ResponseBody::Text(text) => text,
+        other => panic!("unexpected body {:?}", other),
+    }
+}
+
+#[test]
+fn api_version_parses_common_formats() {
+    assert_eq!("2".parse::<ApiVersion>(), Ok(V2));
+    assert_eq!("v1".parse::<ApiVersion>(), Ok(V1));
+    assert_eq!("2.1".parse::<ApiVersion>(), Ok(ApiVersion::new(2, 1)));
+    assert!("two".parse::<ApiVersion>().is_err());
+    assert_eq!(ApiVersion::new(2, 1).to_string(), "2.1");
+}
+
+#[test]
+fn selects_endpoint_by_route_segment() {
+    let app = build_app(
+        ApiVersioning::new().read_from(ApiVersionReader::route_param("version")),
+        "/api/{version}/users",
+    );
+
+    let response = send(&app, HttpRequest::new("GET", "/api/v2/users"));
+    assert_eq!(response.status(), 200);
+    assert_eq!(body(&response), "users-v2:2.0");
+    assert_eq!(
+        response.headers().get("api-supported-versions"),
+        Some("1.0, 2.0")
+    );
+
+    let response = send(&app, HttpRequest::new("GET", "/api/v1/users"));
+    assert_eq!(body(&response), "users-v1:1.0");
+}
+
+#[test]
+fn selects_endpoint_by_query_header_and_media_type() {
+    let app = build_app(
+        ApiVersioning::new()
+            .read_from(ApiVersionReader::query("api-version"))
+            .read_from(ApiVersionReader::header("X-Api-Version"))
+            .read_from(ApiVersionReader::media_type("v")),
+        "/users",
+    );
+
+    let mut request = HttpRequest::new("GET", "/users");
+    request.set_query(Some("api-version=2.0".to_string()));
+    assert_eq!(body(&send(&app, request)), "users-v2:2.0");
+
+    let mut request = HttpRequest::new("GET", "/users");
+    request.headers_mut().insert("x-api-version", "1");
+    assert_eq!(body(&send(&app, request)), "users-v1:1.0");
+
+    let mut request = HttpRequest::new("GET", "/users");
+    request
+        .headers_mut()
+        .insert("accept", "application/json; v=2");
+    assert_eq!(body(&send(&app, request)), "users-v2:2.0");
+}
+
+#[test]
+fn unsupported_or_missing_version_returns_bad_request() {
+    let app = build_app(
+        ApiVersioning::new().read_from(ApiVersionReader::query("api-version")),
+        "/users",
+    );
+
+    let mut request = HttpRequest::new("GET", "/users");
+    request.set_query(Some("api-version=3".to_string()));
+    let response = send(&app, request);
+    assert_eq!(response.status(), 400);
+    assert!(body(&response).contains("3.0 is not supported"));
+
+    let mut request = HttpRequest::new("GET", "/users");
+    request.set_query(Some("api-version=latest".to_string()));
+    assert_eq!(send(&app, request).status(), 400);
+
+    let response = send(&app, HttpRequest::new("GET", "/users"));
+    assert_eq!(response.status(), 400);
+    assert_eq!(body(&response), "API version is required");
+}
+
+#[test]
+fn default_version_applies_when_unspecified() {
+    let app = build_app(
+        ApiVersioning::new()
+            .read_from(ApiVersionReader::query("api-version"))
+            .default_version(V1),
+        "/users",
+    );
+
+    let response = send(&app, HttpRequest::new("GET", "/users"));
+    assert_eq!(body(&response), "users-v1:1.0");
+}
+
+#[test]
+fn deprecated_versions_emit_deprecation_and_sunset_headers() {
+    let app = build_app(
+        ApiVersioning::new()
+            .read_from(ApiVersionReader::query("api-version"))
+            .sunset(V1, "Wed, 31 Dec 2025 23:59:59 GMT"),
+        "/users",
+    );
+
+    let mut request = HttpRequest::new("GET", "/users");
+    request.set_query(Some("api-version=1".to_string()));
+    let response = send(&app, request);
+    assert_eq!(response.headers().get("deprecation"), Some("true"));
+    assert_eq!(
+        response.headers().get("sunset"),
+        Some("Wed, 31 Dec 2025 23:59:59 GMT")
+    );
+    assert_eq!(
+        response.headers().get("api-deprecated-versions"),
+        Some("1.0")
+    );
+
+    let mut request = HttpRequest::new("GET", "/users");
+    request.set_query(Some("api-version=2".to_string()));
+    let response = send(&app, request);
+    assert_eq!(response.headers().get("deprecation"), None);
+    assert_eq!(response.headers().get("sunset"), None);
+}
+
+#[test]
+fn version_neutral_endpoints_ignore_versioning() {
+    let app = build_app(
+        ApiVersioning::new().read_from(ApiVersionReader::query("api-version")),
+        "/users",
+    );
+
+    let response = send(&app, HttpRequest::new("GET", "/health"));
+    assert_eq!(response.status(), 200);
+    assert_eq!(body(&response), "health:none");
+    assert_eq!(response.headers().get("api-supported-versions"), None);
+}